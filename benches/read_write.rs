@@ -0,0 +1,90 @@
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use std::io::{BufReader, Cursor};
+use wavefront_rs::obj::entity::Entity;
+use wavefront_rs::obj::format_writer::FormatWriter;
+use wavefront_rs::obj::read_lexer::ReadLexer;
+#[cfg(feature = "rayon")]
+use wavefront_rs::obj::reader::par_read_all;
+use wavefront_rs::obj::reader::read_all;
+use wavefront_rs::obj::writer::write_all;
+
+const VERTICES: usize = 100_000;
+const FACES: usize = 200_000;
+
+/// Creates a document with `VERTICES` vertices, normals and textures followed by `FACES` triangles.
+fn document() -> String {
+    let mut result = String::new();
+    for i in 0..VERTICES {
+        let x = i as f64 * 0.001;
+        result.push_str(&format!("v {} {} {}\n", x, x + 0.5, x * 2.0));
+        result.push_str(&format!("vn {} {} 1\n", x, -x));
+        result.push_str(&format!("vt {} {}\n", x, 1.0 - x));
+    }
+    for i in 0..FACES {
+        let a = i % (VERTICES - 2) + 1;
+        result.push_str(&format!(
+            "f {}/{}/{} {}/{}/{} {}/{}/{}\n",
+            a,
+            a,
+            a,
+            a + 1,
+            a + 1,
+            a + 1,
+            a + 2,
+            a + 2,
+            a + 2
+        ));
+    }
+    result
+}
+
+fn bench_read(c: &mut Criterion) {
+    let input = document();
+    let mut group = c.benchmark_group("read");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(input.len() as u64));
+    group.bench_function("read_all", |b| {
+        b.iter(|| read_all(&mut BufReader::new(Cursor::new(&input))).unwrap())
+    });
+    group.bench_function("read_line", |b| {
+        b.iter(|| {
+            let mut reader = BufReader::new(Cursor::new(&input));
+            let mut count = 0;
+            while ReadLexer::read_line(&mut reader).is_ok() {
+                count += 1;
+            }
+            count
+        })
+    });
+    #[cfg(feature = "rayon")]
+    group.bench_function("par_read_all", |b| b.iter(|| par_read_all(&input).unwrap()));
+    group.finish();
+}
+
+fn bench_write(c: &mut Criterion) {
+    let input = document();
+    let entities: Vec<Entity> = read_all(&mut BufReader::new(Cursor::new(&input))).unwrap();
+    let mut group = c.benchmark_group("write");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(input.len() as u64));
+    group.bench_function("write_all", |b| {
+        b.iter(|| {
+            let mut result = Vec::with_capacity(input.len());
+            write_all(&mut result, &entities).unwrap();
+            result
+        })
+    });
+    group.bench_function("format_writer_write", |b| {
+        b.iter(|| {
+            let mut result = Vec::with_capacity(input.len());
+            for e in &entities {
+                FormatWriter::write_line(&mut result, e).unwrap();
+            }
+            result
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_read, bench_write);
+criterion_main!(benches);
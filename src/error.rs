@@ -1,6 +1,8 @@
 //! The error module contains a generic error that is used by the rest of the crate.
 //!
 
+use std::fmt;
+
 #[derive(Debug)]
 pub struct Error {
     details: String,
@@ -14,8 +16,209 @@ impl Error {
     }
 }
 
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.details)
+    }
+}
+
+impl std::error::Error for Error {}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Error {
         Error::new(&e.to_string())
     }
 }
+
+/// Describes the category of a `ReaderError`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReaderErrorKind {
+    /// The line starts with a token that is not known.
+    UnknownToken,
+    /// A field that is required by the statement is missing.
+    MissingField,
+    /// A field could not be parsed as number.
+    InvalidNumber,
+    /// An index refers to a declaration that does not exist (yet).
+    IndexOutOfRange,
+    /// The underlying reader failed.
+    Io,
+    /// The reader did not contain any more lines.
+    EndOfFile,
+}
+
+/// Error that is returned when reading a document failed.\
+/// Carries the category of the failure, the 1-based number of the offending line and the raw line itself.\
+/// Lines are counted from where reading started, which means that reading a single line always reports line 1.
+#[derive(Debug)]
+pub struct ReaderError {
+    kind: ReaderErrorKind,
+    line: usize,
+    raw: String,
+    details: String,
+}
+
+impl ReaderError {
+    pub fn new(kind: ReaderErrorKind, raw: &str, msg: &str) -> ReaderError {
+        ReaderError {
+            kind,
+            line: 1,
+            raw: raw.to_string(),
+            details: msg.to_string(),
+        }
+    }
+
+    /// Sets the 1-based number of the offending line.
+    pub fn with_line(mut self, line: usize) -> ReaderError {
+        self.line = line;
+        self
+    }
+
+    /// The category of the failure.
+    pub fn kind(&self) -> ReaderErrorKind {
+        self.kind
+    }
+
+    /// The 1-based number of the offending line.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The raw text of the offending line.
+    pub fn raw(&self) -> &str {
+        &self.raw
+    }
+}
+
+impl fmt::Display for ReaderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {} (\"{}\")", self.line, self.details, self.raw)
+    }
+}
+
+impl std::error::Error for ReaderError {}
+
+impl From<std::io::Error> for ReaderError {
+    fn from(e: std::io::Error) -> ReaderError {
+        ReaderError::new(ReaderErrorKind::Io, "", &e.to_string())
+    }
+}
+
+/// Error that is returned when writing a document failed.
+#[derive(Debug)]
+pub struct WriterError {
+    details: String,
+}
+
+impl WriterError {
+    pub fn new(msg: &str) -> WriterError {
+        WriterError {
+            details: msg.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for WriterError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.details)
+    }
+}
+
+impl std::error::Error for WriterError {}
+
+impl From<std::io::Error> for WriterError {
+    fn from(e: std::io::Error) -> WriterError {
+        WriterError::new(&e.to_string())
+    }
+}
+
+/// Describes which kind of reference a `ValidationError` refers to.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValidationErrorKind {
+    /// A vertex index is out of range.
+    Vertex,
+    /// A normal index is out of range.
+    Normal,
+    /// A texture index is out of range.
+    Texture,
+    /// A material name is declared more than once.
+    DuplicateMaterial,
+    /// An illumination model is outside of `0` to `10`.
+    IllumModel,
+}
+
+/// Error that is reported when validating a document found an invalid reference.\
+/// Carries the category of the reference, the 0-based position of the offending entity and the index as it
+/// is written in that entity. For illumination models the index is the mode and for duplicate materials it is the
+/// position of the first declaration and the name of the material is set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    kind: ValidationErrorKind,
+    entity: usize,
+    index: i64,
+    name: Option<String>,
+}
+
+impl ValidationError {
+    pub fn new(kind: ValidationErrorKind, entity: usize, index: i64) -> ValidationError {
+        ValidationError {
+            kind,
+            entity,
+            index,
+            name: None,
+        }
+    }
+
+    /// Sets the name the error refers to.
+    pub fn with_name(mut self, name: &str) -> ValidationError {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// The category of the invalid reference.
+    pub fn kind(&self) -> ValidationErrorKind {
+        self.kind
+    }
+
+    /// The 0-based position of the offending entity.
+    pub fn entity(&self) -> usize {
+        self.entity
+    }
+
+    /// The index as it is written in the offending entity.
+    pub fn index(&self) -> i64 {
+        self.index
+    }
+
+    /// The name the error refers to (e.g. of a duplicate material).
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind = match self.kind {
+            ValidationErrorKind::Vertex => "vertex index",
+            ValidationErrorKind::Normal => "normal index",
+            ValidationErrorKind::Texture => "texture index",
+            ValidationErrorKind::IllumModel => "illum mode",
+            ValidationErrorKind::DuplicateMaterial => {
+                return write!(
+                    f,
+                    "entity {}: material \"{}\" is already declared at entity {}",
+                    self.entity,
+                    self.name.as_deref().unwrap_or(""),
+                    self.index
+                )
+            }
+        };
+        write!(
+            f,
+            "entity {}: {} {} is out of range",
+            self.entity, kind, self.index
+        )
+    }
+}
+
+impl std::error::Error for ValidationError {}
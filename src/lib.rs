@@ -14,10 +14,41 @@ pub mod obj {
     //! [`wavefront obj`]: https://en.wikipedia.org/wiki/Wavefront_.obj_file
     //!
 
+    #[cfg(feature = "tokio")]
+    pub mod async_reader;
+    pub mod diff;
     pub mod entity;
+    pub mod export;
+    pub mod filter;
     pub mod format_writer;
+    pub mod geometry;
+    pub mod model;
+    pub mod optimize;
     pub mod read_lexer;
+    pub mod reader;
+    pub mod stats;
     pub mod string_lexer;
+    pub mod transform;
+    pub mod validate;
+    pub mod writer;
+
+    mod line_parser;
+}
+
+pub mod mtl {
+    //! The mtl module contains all the types that are used when working with the [`wavefront mtl`]
+    //! format including entities, lexer, writer etc.
+    //!
+    //! [`wavefront mtl`]: https://en.wikipedia.org/wiki/Wavefront_.obj_file#Material_template_library
+    //!
+
+    pub mod entity;
+    pub mod model;
+    pub mod read_lexer;
+    pub mod reader;
+    pub mod util;
+    pub mod validate;
+    pub mod writer;
 
     mod line_parser;
 }
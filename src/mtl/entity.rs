@@ -0,0 +1,592 @@
+//! Contains the entity types that are used when working with the [`wavefront mtl`] format.
+//!
+//! [`wavefront mtl`]: https://en.wikipedia.org/wiki/Wavefront_.obj_file#Material_template_library
+//!
+
+use crate::error::ReaderError;
+use crate::mtl::read_lexer::ReadLexer;
+use crate::mtl::writer::Writer;
+use std::fmt;
+use std::io::{BufReader, Cursor};
+use std::str::FromStr;
+
+/// Contains all possible entities that can exist in an MTL format.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Entity {
+    Comment {
+        content: String,
+    },
+    /// Starts the definition of a new material with the given name.\
+    /// Example: `newmtl bronze`
+    MaterialName {
+        name: String,
+    },
+    /// Example: `Ka 0.1 0.2 0.3`
+    AmbientColor {
+        r: f64,
+        g: f64,
+        b: f64,
+    },
+    /// The ambient color as spectral curve file with an optional multiplier `factor` (defaults to `1.0`).\
+    /// Example: `Ka spectral ambient.rfl 0.5`
+    AmbientColorSpectral {
+        file: String,
+        factor: Option<f64>,
+    },
+    /// The ambient color in CIE XYZ color space.\
+    /// Example: `Ka xyz 0.1 0.2 0.3`
+    AmbientColorXYZ {
+        x: f64,
+        y: f64,
+        z: f64,
+    },
+    /// Example: `Kd 0.1 0.2 0.3`
+    DiffuseColor {
+        r: f64,
+        g: f64,
+        b: f64,
+    },
+    /// The diffuse color as spectral curve file with an optional multiplier `factor` (defaults to `1.0`).\
+    /// Example: `Kd spectral diffuse.rfl 0.5`
+    DiffuseColorSpectral {
+        file: String,
+        factor: Option<f64>,
+    },
+    /// The diffuse color in CIE XYZ color space.\
+    /// Example: `Kd xyz 0.1 0.2 0.3`
+    DiffuseColorXYZ {
+        x: f64,
+        y: f64,
+        z: f64,
+    },
+    /// Example: `Ks 0.1 0.2 0.3`
+    SpecularColor {
+        r: f64,
+        g: f64,
+        b: f64,
+    },
+    /// The specular color as spectral curve file with an optional multiplier `factor` (defaults to `1.0`).\
+    /// Example: `Ks spectral specular.rfl 0.5`
+    SpecularColorSpectral {
+        file: String,
+        factor: Option<f64>,
+    },
+    /// The specular color in CIE XYZ color space.\
+    /// Example: `Ks xyz 0.1 0.2 0.3`
+    SpecularColorXYZ {
+        x: f64,
+        y: f64,
+        z: f64,
+    },
+    /// The emissive color of the material.\
+    /// Example: `Ke 0.1 0.2 0.3`
+    EmissiveColor {
+        r: f64,
+        g: f64,
+        b: f64,
+    },
+    /// The specular exponent of the material.\
+    /// Example: `Ns 10.0`
+    SpecularHighlights {
+        value: f64,
+    },
+    /// The index of refraction of the material.\
+    /// Example: `Ni 1.5`
+    OpticalDensity {
+        value: f64,
+    },
+    /// The sharpness of the reflections of the reflection map (`0` to `1000`, `60` by default).\
+    /// Example: `sharpness 60`
+    Sharpness {
+        value: f64,
+    },
+    /// Example: `d 0.9`
+    Dissolve {
+        value: f64,
+    },
+    /// The inverse of `Dissolve` (`Tr = 1 - d`).\
+    /// Example: `Tr 0.1`
+    InvertedDissolve {
+        value: f64,
+    },
+    /// The PBR roughness of the material.\
+    /// Example: `Pr 0.5`
+    Roughness {
+        value: f64,
+    },
+    /// The PBR metallic factor of the material.\
+    /// Example: `Pm 0.5`
+    Metallic {
+        value: f64,
+    },
+    /// The PBR sheen of the material.\
+    /// Example: `Ps 0.5`
+    Sheen {
+        value: f64,
+    },
+    /// The PBR clearcoat thickness of the material.\
+    /// Example: `Pc 0.5`
+    ClearcoatThickness {
+        value: f64,
+    },
+    /// The PBR clearcoat roughness of the material.\
+    /// Example: `Pcr 0.5`
+    ClearcoatRoughness {
+        value: f64,
+    },
+    /// The illumination model of the material.\
+    /// Example: `illum 2`
+    Illum {
+        mode: IllumModel,
+    },
+    /// Example: `map_Ka ambient.png`
+    TextureMapAmbient {
+        file: String,
+        options: TextureMapOptions,
+    },
+    /// Example: `map_Kd diffuse.png`
+    TextureMapDiffuse {
+        file: String,
+        options: TextureMapOptions,
+    },
+    /// Example: `map_Ks specular.png`
+    TextureMapSpecular {
+        file: String,
+        options: TextureMapOptions,
+    },
+    /// Example: `map_Ns highlights.png`
+    TextureMapSpecularHighlights {
+        file: String,
+        options: TextureMapOptions,
+    },
+    /// Example: `map_d alpha.png`
+    TextureMapAlpha {
+        file: String,
+        options: TextureMapOptions,
+    },
+    /// Example: `map_Pr roughness.png`
+    TextureMapRoughness {
+        file: String,
+        options: TextureMapOptions,
+    },
+    /// Example: `map_Pm metallic.png`
+    TextureMapMetallic {
+        file: String,
+        options: TextureMapOptions,
+    },
+    /// Example: `map_Ps sheen.png`
+    TextureMapSheen {
+        file: String,
+        options: TextureMapOptions,
+    },
+    /// Example: `map_Ke emissive.png`
+    TextureMapEmissive {
+        file: String,
+        options: TextureMapOptions,
+    },
+    /// Example: `norm normal.png`
+    NormalMap {
+        file: String,
+        options: TextureMapOptions,
+    },
+    /// Example: `bump bump.png`
+    BumpMap {
+        file: String,
+        options: TextureMapOptions,
+    },
+    /// Example: `disp displacement.png`
+    DisplacementMap {
+        file: String,
+        options: TextureMapOptions,
+    },
+    /// Example: `decal decal.png`
+    StencilDecalMap {
+        file: String,
+        options: TextureMapOptions,
+    },
+    /// A reflection map without `-type` option, which is a sphere map.\
+    /// Example: `refl reflection.png`
+    SphericalReflectionMap {
+        file: String,
+        options: TextureMapOptions,
+    },
+    /// A reflection map with an explicit `-type` option, which is either a sphere map or one side of a cube map.\
+    /// Example: `refl -type cube_top top.png`
+    ReflectionMap {
+        kind: ReflectionType,
+        file: String,
+        options: TextureMapOptions,
+    },
+    /// Example: `Tf 0.1 0.2 0.3`
+    TransmissionFilterColorRGB {
+        r: f64,
+        g: f64,
+        b: f64,
+    },
+    /// The transmission filter as spectral curve file with an optional multiplier `factor` (defaults to `1.0`).\
+    /// Example: `Tf spectral filter.rfl 0.5`
+    TransmissionFilterSpectral {
+        file: String,
+        factor: Option<f64>,
+    },
+    /// The transmission filter in CIE XYZ color space.\
+    /// Example: `Tf xyz 0.1 0.2 0.3`
+    TransmissionFilterXYZ {
+        x: f64,
+        y: f64,
+        z: f64,
+    },
+}
+
+/// The kind of an `Entity` without its fields, which is cheap to copy, compare and hash (e.g. for grouping
+/// entities by kind).\
+/// Example: `EntityKind::DiffuseColor` for `Kd 1 0 0`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EntityKind {
+    Comment,
+    MaterialName,
+    AmbientColor,
+    AmbientColorSpectral,
+    AmbientColorXYZ,
+    DiffuseColor,
+    DiffuseColorSpectral,
+    DiffuseColorXYZ,
+    SpecularColor,
+    SpecularColorSpectral,
+    SpecularColorXYZ,
+    EmissiveColor,
+    SpecularHighlights,
+    OpticalDensity,
+    Sharpness,
+    Dissolve,
+    InvertedDissolve,
+    Roughness,
+    Metallic,
+    Sheen,
+    ClearcoatThickness,
+    ClearcoatRoughness,
+    Illum,
+    TextureMapAmbient,
+    TextureMapDiffuse,
+    TextureMapSpecular,
+    TextureMapSpecularHighlights,
+    TextureMapAlpha,
+    TextureMapRoughness,
+    TextureMapMetallic,
+    TextureMapSheen,
+    TextureMapEmissive,
+    NormalMap,
+    BumpMap,
+    DisplacementMap,
+    StencilDecalMap,
+    SphericalReflectionMap,
+    ReflectionMap,
+    TransmissionFilterColorRGB,
+    TransmissionFilterSpectral,
+    TransmissionFilterXYZ,
+}
+
+impl Entity {
+    /// Returns the kind of the entity without its fields.
+    pub fn kind(&self) -> EntityKind {
+        match self {
+            Self::Comment { .. } => EntityKind::Comment,
+            Self::MaterialName { .. } => EntityKind::MaterialName,
+            Self::AmbientColor { .. } => EntityKind::AmbientColor,
+            Self::AmbientColorSpectral { .. } => EntityKind::AmbientColorSpectral,
+            Self::AmbientColorXYZ { .. } => EntityKind::AmbientColorXYZ,
+            Self::DiffuseColor { .. } => EntityKind::DiffuseColor,
+            Self::DiffuseColorSpectral { .. } => EntityKind::DiffuseColorSpectral,
+            Self::DiffuseColorXYZ { .. } => EntityKind::DiffuseColorXYZ,
+            Self::SpecularColor { .. } => EntityKind::SpecularColor,
+            Self::SpecularColorSpectral { .. } => EntityKind::SpecularColorSpectral,
+            Self::SpecularColorXYZ { .. } => EntityKind::SpecularColorXYZ,
+            Self::EmissiveColor { .. } => EntityKind::EmissiveColor,
+            Self::SpecularHighlights { .. } => EntityKind::SpecularHighlights,
+            Self::OpticalDensity { .. } => EntityKind::OpticalDensity,
+            Self::Sharpness { .. } => EntityKind::Sharpness,
+            Self::Dissolve { .. } => EntityKind::Dissolve,
+            Self::InvertedDissolve { .. } => EntityKind::InvertedDissolve,
+            Self::Roughness { .. } => EntityKind::Roughness,
+            Self::Metallic { .. } => EntityKind::Metallic,
+            Self::Sheen { .. } => EntityKind::Sheen,
+            Self::ClearcoatThickness { .. } => EntityKind::ClearcoatThickness,
+            Self::ClearcoatRoughness { .. } => EntityKind::ClearcoatRoughness,
+            Self::Illum { .. } => EntityKind::Illum,
+            Self::TextureMapAmbient { .. } => EntityKind::TextureMapAmbient,
+            Self::TextureMapDiffuse { .. } => EntityKind::TextureMapDiffuse,
+            Self::TextureMapSpecular { .. } => EntityKind::TextureMapSpecular,
+            Self::TextureMapSpecularHighlights { .. } => EntityKind::TextureMapSpecularHighlights,
+            Self::TextureMapAlpha { .. } => EntityKind::TextureMapAlpha,
+            Self::TextureMapRoughness { .. } => EntityKind::TextureMapRoughness,
+            Self::TextureMapMetallic { .. } => EntityKind::TextureMapMetallic,
+            Self::TextureMapSheen { .. } => EntityKind::TextureMapSheen,
+            Self::TextureMapEmissive { .. } => EntityKind::TextureMapEmissive,
+            Self::NormalMap { .. } => EntityKind::NormalMap,
+            Self::BumpMap { .. } => EntityKind::BumpMap,
+            Self::DisplacementMap { .. } => EntityKind::DisplacementMap,
+            Self::StencilDecalMap { .. } => EntityKind::StencilDecalMap,
+            Self::SphericalReflectionMap { .. } => EntityKind::SphericalReflectionMap,
+            Self::ReflectionMap { .. } => EntityKind::ReflectionMap,
+            Self::TransmissionFilterColorRGB { .. } => EntityKind::TransmissionFilterColorRGB,
+            Self::TransmissionFilterSpectral { .. } => EntityKind::TransmissionFilterSpectral,
+            Self::TransmissionFilterXYZ { .. } => EntityKind::TransmissionFilterXYZ,
+        }
+    }
+
+    /// The file of the entity if it is a texture map (e.g. `map_Kd`, `bump`, `disp` or `refl`) or `None`.
+    pub fn texture_map_file(&self) -> Option<&str> {
+        match self {
+            Self::TextureMapAmbient { file, .. }
+            | Self::TextureMapDiffuse { file, .. }
+            | Self::TextureMapSpecular { file, .. }
+            | Self::TextureMapSpecularHighlights { file, .. }
+            | Self::TextureMapAlpha { file, .. }
+            | Self::TextureMapRoughness { file, .. }
+            | Self::TextureMapMetallic { file, .. }
+            | Self::TextureMapSheen { file, .. }
+            | Self::TextureMapEmissive { file, .. }
+            | Self::NormalMap { file, .. }
+            | Self::BumpMap { file, .. }
+            | Self::DisplacementMap { file, .. }
+            | Self::StencilDecalMap { file, .. }
+            | Self::SphericalReflectionMap { file, .. }
+            | Self::ReflectionMap { file, .. } => Some(file),
+            _ => None,
+        }
+    }
+
+    /// Returns the MTL keyword of the entity as it is written at the start of its line (e.g. `Ka` or `map_Kd`).
+    pub fn token(&self) -> &str {
+        match self {
+            Self::Comment { .. } => "#",
+            Self::MaterialName { .. } => "newmtl",
+            Self::AmbientColor { .. } => "Ka",
+            Self::AmbientColorSpectral { .. } => "Ka",
+            Self::AmbientColorXYZ { .. } => "Ka",
+            Self::DiffuseColor { .. } => "Kd",
+            Self::DiffuseColorSpectral { .. } => "Kd",
+            Self::DiffuseColorXYZ { .. } => "Kd",
+            Self::SpecularColor { .. } => "Ks",
+            Self::SpecularColorSpectral { .. } => "Ks",
+            Self::SpecularColorXYZ { .. } => "Ks",
+            Self::EmissiveColor { .. } => "Ke",
+            Self::SpecularHighlights { .. } => "Ns",
+            Self::OpticalDensity { .. } => "Ni",
+            Self::Sharpness { .. } => "sharpness",
+            Self::Dissolve { .. } => "d",
+            Self::InvertedDissolve { .. } => "Tr",
+            Self::Roughness { .. } => "Pr",
+            Self::Metallic { .. } => "Pm",
+            Self::Sheen { .. } => "Ps",
+            Self::ClearcoatThickness { .. } => "Pc",
+            Self::ClearcoatRoughness { .. } => "Pcr",
+            Self::Illum { .. } => "illum",
+            Self::TextureMapAmbient { .. } => "map_Ka",
+            Self::TextureMapDiffuse { .. } => "map_Kd",
+            Self::TextureMapSpecular { .. } => "map_Ks",
+            Self::TextureMapSpecularHighlights { .. } => "map_Ns",
+            Self::TextureMapAlpha { .. } => "map_d",
+            Self::TextureMapRoughness { .. } => "map_Pr",
+            Self::TextureMapMetallic { .. } => "map_Pm",
+            Self::TextureMapSheen { .. } => "map_Ps",
+            Self::TextureMapEmissive { .. } => "map_Ke",
+            Self::NormalMap { .. } => "norm",
+            Self::BumpMap { .. } => "bump",
+            Self::DisplacementMap { .. } => "disp",
+            Self::StencilDecalMap { .. } => "decal",
+            Self::SphericalReflectionMap { .. } => "refl",
+            Self::ReflectionMap { .. } => "refl",
+            Self::TransmissionFilterColorRGB { .. } => "Tf",
+            Self::TransmissionFilterSpectral { .. } => "Tf",
+            Self::TransmissionFilterXYZ { .. } => "Tf",
+        }
+    }
+}
+
+impl fmt::Display for Entity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut result = Vec::new();
+        Writer::write(&mut result, self).map_err(|_| fmt::Error)?;
+        f.pad(std::str::from_utf8(&result).map_err(|_| fmt::Error)?)
+    }
+}
+
+impl FromStr for Entity {
+    type Err = ReaderError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ReadLexer::read_line(&mut BufReader::new(Cursor::new(s)))
+    }
+}
+
+/// The options of a texture map statement that precede the file name.\
+/// Options are written in the order of `order` and with as many values as they were read with, so that a
+/// statement is written as it was read apart from its whitespace and number format.\
+/// Example: `map_Kd -s 2 2 1 -o 0.5 0 0 diffuse.png`
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TextureMapOptions {
+    /// Horizontal texture blending (`-blendu on|off`).
+    pub blend_u: Option<bool>,
+    /// Vertical texture blending (`-blendv on|off`).
+    pub blend_v: Option<bool>,
+    /// The bump multiplier of bump maps (`-bm 0.5`).
+    pub bump_multiplier: Option<f64>,
+    /// Clamping of the texture coordinates (`-clamp on|off`).
+    pub clamp: Option<bool>,
+    /// The channel of the file that is used for scalar and bump textures (`-imfchan r|g|b|m|l|z`).
+    pub imfchan: Option<char>,
+    /// The resolution of the texture that is created (`-texres 512`).
+    pub texres: Option<u32>,
+    /// The offset of the texture origin (`-o u v w`) with the one to three values as written. Missing `v` and
+    /// `w` default to `0.0`.
+    pub offset: Option<Vec<f64>>,
+    /// The scale of the texture pattern (`-s u v w`) with the one to three values as written. Missing `v` and
+    /// `w` default to `1.0`.
+    pub scale: Option<Vec<f64>>,
+    /// The turbulence of the texture (`-t u v w`) with the one to three values as written. Missing `v` and `w`
+    /// default to `0.0`.
+    pub turbulence: Option<Vec<f64>>,
+    /// All other options (e.g. `-mm 0 1` or `-cc on`) verbatim and in order.
+    pub other: Vec<String>,
+    /// The names of all options (e.g. `-s` or `-mm`) in the order they were read.\
+    /// Options that are set but not listed are written after the listed ones, the known options in the order
+    /// of the fields above followed by `other`.
+    pub order: Vec<String>,
+}
+
+/// The `-type` option of a `refl` statement.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ReflectionType {
+    /// `sphere`: A single sphere map.
+    Sphere,
+    /// `cube_top`: The top side of a cube map.
+    CubeTop,
+    /// `cube_bottom`: The bottom side of a cube map.
+    CubeBottom,
+    /// `cube_front`: The front side of a cube map.
+    CubeFront,
+    /// `cube_back`: The back side of a cube map.
+    CubeBack,
+    /// `cube_left`: The left side of a cube map.
+    CubeLeft,
+    /// `cube_right`: The right side of a cube map.
+    CubeRight,
+}
+
+impl ReflectionType {
+    /// The name as it is written after `-type`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Sphere => "sphere",
+            Self::CubeTop => "cube_top",
+            Self::CubeBottom => "cube_bottom",
+            Self::CubeFront => "cube_front",
+            Self::CubeBack => "cube_back",
+            Self::CubeLeft => "cube_left",
+            Self::CubeRight => "cube_right",
+        }
+    }
+
+    /// The type with the given name (case-insensitive) or `None` if the name is not known.
+    pub fn from_name(name: &str) -> Option<Self> {
+        [
+            Self::Sphere,
+            Self::CubeTop,
+            Self::CubeBottom,
+            Self::CubeFront,
+            Self::CubeBack,
+            Self::CubeLeft,
+            Self::CubeRight,
+        ]
+        .iter()
+        .copied()
+        .find(|x| x.name().eq_ignore_ascii_case(name))
+    }
+}
+
+impl fmt::Display for ReflectionType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+/// The illumination model of an `illum` statement.\
+/// Known modes (`0` to `10`) are mapped to named variants while all other modes are preserved as `Unknown`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IllumModel {
+    /// `0`: Color on and ambient off.
+    ColorNoAmbient,
+    /// `1`: Color on and ambient on.
+    ColorAmbient,
+    /// `2`: Highlight on.
+    HighlightOn,
+    /// `3`: Reflection on and ray trace on.
+    ReflectionRayTrace,
+    /// `4`: Transparency with glass on, reflection with ray trace on.
+    GlassRayTrace,
+    /// `5`: Reflection with fresnel on and ray trace on.
+    FresnelRayTrace,
+    /// `6`: Transparency with refraction on, reflection with fresnel off and ray trace on.
+    RefractionRayTrace,
+    /// `7`: Transparency with refraction on, reflection with fresnel on and ray trace on.
+    RefractionFresnelRayTrace,
+    /// `8`: Reflection on and ray trace off.
+    ReflectionNoRayTrace,
+    /// `9`: Transparency with glass on, reflection with ray trace off.
+    GlassNoRayTrace,
+    /// `10`: Casts shadows onto invisible surfaces.
+    ShadowsOnInvisible,
+    /// Any mode outside of `0` to `10`.
+    Unknown(i64),
+}
+
+impl IllumModel {
+    /// The numeric mode as it is written in the `illum` statement.
+    pub fn mode(&self) -> i64 {
+        match self {
+            Self::ColorNoAmbient => 0,
+            Self::ColorAmbient => 1,
+            Self::HighlightOn => 2,
+            Self::ReflectionRayTrace => 3,
+            Self::GlassRayTrace => 4,
+            Self::FresnelRayTrace => 5,
+            Self::RefractionRayTrace => 6,
+            Self::RefractionFresnelRayTrace => 7,
+            Self::ReflectionNoRayTrace => 8,
+            Self::GlassNoRayTrace => 9,
+            Self::ShadowsOnInvisible => 10,
+            Self::Unknown(x) => *x,
+        }
+    }
+}
+
+impl From<i64> for IllumModel {
+    fn from(mode: i64) -> Self {
+        match mode {
+            0 => Self::ColorNoAmbient,
+            1 => Self::ColorAmbient,
+            2 => Self::HighlightOn,
+            3 => Self::ReflectionRayTrace,
+            4 => Self::GlassRayTrace,
+            5 => Self::FresnelRayTrace,
+            6 => Self::RefractionRayTrace,
+            7 => Self::RefractionFresnelRayTrace,
+            8 => Self::ReflectionNoRayTrace,
+            9 => Self::GlassNoRayTrace,
+            10 => Self::ShadowsOnInvisible,
+            x => Self::Unknown(x),
+        }
+    }
+}
+
+impl fmt::Display for IllumModel {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.mode())
+    }
+}
@@ -0,0 +1,486 @@
+use crate::error::{ReaderError, ReaderErrorKind};
+use crate::mtl::entity::{Entity, ReflectionType, TextureMapOptions};
+
+pub struct LineParser {}
+
+/// The form in which a color statement is specified.
+enum ColorKind {
+    Rgb,
+    Xyz,
+    Spectral,
+}
+
+impl LineParser {
+    pub fn parse_line(line: &str) -> Result<Entity, ReaderError> {
+        let line = line.trim();
+        if let Some(content) = line.strip_prefix('#') {
+            return Ok(Entity::Comment {
+                content: content.strip_prefix(' ').unwrap_or(content).to_owned(),
+            });
+        }
+        let statement = match line.find('#') {
+            Some(x) => &line[..x],
+            None => line,
+        };
+        let mut split = statement.split_whitespace();
+        let token = match split.next() {
+            Some(x) => x,
+            None => {
+                return Err(ReaderError::new(
+                    ReaderErrorKind::MissingField,
+                    line,
+                    "invalid line",
+                ))
+            }
+        };
+        match token.to_lowercase().as_str() {
+            "newmtl" => Ok(Entity::MaterialName {
+                name: Self::parse_name(&mut split, token, line)?,
+            }),
+            "ka" => match Self::parse_color_kind(&mut split) {
+                ColorKind::Spectral => {
+                    let (file, factor) = Self::parse_spectral(&mut split, token, line)?;
+                    Ok(Entity::AmbientColorSpectral { file, factor })
+                }
+                ColorKind::Xyz => {
+                    let (x, y, z) = Self::parse_rgb(&mut split, token, line)?;
+                    Ok(Entity::AmbientColorXYZ { x, y, z })
+                }
+                ColorKind::Rgb => {
+                    let (r, g, b) = Self::parse_rgb(&mut split, token, line)?;
+                    Ok(Entity::AmbientColor { r, g, b })
+                }
+            },
+            "kd" => match Self::parse_color_kind(&mut split) {
+                ColorKind::Spectral => {
+                    let (file, factor) = Self::parse_spectral(&mut split, token, line)?;
+                    Ok(Entity::DiffuseColorSpectral { file, factor })
+                }
+                ColorKind::Xyz => {
+                    let (x, y, z) = Self::parse_rgb(&mut split, token, line)?;
+                    Ok(Entity::DiffuseColorXYZ { x, y, z })
+                }
+                ColorKind::Rgb => {
+                    let (r, g, b) = Self::parse_rgb(&mut split, token, line)?;
+                    Ok(Entity::DiffuseColor { r, g, b })
+                }
+            },
+            "ks" => match Self::parse_color_kind(&mut split) {
+                ColorKind::Spectral => {
+                    let (file, factor) = Self::parse_spectral(&mut split, token, line)?;
+                    Ok(Entity::SpecularColorSpectral { file, factor })
+                }
+                ColorKind::Xyz => {
+                    let (x, y, z) = Self::parse_rgb(&mut split, token, line)?;
+                    Ok(Entity::SpecularColorXYZ { x, y, z })
+                }
+                ColorKind::Rgb => {
+                    let (r, g, b) = Self::parse_rgb(&mut split, token, line)?;
+                    Ok(Entity::SpecularColor { r, g, b })
+                }
+            },
+            "ke" => {
+                let (r, g, b) = Self::parse_rgb(&mut split, token, line)?;
+                Ok(Entity::EmissiveColor { r, g, b })
+            }
+            "tf" => match Self::parse_color_kind(&mut split) {
+                ColorKind::Spectral => {
+                    let (file, factor) = Self::parse_spectral(&mut split, token, line)?;
+                    Ok(Entity::TransmissionFilterSpectral { file, factor })
+                }
+                ColorKind::Xyz => {
+                    let (x, y, z) = Self::parse_rgb(&mut split, token, line)?;
+                    Ok(Entity::TransmissionFilterXYZ { x, y, z })
+                }
+                ColorKind::Rgb => {
+                    let (r, g, b) = Self::parse_rgb(&mut split, token, line)?;
+                    Ok(Entity::TransmissionFilterColorRGB { r, g, b })
+                }
+            },
+            "ns" => Ok(Entity::SpecularHighlights {
+                value: Self::parse_f64(&mut split, token, line)?,
+            }),
+            "ni" => Ok(Entity::OpticalDensity {
+                value: Self::parse_f64(&mut split, token, line)?,
+            }),
+            "sharpness" => Ok(Entity::Sharpness {
+                value: Self::parse_f64(&mut split, token, line)?,
+            }),
+            "d" => Ok(Entity::Dissolve {
+                value: Self::parse_f64(&mut split, token, line)?,
+            }),
+            "tr" => Ok(Entity::InvertedDissolve {
+                value: Self::parse_f64(&mut split, token, line)?,
+            }),
+            "pr" => Ok(Entity::Roughness {
+                value: Self::parse_f64(&mut split, token, line)?,
+            }),
+            "pm" => Ok(Entity::Metallic {
+                value: Self::parse_f64(&mut split, token, line)?,
+            }),
+            "ps" => Ok(Entity::Sheen {
+                value: Self::parse_f64(&mut split, token, line)?,
+            }),
+            "pc" => Ok(Entity::ClearcoatThickness {
+                value: Self::parse_f64(&mut split, token, line)?,
+            }),
+            "pcr" => Ok(Entity::ClearcoatRoughness {
+                value: Self::parse_f64(&mut split, token, line)?,
+            }),
+            "illum" => match split.next() {
+                Some(x) => match x.parse::<i64>() {
+                    Ok(mode) => Ok(Entity::Illum { mode: mode.into() }),
+                    Err(_) => Err(Self::invalid_data(
+                        ReaderErrorKind::InvalidNumber,
+                        token,
+                        line,
+                    )),
+                },
+                None => Err(Self::invalid_data(
+                    ReaderErrorKind::MissingField,
+                    token,
+                    line,
+                )),
+            },
+            "map_ka" => {
+                let (file, options) = Self::parse_texture_map(&mut split, token, line)?;
+                Ok(Entity::TextureMapAmbient { file, options })
+            }
+            "map_kd" => {
+                let (file, options) = Self::parse_texture_map(&mut split, token, line)?;
+                Ok(Entity::TextureMapDiffuse { file, options })
+            }
+            "map_ks" => {
+                let (file, options) = Self::parse_texture_map(&mut split, token, line)?;
+                Ok(Entity::TextureMapSpecular { file, options })
+            }
+            "map_ns" => {
+                let (file, options) = Self::parse_texture_map(&mut split, token, line)?;
+                Ok(Entity::TextureMapSpecularHighlights { file, options })
+            }
+            "map_d" => {
+                let (file, options) = Self::parse_texture_map(&mut split, token, line)?;
+                Ok(Entity::TextureMapAlpha { file, options })
+            }
+            "map_pr" => {
+                let (file, options) = Self::parse_texture_map(&mut split, token, line)?;
+                Ok(Entity::TextureMapRoughness { file, options })
+            }
+            "map_pm" => {
+                let (file, options) = Self::parse_texture_map(&mut split, token, line)?;
+                Ok(Entity::TextureMapMetallic { file, options })
+            }
+            "map_ps" => {
+                let (file, options) = Self::parse_texture_map(&mut split, token, line)?;
+                Ok(Entity::TextureMapSheen { file, options })
+            }
+            "map_ke" => {
+                let (file, options) = Self::parse_texture_map(&mut split, token, line)?;
+                Ok(Entity::TextureMapEmissive { file, options })
+            }
+            "norm" => {
+                let (file, options) = Self::parse_texture_map(&mut split, token, line)?;
+                Ok(Entity::NormalMap { file, options })
+            }
+            "bump" => {
+                let (file, options) = Self::parse_texture_map(&mut split, token, line)?;
+                Ok(Entity::BumpMap { file, options })
+            }
+            "disp" => {
+                let (file, options) = Self::parse_texture_map(&mut split, token, line)?;
+                Ok(Entity::DisplacementMap { file, options })
+            }
+            "decal" => {
+                let (file, options) = Self::parse_texture_map(&mut split, token, line)?;
+                Ok(Entity::StencilDecalMap { file, options })
+            }
+            "refl" => {
+                let (file, mut options) = Self::parse_texture_map(&mut split, token, line)?;
+                match Self::take_reflection_type(&mut options, token, line)? {
+                    Some(kind) => Ok(Entity::ReflectionMap {
+                        kind,
+                        file,
+                        options,
+                    }),
+                    None => Ok(Entity::SphericalReflectionMap { file, options }),
+                }
+            }
+            _ => Err(ReaderError::new(
+                ReaderErrorKind::UnknownToken,
+                line,
+                format!("unknown token \"{}\"", token).as_ref(),
+            )),
+        }
+    }
+
+    fn invalid_data(kind: ReaderErrorKind, token: &str, line: &str) -> ReaderError {
+        ReaderError::new(kind, line, format!("invalid data for {}", token).as_ref())
+    }
+
+    fn parse_name(
+        split: &mut std::str::SplitWhitespace,
+        token: &str,
+        line: &str,
+    ) -> Result<String, ReaderError> {
+        match split.next() {
+            Some(x) => Ok(x.to_owned()),
+            None => Err(Self::invalid_data(
+                ReaderErrorKind::MissingField,
+                token,
+                line,
+            )),
+        }
+    }
+
+    fn parse_f64(
+        split: &mut std::str::SplitWhitespace,
+        token: &str,
+        line: &str,
+    ) -> Result<f64, ReaderError> {
+        match split.next() {
+            Some(x) => x
+                .parse::<f64>()
+                .map_err(|_| Self::invalid_data(ReaderErrorKind::InvalidNumber, token, line)),
+            None => Err(Self::invalid_data(
+                ReaderErrorKind::MissingField,
+                token,
+                line,
+            )),
+        }
+    }
+
+    fn parse_texture_map(
+        split: &mut std::str::SplitWhitespace,
+        token: &str,
+        line: &str,
+    ) -> Result<(String, TextureMapOptions), ReaderError> {
+        let mut options = TextureMapOptions::default();
+        while let Some(option) = split.clone().next().filter(|x| Self::is_option(x)) {
+            split.next();
+            let values = Self::option_values(split, option);
+            options.order.push(option.to_owned());
+            match option {
+                "-blendu" => options.blend_u = Some(Self::parse_on_off(&values, token, line)?),
+                "-blendv" => options.blend_v = Some(Self::parse_on_off(&values, token, line)?),
+                "-clamp" => options.clamp = Some(Self::parse_on_off(&values, token, line)?),
+                "-imfchan" => options.imfchan = Some(Self::parse_channel(&values, token, line)?),
+                "-texres" => options.texres = Some(Self::parse_texres(&values, token, line)?),
+                "-bm" => {
+                    options.bump_multiplier =
+                        Some(Self::parse_values(&values, 1, 0.0, token, line)?[0])
+                }
+                "-o" => options.offset = Some(Self::parse_uvw(&values, token, line)?),
+                "-s" => options.scale = Some(Self::parse_uvw(&values, token, line)?),
+                "-t" => options.turbulence = Some(Self::parse_uvw(&values, token, line)?),
+                _ => {
+                    options.other.push(option.to_owned());
+                    options.other.extend(values.iter().map(|x| (*x).to_owned()));
+                }
+            }
+        }
+        let file = match Self::parse_remainder(split, line) {
+            Some(x) => x,
+            None => {
+                return Err(Self::invalid_data(
+                    ReaderErrorKind::MissingField,
+                    token,
+                    line,
+                ))
+            }
+        };
+        Ok((file, options))
+    }
+
+    /// Consumes the values of the given texture map option.\
+    /// Options with a single value take the next field, `-o`, `-s` and `-t` take up to three numbers and
+    /// unknown options take all following numbers and `on`/`off` switches, so that the fields after the
+    /// options remain for the file name.
+    fn option_values<'a>(split: &mut std::str::SplitWhitespace<'a>, option: &str) -> Vec<&'a str> {
+        let (max, is_value): (usize, fn(&str) -> bool) = match option {
+            "-blendu" | "-blendv" | "-clamp" | "-imfchan" | "-texres" | "-bm" | "-type" => {
+                (1, |x| !Self::is_option(x))
+            }
+            "-o" | "-s" | "-t" => (3, |x| x.parse::<f64>().is_ok()),
+            _ => (usize::MAX, |x| {
+                x.parse::<f64>().is_ok() || x == "on" || x == "off"
+            }),
+        };
+        let mut values = Vec::new();
+        while values.len() < max {
+            match split.clone().next() {
+                Some(x) if is_value(x) => values.push(x),
+                _ => break,
+            }
+            split.next();
+        }
+        values
+    }
+
+    /// Consumes all remaining fields and returns the rest of the given line starting with the next field, so
+    /// that file names keep their embedded spaces.\
+    /// Returns `None` if there are no fields left.
+    fn parse_remainder(split: &mut std::str::SplitWhitespace, line: &str) -> Option<String> {
+        let first = split.next()?;
+        let start = first.as_ptr() as usize - line.as_ptr() as usize;
+        let rest = &line[start..];
+        let rest = match rest.find('#') {
+            Some(x) => &rest[..x],
+            None => rest,
+        };
+        split.for_each(drop);
+        Some(rest.trim_end().to_owned())
+    }
+
+    /// Removes the `-type` option and its value from the other options of a `refl` statement.\
+    /// Returns `None` if the option is not set.
+    fn take_reflection_type(
+        options: &mut TextureMapOptions,
+        token: &str,
+        line: &str,
+    ) -> Result<Option<ReflectionType>, ReaderError> {
+        let i = match options.other.iter().position(|x| x == "-type") {
+            Some(x) => x,
+            None => return Ok(None),
+        };
+        let kind = match options.other.get(i + 1) {
+            Some(x) if !Self::is_option(x) => ReflectionType::from_name(x)
+                .ok_or_else(|| Self::invalid_data(ReaderErrorKind::InvalidNumber, token, line))?,
+            _ => {
+                return Err(Self::invalid_data(
+                    ReaderErrorKind::MissingField,
+                    token,
+                    line,
+                ))
+            }
+        };
+        options.other.drain(i..i + 2);
+        options.order.retain(|x| x != "-type");
+        Ok(Some(kind))
+    }
+
+    fn is_option(value: &str) -> bool {
+        let mut chars = value.chars();
+        chars.next() == Some('-') && chars.next().is_some_and(|x| x.is_alphabetic())
+    }
+
+    fn parse_on_off(values: &[&str], token: &str, line: &str) -> Result<bool, ReaderError> {
+        match values {
+            ["on"] => Ok(true),
+            ["off"] => Ok(false),
+            [] => Err(Self::invalid_data(
+                ReaderErrorKind::MissingField,
+                token,
+                line,
+            )),
+            _ => Err(Self::invalid_data(
+                ReaderErrorKind::InvalidNumber,
+                token,
+                line,
+            )),
+        }
+    }
+
+    fn parse_channel(values: &[&str], token: &str, line: &str) -> Result<char, ReaderError> {
+        match values {
+            [x] if ["r", "g", "b", "m", "l", "z"].contains(x) => Ok(x.chars().next().unwrap()),
+            [] => Err(Self::invalid_data(
+                ReaderErrorKind::MissingField,
+                token,
+                line,
+            )),
+            _ => Err(Self::invalid_data(
+                ReaderErrorKind::InvalidNumber,
+                token,
+                line,
+            )),
+        }
+    }
+
+    fn parse_texres(values: &[&str], token: &str, line: &str) -> Result<u32, ReaderError> {
+        match values {
+            [x] => x
+                .parse::<u32>()
+                .map_err(|_| Self::invalid_data(ReaderErrorKind::InvalidNumber, token, line)),
+            [] => Err(Self::invalid_data(
+                ReaderErrorKind::MissingField,
+                token,
+                line,
+            )),
+            _ => Err(Self::invalid_data(
+                ReaderErrorKind::InvalidNumber,
+                token,
+                line,
+            )),
+        }
+    }
+
+    fn parse_values(
+        values: &[&str],
+        count: usize,
+        default: f64,
+        token: &str,
+        line: &str,
+    ) -> Result<Vec<f64>, ReaderError> {
+        if values.is_empty() {
+            return Err(Self::invalid_data(
+                ReaderErrorKind::MissingField,
+                token,
+                line,
+            ));
+        }
+        if values.len() > count {
+            return Err(Self::invalid_data(
+                ReaderErrorKind::InvalidNumber,
+                token,
+                line,
+            ));
+        }
+        let mut result = vec![default; count];
+        for (i, x) in values.iter().enumerate() {
+            result[i] = x
+                .parse::<f64>()
+                .map_err(|_| Self::invalid_data(ReaderErrorKind::InvalidNumber, token, line))?;
+        }
+        Ok(result)
+    }
+
+    /// Parses the one to three values of `-o`, `-s` or `-t` as they were written.
+    fn parse_uvw(values: &[&str], token: &str, line: &str) -> Result<Vec<f64>, ReaderError> {
+        Self::parse_values(values, values.len().clamp(1, 3), 0.0, token, line)
+    }
+
+    /// Consumes the `spectral` or `xyz` keyword of a color statement if there is one.
+    fn parse_color_kind(split: &mut std::str::SplitWhitespace) -> ColorKind {
+        let kind = match split.clone().next() {
+            Some(x) if x.eq_ignore_ascii_case("spectral") => ColorKind::Spectral,
+            Some(x) if x.eq_ignore_ascii_case("xyz") => ColorKind::Xyz,
+            _ => return ColorKind::Rgb,
+        };
+        split.next();
+        kind
+    }
+
+    fn parse_spectral(
+        split: &mut std::str::SplitWhitespace,
+        token: &str,
+        line: &str,
+    ) -> Result<(String, Option<f64>), ReaderError> {
+        let file = Self::parse_name(split, token, line)?;
+        let factor = match split.clone().next() {
+            Some(_) => Some(Self::parse_f64(split, token, line)?),
+            None => None,
+        };
+        Ok((file, factor))
+    }
+
+    fn parse_rgb(
+        split: &mut std::str::SplitWhitespace,
+        token: &str,
+        line: &str,
+    ) -> Result<(f64, f64, f64), ReaderError> {
+        let r = Self::parse_f64(split, token, line)?;
+        let g = Self::parse_f64(split, token, line)?;
+        let b = Self::parse_f64(split, token, line)?;
+        Ok((r, g, b))
+    }
+}
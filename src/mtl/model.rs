@@ -0,0 +1,187 @@
+//! Contains a structured representation of an MTL document that groups its entities by material.
+//!
+
+use crate::error::ValidationError;
+use crate::mtl::entity::{Entity, IllumModel};
+use crate::mtl::validate::validate;
+
+/// Groups the entities of an MTL document by the `MaterialName` that precedes them.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MtlModel {
+    materials: Vec<Material>,
+}
+
+/// A material of an `MtlModel` with all entities that follow its `newmtl` statement in document order.\
+/// If a property is declared multiple times, the accessors return the last declaration.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Material {
+    name: String,
+    entities: Vec<Entity>,
+}
+
+impl MtlModel {
+    /// Walks the given entities in order and groups them under the preceding `MaterialName`.\
+    /// Entities before the first `MaterialName` are dropped.
+    pub fn from_entities(entities: &[Entity]) -> Self {
+        let mut materials: Vec<Material> = Vec::new();
+        for e in entities {
+            match e {
+                Entity::MaterialName { name } => materials.push(Material {
+                    name: name.clone(),
+                    entities: Vec::new(),
+                }),
+                _ => {
+                    if let Some(m) = materials.last_mut() {
+                        m.entities.push(e.clone());
+                    }
+                }
+            }
+        }
+        Self { materials }
+    }
+
+    /// Same as `from_entities` but fails with the errors of `validate` if a material name is declared more than
+    /// once.
+    pub fn from_entities_strict(entities: &[Entity]) -> Result<Self, Vec<ValidationError>> {
+        let errors = validate(entities);
+        if errors.is_empty() {
+            Ok(Self::from_entities(entities))
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Same as `from_entities` but keeps only the last declaration of each material name.\
+    /// Returns the model together with the errors of `validate` as warnings for the dropped declarations.
+    pub fn from_entities_lenient(entities: &[Entity]) -> (Self, Vec<ValidationError>) {
+        let mut model = Self::from_entities(entities);
+        let mut materials: Vec<Material> = Vec::new();
+        for m in model.materials.into_iter().rev() {
+            if !materials.iter().any(|x| x.name == m.name) {
+                materials.push(m);
+            }
+        }
+        materials.reverse();
+        model.materials = materials;
+        (model, validate(entities))
+    }
+
+    /// All materials with their names in document order.
+    pub fn materials(&self) -> impl Iterator<Item = (&str, &Material)> {
+        self.materials.iter().map(|x| (x.name(), x))
+    }
+
+    /// The last material with the given name or `None` if there is no such material.
+    pub fn material(&self, name: &str) -> Option<&Material> {
+        self.materials.iter().rev().find(|x| x.name == name)
+    }
+}
+
+impl Material {
+    /// The name of the material.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// All entities of the material in document order.
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    /// The last entity with the given token (case-insensitive) or `None` if there is no such entity.
+    pub fn get(&self, token: &str) -> Option<&Entity> {
+        self.entities
+            .iter()
+            .rev()
+            .find(|x| x.token().eq_ignore_ascii_case(token))
+    }
+
+    /// The RGB ambient color (`Ka`).
+    pub fn ambient_color(&self) -> Option<[f64; 3]> {
+        self.find(|e| match e {
+            Entity::AmbientColor { r, g, b } => Some([*r, *g, *b]),
+            _ => None,
+        })
+    }
+
+    /// The RGB diffuse color (`Kd`).
+    pub fn diffuse_color(&self) -> Option<[f64; 3]> {
+        self.find(|e| match e {
+            Entity::DiffuseColor { r, g, b } => Some([*r, *g, *b]),
+            _ => None,
+        })
+    }
+
+    /// The RGB specular color (`Ks`).
+    pub fn specular_color(&self) -> Option<[f64; 3]> {
+        self.find(|e| match e {
+            Entity::SpecularColor { r, g, b } => Some([*r, *g, *b]),
+            _ => None,
+        })
+    }
+
+    /// The RGB emissive color (`Ke`).
+    pub fn emissive_color(&self) -> Option<[f64; 3]> {
+        self.find(|e| match e {
+            Entity::EmissiveColor { r, g, b } => Some([*r, *g, *b]),
+            _ => None,
+        })
+    }
+
+    /// The specular exponent (`Ns`).
+    pub fn specular_highlights(&self) -> Option<f64> {
+        self.find(|e| match e {
+            Entity::SpecularHighlights { value } => Some(*value),
+            _ => None,
+        })
+    }
+
+    /// The index of refraction (`Ni`).
+    pub fn optical_density(&self) -> Option<f64> {
+        self.find(|e| match e {
+            Entity::OpticalDensity { value } => Some(*value),
+            _ => None,
+        })
+    }
+
+    /// The sharpness of the reflections (`sharpness`).
+    pub fn sharpness(&self) -> Option<f64> {
+        self.find(|e| match e {
+            Entity::Sharpness { value } => Some(*value),
+            _ => None,
+        })
+    }
+
+    /// The dissolve (`d`) or `1 - Tr` if only the inverted dissolve is declared.
+    pub fn dissolve(&self) -> Option<f64> {
+        self.find(|e| match e {
+            Entity::Dissolve { value } => Some(*value),
+            _ => None,
+        })
+        .or_else(|| {
+            self.find(|e| match e {
+                Entity::InvertedDissolve { value } => Some(1.0 - *value),
+                _ => None,
+            })
+        })
+    }
+
+    /// The illumination model (`illum`).
+    pub fn illum(&self) -> Option<IllumModel> {
+        self.find(|e| match e {
+            Entity::Illum { mode } => Some(*mode),
+            _ => None,
+        })
+    }
+
+    /// The file of the last texture map with the given token (case-insensitive, e.g. `map_Kd` or `bump`).
+    pub fn texture_map(&self, token: &str) -> Option<&str> {
+        self.get(token)?.texture_map_file()
+    }
+
+    fn find<T>(&self, f: impl Fn(&Entity) -> Option<T>) -> Option<T> {
+        self.entities.iter().rev().find_map(f)
+    }
+}
@@ -0,0 +1,48 @@
+//! Contains logic to read entities from a `BufRead` that returns MTL formatted strings.
+//!
+
+use crate::error::{ReaderError, ReaderErrorKind};
+use crate::mtl::entity::Entity;
+use crate::mtl::line_parser::LineParser;
+use std::io::BufRead;
+use std::result::Result;
+
+/// Will read from a given `BufRead` and parse entities.
+pub struct ReadLexer {}
+
+impl ReadLexer {
+    /// Will read from the given `BufRead`as long as it is not EOF.\
+    /// When an entity is parsed, the given callback is invoked and the entity is inserted into it as parameter.\
+    /// Blank lines are skipped.\
+    /// Will return `Ok(())` if successful or a `ReaderError` (if parsing failed).
+    pub fn read_to_end<R: BufRead>(
+        reader: &mut R,
+        callback: impl Fn(Entity),
+    ) -> Result<(), ReaderError> {
+        for (i, l) in reader.lines().enumerate() {
+            let s: String = l.map_err(|e| ReaderError::from(e).with_line(i + 1))?;
+            if s.trim().is_empty() {
+                continue;
+            }
+            callback(LineParser::parse_line(s.as_ref()).map_err(|e| e.with_line(i + 1))?);
+        }
+        Ok(())
+    }
+
+    /// Will read from the given `BufRead` until the first encountered linebreak.\
+    /// The line break itself (`\n` or `\r\n`) is not part of the parsed line.\
+    /// Leading whitespace, repeated whitespace between fields and trailing comments are tolerated.\
+    /// Will return `Ok(Entity)` if successful or a `ReaderError` (if parsing failed).
+    pub fn read_line<R: BufRead>(reader: &mut R) -> Result<Entity, ReaderError> {
+        let value = &mut String::new();
+        if reader.read_line(value)? > 0 {
+            LineParser::parse_line(value.trim_end_matches(&['\r', '\n'][..]))
+        } else {
+            Err(ReaderError::new(
+                ReaderErrorKind::EndOfFile,
+                "",
+                "reached EOF",
+            ))
+        }
+    }
+}
@@ -0,0 +1,36 @@
+//! Contains logic to read whole MTL documents at once.
+//!
+
+use crate::error::ReaderError;
+use crate::mtl::entity::Entity;
+use crate::mtl::line_parser::LineParser;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::result::Result;
+
+/// Will read from the given `BufRead` as long as it is not EOF and collect all parsed entities in file order.\
+/// Blank lines are skipped and a UTF-8 byte order mark at the start of the first line is ignored.\
+/// Will return `Ok(Vec<Entity>)` if successful or a `ReaderError` containing the failing line number.
+pub fn read_all<R: BufRead>(reader: &mut R) -> Result<Vec<Entity>, ReaderError> {
+    let mut entities = Vec::new();
+    for (i, l) in reader.lines().enumerate() {
+        let s: String = l.map_err(|e| ReaderError::from(e).with_line(i + 1))?;
+        let s = match s.strip_prefix('\u{FEFF}') {
+            Some(x) if i == 0 => x,
+            _ => s.as_str(),
+        };
+        if s.trim().is_empty() {
+            continue;
+        }
+        entities.push(LineParser::parse_line(s).map_err(|e| e.with_line(i + 1))?);
+    }
+    Ok(entities)
+}
+
+/// Same as `read_all` but opens the file at the given path and reads it buffered.\
+/// Will return a `ReaderError` of kind `Io` if the file cannot be opened.
+pub fn read_file<P: AsRef<Path>>(path: P) -> Result<Vec<Entity>, ReaderError> {
+    let file = File::open(path)?;
+    read_all(&mut BufReader::new(file))
+}
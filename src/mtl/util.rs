@@ -0,0 +1,65 @@
+//! Contains helpers to reconcile redundant statements of an MTL document.
+//!
+
+use crate::mtl::entity::Entity;
+
+/// Describes which of the two transparency statements is kept by `normalize_transparency`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Transparency {
+    /// Keeps the dissolve (`d`).
+    #[default]
+    Dissolve,
+    /// Keeps the inverted dissolve (`Tr = 1 - d`).
+    InvertedDissolve,
+}
+
+/// Rewrites the first `Dissolve` or `InvertedDissolve` of each material into the representation to keep and
+/// removes all others, so that each material declares its transparency once.\
+/// The value of a material is taken from its last statement of the kept representation or, if there is none,
+/// computed from its last statement of the other one. Statements before the first `MaterialName` are treated
+/// like a material of their own.\
+/// Example (`Transparency::Dissolve`): `Tr 0.25` becomes `d 0.75` and `d 0.5` followed by `Tr 0.5` becomes
+/// `d 0.5`.
+pub fn normalize_transparency(entities: &mut Vec<Entity>, keep: Transparency) {
+    let source = std::mem::take(entities);
+    let mut start = 0;
+    while start < source.len() {
+        let end = source[start + 1..]
+            .iter()
+            .position(|e| matches!(e, Entity::MaterialName { .. }))
+            .map_or(source.len(), |x| start + 1 + x);
+        normalize_material(&source[start..end], keep, entities);
+        start = end;
+    }
+}
+
+/// Appends the given entities of one material to the result with its transparency normalized.
+fn normalize_material(entities: &[Entity], keep: Transparency, result: &mut Vec<Entity>) {
+    let (kept, other) = entities
+        .iter()
+        .fold((None, None), |(kept, other), e| match (e, keep) {
+            (Entity::Dissolve { value }, Transparency::Dissolve)
+            | (Entity::InvertedDissolve { value }, Transparency::InvertedDissolve) => {
+                (Some(*value), other)
+            }
+            (Entity::Dissolve { value }, Transparency::InvertedDissolve)
+            | (Entity::InvertedDissolve { value }, Transparency::Dissolve) => {
+                (kept, Some(1.0 - *value))
+            }
+            _ => (kept, other),
+        });
+    let mut value = kept.or(other);
+    for e in entities {
+        match e {
+            Entity::Dissolve { .. } | Entity::InvertedDissolve { .. } => {
+                if let Some(value) = value.take() {
+                    result.push(match keep {
+                        Transparency::Dissolve => Entity::Dissolve { value },
+                        Transparency::InvertedDissolve => Entity::InvertedDissolve { value },
+                    });
+                }
+            }
+            _ => result.push(e.clone()),
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! Contains the logic to check the consistency of an MTL document.
+//!
+
+use crate::error::{ValidationError, ValidationErrorKind};
+use crate::mtl::entity::{Entity, IllumModel};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// A texture map file that is referenced by an MTL document but does not exist, see `check_map_files`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MissingFile {
+    /// The 0-based position of the referencing entity.
+    pub entity: usize,
+    /// The file as it is written in the referencing entity.
+    pub file: String,
+    /// The path the file was resolved to.
+    pub path: PathBuf,
+}
+
+/// Checks that every `MaterialName` is declared only once.\
+/// Returns an error for every repeated declaration in document order or an empty vec if the document is valid.
+/// The errors carry the position of the repeated and of the first declaration as well as the name.
+pub fn validate(entities: &[Entity]) -> Vec<ValidationError> {
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    let mut errors = Vec::new();
+    for (position, e) in entities.iter().enumerate() {
+        if let Entity::MaterialName { name } = e {
+            match seen.get(name.as_str()) {
+                Some(first) => errors.push(
+                    ValidationError::new(
+                        ValidationErrorKind::DuplicateMaterial,
+                        position,
+                        *first as i64,
+                    )
+                    .with_name(name),
+                ),
+                None => {
+                    seen.insert(name, position);
+                }
+            }
+        }
+    }
+    errors
+}
+
+/// Checks that every `Illum` is a known mode (`0` to `10`).\
+/// Returns an error carrying the position and the mode for every unknown mode in document order or an empty vec
+/// if all modes are known.
+pub fn validate_illum(entities: &[Entity]) -> Vec<ValidationError> {
+    entities
+        .iter()
+        .enumerate()
+        .filter_map(|(position, e)| match e {
+            Entity::Illum {
+                mode: IllumModel::Unknown(mode),
+            } => Some(ValidationError::new(
+                ValidationErrorKind::IllumModel,
+                position,
+                *mode,
+            )),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Resolves the file of every texture map (see `Entity::texture_map_file`) relative to the given base directory
+/// (usually the directory of the MTL file) and checks that it is an existing file.\
+/// Returns a `MissingFile` for every texture map whose file does not exist in document order or an empty vec if
+/// all files exist. Absolute file names are checked as they are.
+pub fn check_map_files(entities: &[Entity], base_dir: &Path) -> Vec<MissingFile> {
+    entities
+        .iter()
+        .enumerate()
+        .filter_map(|(entity, e)| {
+            let file = e.texture_map_file()?;
+            let path = base_dir.join(file);
+            if path.is_file() {
+                None
+            } else {
+                Some(MissingFile {
+                    entity,
+                    file: file.to_owned(),
+                    path,
+                })
+            }
+        })
+        .collect()
+}
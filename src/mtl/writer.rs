@@ -0,0 +1,340 @@
+//! Contains the logic to transform entities to MTL formatted strings.
+//!
+
+use crate::error::WriterError;
+use crate::mtl::entity::{Entity, TextureMapOptions};
+use crate::obj::format_writer::float;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+
+/// Writes all given entities to the given `Write` trait, each on its own line with a terminating `\n`.\
+/// The writer is flushed afterwards.\
+/// Will return `Ok(())` if successful or a `WriterError` (if writing failed).
+pub fn write_all<W: Write>(writer: &mut W, entities: &[Entity]) -> Result<(), WriterError> {
+    for e in entities {
+        Writer::write(writer, e)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Same as `write_all` but creates (or truncates) the file at the given path and writes it buffered.
+pub fn write_file<P: AsRef<Path>>(path: P, entities: &[Entity]) -> Result<(), WriterError> {
+    let file = File::create(path)?;
+    write_all(&mut BufWriter::new(file), entities)
+}
+
+/// Same as `write_all` but writes the entities according to the given `DisplayOptions`.\
+/// Will return the 0-based positions of all colors with a component outside of `[0, 1]` and all clamped scalars
+/// (see `Writer::write_with_options`), which callers should report as warnings, or a `WriterError` (if
+/// writing failed).
+pub fn write_all_with<W: Write>(
+    writer: &mut W,
+    entities: &[Entity],
+    options: &DisplayOptions,
+) -> Result<Vec<usize>, WriterError> {
+    let mut warnings = Vec::new();
+    for (i, e) in entities.iter().enumerate() {
+        if Writer::write_with_options(writer, e, options)? {
+            warnings.push(i);
+        }
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(warnings)
+}
+
+/// Describes how RGB color components outside of `[0, 1]` are written.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ColorRange {
+    /// Clamps every component into `[0, 1]`.
+    #[default]
+    Clamp,
+    /// Divides all components of a color by `255` if any of them exceeds `1` and clamps the result, which
+    /// converts colors given as 0-255 integers.
+    Normalize,
+    /// Writes every component unchanged.
+    Keep,
+}
+
+/// Options that control how entities are written.
+#[derive(Debug, Clone, Default)]
+pub struct DisplayOptions {
+    /// The number of decimal places of float fields or `None` for the default formatting.
+    pub float_precision: Option<usize>,
+    /// How the components of `Ka`, `Kd`, `Ks`, `Ke` and `Tf` RGB colors outside of `[0, 1]` are written.
+    pub colors: ColorRange,
+    /// Clamps scalars outside of their conventional range, which is `[0, 1000]` for `Ns` and `sharpness` and
+    /// `[0, 1]` for `d`, `Tr`, `Pr`, `Pm`, `Ps`, `Pc` and `Pcr`.\
+    /// Scalars are written unchanged by default.
+    pub clamp_scalars: bool,
+}
+
+/// Will write entities to a `Write` trait.
+pub struct Writer {}
+
+impl Writer {
+    /// Writes the given entity to the given `Write` trait as MTL format representation of that `Entity`.\
+    /// Will return `Ok(())` if successful or a `WriterError` (if writing failed).
+    pub fn write<W: Write>(writer: &mut W, e: &Entity) -> Result<(), WriterError> {
+        Self::write_entity(writer, e, None)
+    }
+
+    /// Writes the given entity like `write` but with all float fields formatted with the given number of
+    /// decimal places.\
+    /// Example (3 decimals): `Kd 0.100 0.200 0.300`
+    pub fn write_with_precision<W: Write>(
+        writer: &mut W,
+        e: &Entity,
+        decimals: usize,
+    ) -> Result<(), WriterError> {
+        Self::write_entity(writer, e, Some(decimals))
+    }
+
+    /// Writes the given entity like `write` but according to the given `DisplayOptions`.\
+    /// Will return `Ok(true)` if the entity is an RGB color with a component outside of `[0, 1]` (whether it has
+    /// been adjusted or not) or a scalar that has been clamped, `Ok(false)` otherwise or a `WriterError` (if
+    /// writing failed).\
+    /// Example (`ColorRange::Normalize`): `Kd 255 127.5 0` is written as `Kd 1 0.5 0`\
+    /// Example (`clamp_scalars`): `d 1.5` is written as `d 1`
+    pub fn write_with_options<W: Write>(
+        writer: &mut W,
+        e: &Entity,
+        options: &DisplayOptions,
+    ) -> Result<bool, WriterError> {
+        if let (true, Some((min, max))) = (options.clamp_scalars, Self::scalar_range(e)) {
+            let mut adjusted = e.clone();
+            if let Entity::SpecularHighlights { value }
+            | Entity::Sharpness { value }
+            | Entity::Dissolve { value }
+            | Entity::InvertedDissolve { value }
+            | Entity::Roughness { value }
+            | Entity::Metallic { value }
+            | Entity::Sheen { value }
+            | Entity::ClearcoatThickness { value }
+            | Entity::ClearcoatRoughness { value } = &mut adjusted
+            {
+                if !(min..=max).contains(value) {
+                    *value = value.clamp(min, max);
+                    Self::write_entity(writer, &adjusted, options.float_precision)?;
+                    return Ok(true);
+                }
+            }
+        }
+        let color = match e {
+            Entity::AmbientColor { r, g, b }
+            | Entity::DiffuseColor { r, g, b }
+            | Entity::SpecularColor { r, g, b }
+            | Entity::EmissiveColor { r, g, b }
+            | Entity::TransmissionFilterColorRGB { r, g, b } => [*r, *g, *b],
+            _ => {
+                Self::write_entity(writer, e, options.float_precision)?;
+                return Ok(false);
+            }
+        };
+        let out_of_range = color.iter().any(|x| !(0.0..=1.0).contains(x));
+        let [r, g, b] = match options.colors {
+            _ if !out_of_range => color,
+            ColorRange::Keep => color,
+            ColorRange::Clamp => color.map(|x| x.clamp(0.0, 1.0)),
+            ColorRange::Normalize if color.iter().any(|x| *x > 1.0) => {
+                color.map(|x| (x / 255.0).clamp(0.0, 1.0))
+            }
+            ColorRange::Normalize => color.map(|x| x.clamp(0.0, 1.0)),
+        };
+        let adjusted = match e {
+            Entity::AmbientColor { .. } => Entity::AmbientColor { r, g, b },
+            Entity::DiffuseColor { .. } => Entity::DiffuseColor { r, g, b },
+            Entity::SpecularColor { .. } => Entity::SpecularColor { r, g, b },
+            Entity::EmissiveColor { .. } => Entity::EmissiveColor { r, g, b },
+            _ => Entity::TransmissionFilterColorRGB { r, g, b },
+        };
+        Self::write_entity(writer, &adjusted, options.float_precision)?;
+        Ok(out_of_range)
+    }
+
+    /// The conventional range of the scalar of the given entity (see `DisplayOptions::clamp_scalars`) or `None`
+    /// if it has no bounded scalar.
+    fn scalar_range(e: &Entity) -> Option<(f64, f64)> {
+        match e {
+            Entity::SpecularHighlights { .. } | Entity::Sharpness { .. } => Some((0.0, 1000.0)),
+            Entity::Dissolve { .. }
+            | Entity::InvertedDissolve { .. }
+            | Entity::Roughness { .. }
+            | Entity::Metallic { .. }
+            | Entity::Sheen { .. }
+            | Entity::ClearcoatThickness { .. }
+            | Entity::ClearcoatRoughness { .. } => Some((0.0, 1.0)),
+            _ => None,
+        }
+    }
+
+    fn write_entity<W: Write>(
+        writer: &mut W,
+        e: &Entity,
+        precision: Option<usize>,
+    ) -> Result<(), WriterError> {
+        let f = |x: &f64| float(*x, precision);
+        match e {
+            Entity::Comment { content } if content.is_empty() => {
+                writer.write_all(e.token().as_ref())?;
+            }
+            Entity::Comment { content } => {
+                writer.write_all(format!("{} {}", e.token(), content).as_ref())?;
+            }
+            Entity::MaterialName { name } => {
+                writer.write_all(format!("{} {}", e.token(), name).as_ref())?;
+            }
+            Entity::AmbientColor { r, g, b }
+            | Entity::DiffuseColor { r, g, b }
+            | Entity::SpecularColor { r, g, b }
+            | Entity::EmissiveColor { r, g, b }
+            | Entity::TransmissionFilterColorRGB { r, g, b } => {
+                writer.write_all(format!("{} {} {} {}", e.token(), f(r), f(g), f(b)).as_ref())?;
+            }
+            Entity::SpecularHighlights { value }
+            | Entity::OpticalDensity { value }
+            | Entity::Sharpness { value }
+            | Entity::Dissolve { value }
+            | Entity::InvertedDissolve { value }
+            | Entity::Roughness { value }
+            | Entity::Metallic { value }
+            | Entity::Sheen { value }
+            | Entity::ClearcoatThickness { value }
+            | Entity::ClearcoatRoughness { value } => {
+                writer.write_all(format!("{} {}", e.token(), f(value)).as_ref())?;
+            }
+            Entity::AmbientColorSpectral { file, factor }
+            | Entity::DiffuseColorSpectral { file, factor }
+            | Entity::SpecularColorSpectral { file, factor }
+            | Entity::TransmissionFilterSpectral { file, factor } => {
+                writer.write_all(format!("{} spectral {}", e.token(), file).as_ref())?;
+                if let Some(x) = factor {
+                    writer.write_all(format!(" {}", f(x)).as_ref())?;
+                }
+            }
+            Entity::AmbientColorXYZ { x, y, z }
+            | Entity::DiffuseColorXYZ { x, y, z }
+            | Entity::SpecularColorXYZ { x, y, z }
+            | Entity::TransmissionFilterXYZ { x, y, z } => {
+                writer
+                    .write_all(format!("{} xyz {} {} {}", e.token(), f(x), f(y), f(z)).as_ref())?;
+            }
+            Entity::Illum { mode } => {
+                writer.write_all(format!("{} {}", e.token(), mode).as_ref())?;
+            }
+            Entity::TextureMapAmbient { file, options }
+            | Entity::TextureMapDiffuse { file, options }
+            | Entity::TextureMapSpecular { file, options }
+            | Entity::TextureMapSpecularHighlights { file, options }
+            | Entity::TextureMapAlpha { file, options }
+            | Entity::TextureMapRoughness { file, options }
+            | Entity::TextureMapMetallic { file, options }
+            | Entity::TextureMapSheen { file, options }
+            | Entity::TextureMapEmissive { file, options }
+            | Entity::NormalMap { file, options }
+            | Entity::BumpMap { file, options }
+            | Entity::DisplacementMap { file, options }
+            | Entity::StencilDecalMap { file, options }
+            | Entity::SphericalReflectionMap { file, options } => {
+                writer.write_all(e.token().as_ref())?;
+                Self::write_texture_map_options(writer, options, precision)?;
+                writer.write_all(format!(" {}", file).as_ref())?;
+            }
+            Entity::ReflectionMap {
+                kind,
+                file,
+                options,
+            } => {
+                writer.write_all(format!("{} -type {}", e.token(), kind).as_ref())?;
+                Self::write_texture_map_options(writer, options, precision)?;
+                writer.write_all(format!(" {}", file).as_ref())?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the options in the order of `TextureMapOptions::order` followed by the set options that are not
+    /// listed there.
+    fn write_texture_map_options<W: Write>(
+        writer: &mut W,
+        options: &TextureMapOptions,
+        precision: Option<usize>,
+    ) -> Result<(), WriterError> {
+        let known = [
+            "-blendu", "-blendv", "-bm", "-clamp", "-imfchan", "-texres", "-o", "-s", "-t",
+        ];
+        let mut other: Vec<Option<&[String]>> = Vec::new();
+        let mut start = 0;
+        for i in 1..=options.other.len() {
+            if i == options.other.len() || is_option(&options.other[i]) {
+                other.push(Some(&options.other[start..i]));
+                start = i;
+            }
+        }
+        let mut written: Vec<&str> = Vec::new();
+        for name in &options.order {
+            if known.contains(&name.as_str()) {
+                if written.contains(&name.as_str()) {
+                    continue;
+                }
+                written.push(name);
+                if let Some(x) = Self::texture_map_option(options, name, precision) {
+                    writer.write_all(format!(" {}", x).as_ref())?;
+                }
+            } else if let Some(x) = other
+                .iter_mut()
+                .find(|x| x.is_some_and(|x| x[0] == *name))
+                .and_then(|x| x.take())
+            {
+                writer.write_all(format!(" {}", x.join(" ")).as_ref())?;
+            }
+        }
+        for name in known.iter().filter(|x| !written.contains(x)) {
+            if let Some(x) = Self::texture_map_option(options, name, precision) {
+                writer.write_all(format!(" {}", x).as_ref())?;
+            }
+        }
+        for x in other.into_iter().flatten() {
+            writer.write_all(format!(" {}", x.join(" ")).as_ref())?;
+        }
+        Ok(())
+    }
+
+    /// Formats the known texture map option with the given name or returns `None` if it is not set.
+    fn texture_map_option(
+        options: &TextureMapOptions,
+        name: &str,
+        precision: Option<usize>,
+    ) -> Option<String> {
+        let on_off = |x: bool| if x { "on" } else { "off" };
+        let uvw = |x: &Option<Vec<f64>>| {
+            x.as_ref().map(|x| {
+                let values: Vec<String> = x.iter().map(|x| float(*x, precision)).collect();
+                format!("{} {}", name, values.join(" "))
+            })
+        };
+        match name {
+            "-blendu" => options.blend_u.map(|x| format!("-blendu {}", on_off(x))),
+            "-blendv" => options.blend_v.map(|x| format!("-blendv {}", on_off(x))),
+            "-bm" => options
+                .bump_multiplier
+                .map(|x| format!("-bm {}", float(x, precision))),
+            "-clamp" => options.clamp.map(|x| format!("-clamp {}", on_off(x))),
+            "-imfchan" => options.imfchan.map(|x| format!("-imfchan {}", x)),
+            "-texres" => options.texres.map(|x| format!("-texres {}", x)),
+            "-o" => uvw(&options.offset),
+            "-s" => uvw(&options.scale),
+            "-t" => uvw(&options.turbulence),
+            _ => None,
+        }
+    }
+}
+
+/// Whether the given field of `TextureMapOptions::other` starts a new option (e.g. `-mm` but not `-0.5`).
+fn is_option(value: &str) -> bool {
+    let mut chars = value.chars();
+    chars.next() == Some('-') && chars.next().is_some_and(|x| x.is_alphabetic())
+}
@@ -0,0 +1,30 @@
+//! Contains logic to read entities from an `AsyncBufRead` that returns OBJ formatted strings.\
+//! Requires the `tokio` feature.
+//!
+
+use crate::error::ReaderError;
+use crate::obj::entity::Entity;
+use crate::obj::line_parser::LineParser;
+use crate::obj::read_lexer::ReadOptions;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt};
+
+/// Will read from the given `AsyncBufRead` until the first encountered linebreak.\
+/// The line is parsed the same way as by `ReadLexer::read_line`.\
+/// Will return `Ok(Some(Entity))` if successful, `Ok(None)` at EOF or a `ReaderError` (if parsing failed).
+pub async fn read_line<R: AsyncBufRead + Unpin>(
+    reader: &mut R,
+) -> Result<Option<Entity>, ReaderError> {
+    let mut value = String::new();
+    if reader.read_line(&mut value).await? > 0 {
+        LineParser::strip_line_ending(&mut value);
+        while LineParser::begin_continuation(&mut value) {
+            if reader.read_line(&mut value).await? == 0 {
+                break;
+            }
+            LineParser::strip_line_ending(&mut value);
+        }
+        LineParser::parse(&value, &ReadOptions::default()).map(Some)
+    } else {
+        Ok(None)
+    }
+}
@@ -0,0 +1,67 @@
+//! Contains the logic to compare two OBJ documents entity by entity.
+//!
+
+use crate::obj::entity::Entity;
+
+/// The epsilon `diff` compares float fields with.
+pub const DEFAULT_EPSILON: f64 = 1e-9;
+
+/// Describes a difference between two entity lists at the given position.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Difference {
+    /// The second list contains an entity at a position after the end of the first list.
+    Added { index: usize, entity: Entity },
+    /// The first list contains an entity at a position after the end of the second list.
+    Removed { index: usize, entity: Entity },
+    /// Both lists contain an entity at the position but they are not equal.
+    Changed {
+        index: usize,
+        old: Entity,
+        new: Entity,
+    },
+}
+
+impl Difference {
+    /// The position of the difference in the compared lists.
+    pub fn index(&self) -> usize {
+        match self {
+            Self::Added { index, .. }
+            | Self::Removed { index, .. }
+            | Self::Changed { index, .. } => *index,
+        }
+    }
+}
+
+/// Compares the given entity lists position by position with `DEFAULT_EPSILON` as tolerance of float fields.\
+/// Returns the differences ordered by position, which is empty if both lists are equal.
+pub fn diff(a: &[Entity], b: &[Entity]) -> Vec<Difference> {
+    diff_with_epsilon(a, b, DEFAULT_EPSILON)
+}
+
+/// Same as `diff` but entities are compared with `Entity::approx_eq` and the given epsilon, so that documents
+/// which were written with a different precision can be compared.
+pub fn diff_with_epsilon(a: &[Entity], b: &[Entity], epsilon: f64) -> Vec<Difference> {
+    let mut result = Vec::new();
+    for index in 0..a.len().max(b.len()) {
+        match (a.get(index), b.get(index)) {
+            (Some(old), Some(new)) if !old.approx_eq(new, epsilon) => {
+                result.push(Difference::Changed {
+                    index,
+                    old: old.clone(),
+                    new: new.clone(),
+                })
+            }
+            (Some(old), None) => result.push(Difference::Removed {
+                index,
+                entity: old.clone(),
+            }),
+            (None, Some(new)) => result.push(Difference::Added {
+                index,
+                entity: new.clone(),
+            }),
+            _ => {}
+        }
+    }
+    result
+}
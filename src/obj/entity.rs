@@ -3,14 +3,19 @@
 //! [`wavefront obj`]: https://en.wikipedia.org/wiki/Wavefront_.obj_file
 //!
 
+use crate::error::{ReaderError, ReaderErrorKind};
 use crate::obj::format_writer::*;
+use crate::obj::line_parser::LineParser;
 use crate::obj::read_lexer::*;
-use std::io::{BufReader, BufWriter, Cursor};
+use std::fmt;
+use std::io::{BufReader, Cursor, Write};
+use std::str::FromStr;
 
 pub type Format = String;
 
 /// Contains all possible entities that can exist in an OBJ format.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Entity {
     Comment {
         content: String,
@@ -19,10 +24,10 @@ pub enum Entity {
         name: String,
     },
     Group {
-        name: String,
+        names: Vec<String>,
     },
     SmoothingGroup {
-        name: String,
+        group: SmoothingGroup,
     },
     MergingGroup {
         name: String,
@@ -33,14 +38,29 @@ pub enum Entity {
     Usemtl {
         name: String,
     },
+    /// Maplib references the texture map libraries of the document.\
+    /// Example: `maplib textures.mpl`
+    Maplib {
+        name: String,
+    },
+    /// Usemap selects the texture map for the following elements.\
+    /// Example: `usemap wood`
+    Usemap {
+        name: String,
+    },
     /// Vertex consists of `x`, `y`, `z` and `w` whereas `w` is optional.\
+    /// The optional `color` holds the `r`, `g` and `b` values of the vertex color extension which are written
+    /// after `w`.\
     /// Example xyzw: `v 0.1 1.2 2.3 3.4`\
-    /// Example xyz: `v 0.1 1.2 2.3`
+    /// Example xyz: `v 0.1 1.2 2.3`\
+    /// Example xyz + color: `v 0.1 1.2 2.3 1.0 0.5 0.0`\
+    /// Example xyzw + color: `v 0.1 1.2 2.3 3.4 1.0 0.5 0.0`
     Vertex {
         x: f64,
         y: f64,
         z: f64,
         w: Option<f64>,
+        color: Option<[f64; 3]>,
     },
     /// VertexNormal consists of `x`, `y`and `z`. The normal will usually but is not required to be a unit vector.\
     /// Example: `vn 0.1 1.2 2.3`
@@ -78,9 +98,11 @@ pub enum Entity {
     Point {
         vertices: Vec<i64>,
     },
-    /// Line consists of an arbitrary number (whereas n >= 2) of vertices that describe the path.
+    /// Line consists of an arbitrary number (whereas n >= 2) of vertices (with optional texture coordinates)
+    /// that describe the path.\
+    /// Example: `l 1 2 3` or `l 1/1 2/2`
     Line {
-        vertices: Vec<i64>,
+        vertices: Vec<LineVertex>,
     },
     /// Face consists of an arbitrary number (whereas n >= 3) of complex vertices that describe the polygon.\
     /// Example (vertex): `f 0 3 6`\
@@ -90,9 +112,329 @@ pub enum Entity {
     Face {
         vertices: Vec<FaceVertex>,
     },
+    /// Curve consists of the starting and ending parameter values `u_start` and `u_end` followed by an
+    /// arbitrary number (whereas n >= 2) of control vertices.\
+    /// Example: `curv 0.0 1.0 1 2 3 4`
+    Curve {
+        u_start: f64,
+        u_end: f64,
+        vertices: Vec<i64>,
+    },
+    /// Curve2D consists of an arbitrary number (whereas n >= 2) of parameter space vertices (`vp`) that
+    /// describe a 2D curve on a surface.\
+    /// Example: `curv2 1 2 3 4`
+    Curve2D {
+        vertices: Vec<i64>,
+    },
+    /// Surface consists of the starting and ending parameter values for `s` and `t` followed by an
+    /// arbitrary number of complex control vertices (same notation as in faces).\
+    /// Example: `surf 0.0 1.0 0.0 1.0 1 2 3 4`
+    Surface {
+        s_start: f64,
+        s_end: f64,
+        t_start: f64,
+        t_end: f64,
+        vertices: Vec<FaceVertex>,
+    },
+    /// CurveSurfaceType specifies the type of the following curves and surfaces (e.g. `bspline`) and
+    /// whether they are rational.\
+    /// Example (rational): `cstype rat bspline`\
+    /// Example (non rational): `cstype bezier`
+    CurveSurfaceType {
+        rational: bool,
+        kind: String,
+    },
+    /// Degree consists of the degree in `u` and `v` direction whereas `v` is only required for surfaces.\
+    /// Example (curve): `deg 3`\
+    /// Example (surface): `deg 3 3`
+    Degree {
+        u: i64,
+        v: Option<i64>,
+    },
+    /// Parm consists of the parameter direction (`u` or `v`) followed by an arbitrary number of global
+    /// parameter values (the knot vector) of the curve or surface body it is part of.\
+    /// Example: `parm u 0.0 0.0 1.0 1.0`
+    Parm {
+        direction: char,
+        values: Vec<f64>,
+    },
+    /// Step consists of the step size in `u` and `v` direction whereas `v` is only required for surfaces.\
+    /// Example (curve): `step 1`\
+    /// Example (surface): `step 1 3`
+    Step {
+        u: i64,
+        v: Option<i64>,
+    },
+    /// Trim consists of an arbitrary number of references to 2D curves (`curv2`) that form an outer trimming
+    /// loop of the surface body it is part of.\
+    /// Example: `trim 0.0 1.0 1 0.0 1.0 2`
+    Trim {
+        curves: Vec<CurveReference>,
+    },
+    /// Hole consists of an arbitrary number of references to 2D curves (`curv2`) that form an inner trimming
+    /// loop (a hole) of the surface body it is part of.\
+    /// Example: `hole 0.0 1.0 3`
+    Hole {
+        curves: Vec<CurveReference>,
+    },
+    /// SpecialCurve consists of an arbitrary number of references to 2D curves (`curv2`) that have to be
+    /// included in the tessellation of the surface body it is part of.\
+    /// Example: `scrv 0.0 1.0 4`
+    SpecialCurve {
+        curves: Vec<CurveReference>,
+    },
+    /// SpecialPoint consists of an arbitrary number of parameter space vertices (`vp`) that have to be
+    /// included in the tessellation of the curve or surface body it is part of.\
+    /// Example: `sp 1 2`
+    SpecialPoint {
+        vertices: Vec<i64>,
+    },
+    /// End terminates the body of a curve or surface.\
+    /// Example: `end`
+    End,
+    /// Bevel turns bevel interpolation on or off for the following elements.\
+    /// Example: `bevel on`
+    Bevel {
+        enabled: bool,
+    },
+    /// ColorInterpolation turns color interpolation on or off for the following elements.\
+    /// Example: `c_interp on`
+    ColorInterpolation {
+        enabled: bool,
+    },
+    /// DissolveInterpolation turns dissolve interpolation on or off for the following elements.\
+    /// Example: `d_interp off`
+    DissolveInterpolation {
+        enabled: bool,
+    },
+    /// LevelOfDetail sets the level of detail (whereas `0` disables it) of the following elements.\
+    /// Example: `lod 5`
+    LevelOfDetail {
+        level: i64,
+    },
+    /// ShadowObject references the file of the object that casts the shadows.\
+    /// Example: `shadow_obj shadow.obj`
+    ShadowObject {
+        name: String,
+    },
+    /// TraceObject references the file of the object that is used for ray tracing.\
+    /// Example: `trace_obj trace.obj`
+    TraceObject {
+        name: String,
+    },
+    /// Unknown contains a line with an unsupported token verbatim.\
+    /// It is only produced when reading with `ReadOptions::preserve_unknown` and is written back unchanged.
+    Unknown {
+        raw: String,
+    },
+}
+
+/// The kind of an `Entity` without its fields, which is cheap to copy, compare and hash (e.g. for grouping
+/// entities by kind).\
+/// Example: `EntityKind::Vertex` for `v 1 2 3`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EntityKind {
+    Comment,
+    Object,
+    Group,
+    SmoothingGroup,
+    MergingGroup,
+    Mtllib,
+    Usemtl,
+    Maplib,
+    Usemap,
+    Vertex,
+    VertexNormal,
+    VertexTexture,
+    VertexParameter,
+    Point,
+    Line,
+    Face,
+    Curve,
+    Curve2D,
+    Surface,
+    CurveSurfaceType,
+    Degree,
+    Parm,
+    Step,
+    Trim,
+    Hole,
+    SpecialCurve,
+    SpecialPoint,
+    End,
+    Bevel,
+    ColorInterpolation,
+    DissolveInterpolation,
+    LevelOfDetail,
+    ShadowObject,
+    TraceObject,
+    Unknown,
 }
 
 impl Entity {
+    /// Returns the kind of the entity without its fields.
+    pub fn kind(&self) -> EntityKind {
+        match self {
+            Self::Comment { .. } => EntityKind::Comment,
+            Self::Object { .. } => EntityKind::Object,
+            Self::Group { .. } => EntityKind::Group,
+            Self::SmoothingGroup { .. } => EntityKind::SmoothingGroup,
+            Self::MergingGroup { .. } => EntityKind::MergingGroup,
+            Self::Mtllib { .. } => EntityKind::Mtllib,
+            Self::Usemtl { .. } => EntityKind::Usemtl,
+            Self::Maplib { .. } => EntityKind::Maplib,
+            Self::Usemap { .. } => EntityKind::Usemap,
+            Self::Vertex { .. } => EntityKind::Vertex,
+            Self::VertexNormal { .. } => EntityKind::VertexNormal,
+            Self::VertexTexture { .. } => EntityKind::VertexTexture,
+            Self::VertexParameter { .. } => EntityKind::VertexParameter,
+            Self::Point { .. } => EntityKind::Point,
+            Self::Line { .. } => EntityKind::Line,
+            Self::Face { .. } => EntityKind::Face,
+            Self::Curve { .. } => EntityKind::Curve,
+            Self::Curve2D { .. } => EntityKind::Curve2D,
+            Self::Surface { .. } => EntityKind::Surface,
+            Self::CurveSurfaceType { .. } => EntityKind::CurveSurfaceType,
+            Self::Degree { .. } => EntityKind::Degree,
+            Self::Parm { .. } => EntityKind::Parm,
+            Self::Step { .. } => EntityKind::Step,
+            Self::Trim { .. } => EntityKind::Trim,
+            Self::Hole { .. } => EntityKind::Hole,
+            Self::SpecialCurve { .. } => EntityKind::SpecialCurve,
+            Self::SpecialPoint { .. } => EntityKind::SpecialPoint,
+            Self::End => EntityKind::End,
+            Self::Bevel { .. } => EntityKind::Bevel,
+            Self::ColorInterpolation { .. } => EntityKind::ColorInterpolation,
+            Self::DissolveInterpolation { .. } => EntityKind::DissolveInterpolation,
+            Self::LevelOfDetail { .. } => EntityKind::LevelOfDetail,
+            Self::ShadowObject { .. } => EntityKind::ShadowObject,
+            Self::TraceObject { .. } => EntityKind::TraceObject,
+            Self::Unknown { .. } => EntityKind::Unknown,
+        }
+    }
+
+    /// The number of bytes that `FormatWriter::write_line` writes for this entity, including the terminating `\n`.
+    pub fn serialized_len(&self) -> usize {
+        let mut counter = ByteCounter { len: 0 };
+        let options = FormatOptions {
+            line_ending: LineEnding::Lf,
+            ..Default::default()
+        };
+        FormatWriter::write_entity(&mut counter, self, &options).unwrap();
+        counter.len
+    }
+
+    /// Whether this entity equals the given one with float fields considered equal if they differ by at most
+    /// the given epsilon.\
+    /// All other fields (e.g. indices, names and whether optional floats are set) have to be equal.\
+    /// Example: `v 0.3 0 0` equals `v 0.30001 0 0` with an epsilon of `0.0001`.
+    pub fn approx_eq(&self, other: &Entity, epsilon: f64) -> bool {
+        let eq = |x: &f64, y: &f64| x == y || (x - y).abs() <= epsilon;
+        let eq_optional = |x: &Option<f64>, y: &Option<f64>| match (x, y) {
+            (Some(x), Some(y)) => eq(x, y),
+            (x, y) => x.is_none() && y.is_none(),
+        };
+        let eq_all =
+            |x: &[f64], y: &[f64]| x.len() == y.len() && x.iter().zip(y).all(|(x, y)| eq(x, y));
+        match (self, other) {
+            (
+                Entity::Vertex { x, y, z, w, color },
+                Entity::Vertex {
+                    x: x2,
+                    y: y2,
+                    z: z2,
+                    w: w2,
+                    color: color2,
+                },
+            ) => {
+                eq(x, x2)
+                    && eq(y, y2)
+                    && eq(z, z2)
+                    && eq_optional(w, w2)
+                    && match (color, color2) {
+                        (Some(c), Some(c2)) => eq_all(c, c2),
+                        (c, c2) => c.is_none() && c2.is_none(),
+                    }
+            }
+            (
+                Entity::VertexNormal { x, y, z },
+                Entity::VertexNormal {
+                    x: x2,
+                    y: y2,
+                    z: z2,
+                },
+            ) => eq(x, x2) && eq(y, y2) && eq(z, z2),
+            (
+                Entity::VertexTexture { u, v, w },
+                Entity::VertexTexture {
+                    u: u2,
+                    v: v2,
+                    w: w2,
+                },
+            )
+            | (
+                Entity::VertexParameter { u, v, w },
+                Entity::VertexParameter {
+                    u: u2,
+                    v: v2,
+                    w: w2,
+                },
+            ) => eq(u, u2) && eq_optional(v, v2) && eq_optional(w, w2),
+            (
+                Entity::Curve {
+                    u_start,
+                    u_end,
+                    vertices,
+                },
+                Entity::Curve {
+                    u_start: u_start2,
+                    u_end: u_end2,
+                    vertices: vertices2,
+                },
+            ) => eq(u_start, u_start2) && eq(u_end, u_end2) && vertices == vertices2,
+            (
+                Entity::Surface {
+                    s_start,
+                    s_end,
+                    t_start,
+                    t_end,
+                    vertices,
+                },
+                Entity::Surface {
+                    s_start: s_start2,
+                    s_end: s_end2,
+                    t_start: t_start2,
+                    t_end: t_end2,
+                    vertices: vertices2,
+                },
+            ) => {
+                eq_all(
+                    &[*s_start, *s_end, *t_start, *t_end],
+                    &[*s_start2, *s_end2, *t_start2, *t_end2],
+                ) && vertices == vertices2
+            }
+            (
+                Entity::Parm { direction, values },
+                Entity::Parm {
+                    direction: direction2,
+                    values: values2,
+                },
+            ) => direction == direction2 && eq_all(values, values2),
+            (Entity::Trim { curves }, Entity::Trim { curves: curves2 })
+            | (Entity::Hole { curves }, Entity::Hole { curves: curves2 })
+            | (Entity::SpecialCurve { curves }, Entity::SpecialCurve { curves: curves2 }) => {
+                curves.len() == curves2.len()
+                    && curves.iter().zip(curves2).all(|(c, c2)| {
+                        eq(&c.u_start, &c2.u_start)
+                            && eq(&c.u_end, &c2.u_end)
+                            && c.curve == c2.curve
+                    })
+            }
+            _ => self == other,
+        }
+    }
+
     pub fn token(&self) -> &str {
         match self {
             Self::Comment { .. } => "#",
@@ -102,6 +444,8 @@ impl Entity {
             Self::MergingGroup { .. } => "mg",
             Self::Mtllib { .. } => "mtllib",
             Self::Usemtl { .. } => "usemtl",
+            Self::Maplib { .. } => "maplib",
+            Self::Usemap { .. } => "usemap",
             Self::Vertex { .. } => "v",
             Self::VertexNormal { .. } => "vn",
             Self::VertexTexture { .. } => "vt",
@@ -109,12 +453,65 @@ impl Entity {
             Self::Point { .. } => "p",
             Self::Line { .. } => "l",
             Self::Face { .. } => "f",
+            Self::Curve { .. } => "curv",
+            Self::Curve2D { .. } => "curv2",
+            Self::Surface { .. } => "surf",
+            Self::CurveSurfaceType { .. } => "cstype",
+            Self::Degree { .. } => "deg",
+            Self::Parm { .. } => "parm",
+            Self::Step { .. } => "step",
+            Self::Trim { .. } => "trim",
+            Self::Hole { .. } => "hole",
+            Self::SpecialCurve { .. } => "scrv",
+            Self::SpecialPoint { .. } => "sp",
+            Self::End => "end",
+            Self::Bevel { .. } => "bevel",
+            Self::ColorInterpolation { .. } => "c_interp",
+            Self::DissolveInterpolation { .. } => "d_interp",
+            Self::LevelOfDetail { .. } => "lod",
+            Self::ShadowObject { .. } => "shadow_obj",
+            Self::TraceObject { .. } => "trace_obj",
+            Self::Unknown { raw } => raw.split_whitespace().next().unwrap_or(""),
+        }
+    }
+}
+
+/// Describes the smoothing group of an `s` statement.\
+/// Example: `s 1` or `s off`, where `off` is equivalent to `s 0`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SmoothingGroup {
+    Off,
+    Group(u32),
+}
+
+impl SmoothingGroup {
+    /// The number of the smoothing group, which is `0` for `Off`.
+    pub fn number(&self) -> u32 {
+        match self {
+            Self::Off => 0,
+            Self::Group(x) => *x,
+        }
+    }
+
+    /// Whether smoothing is turned off, which is the case for `Off` and `Group(0)`.
+    pub fn is_off(&self) -> bool {
+        self.number() == 0
+    }
+}
+
+impl fmt::Display for SmoothingGroup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Off => write!(f, "off"),
+            Self::Group(x) => write!(f, "{}", x),
         }
     }
 }
 
 /// Describes a vertex in a face.
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct FaceVertex {
     /// The vertex index itself.
     pub vertex: i64,
@@ -140,13 +537,307 @@ impl FaceVertex {
             texture,
         }
     }
+
+    /// Parses a single face vertex in the notation of `Face` entities, which is `v`, `v/normal`, `v//texture` or
+    /// `v/normal/texture`.\
+    /// Note that this crate reads and writes the normal before the texture index, which is the reverse of the
+    /// `v/vt/vn` order of the OBJ specification. `from_token` follows the lexer and writer of this crate, so
+    /// `1/2` sets the normal and `1//3` the texture.\
+    /// Will return a `ReaderError` if the vertex index is missing, an index is invalid or there are more than
+    /// three indices.\
+    /// Example: `1/2/3` yields vertex `1`, normal `2` and texture `3`.
+    pub fn from_token(s: &str) -> Result<Self, ReaderError> {
+        let s = s.trim();
+        if s.split('/').count() > 3 {
+            return Err(ReaderError::new(
+                ReaderErrorKind::InvalidNumber,
+                s,
+                "too many indices for face vertex",
+            ));
+        }
+        LineParser::parse_face_vertex(s, "f", s)
+    }
+
+    /// Formats the face vertex like it is written in a `Face` entity, which is the inverse of `from_token` and
+    /// uses the same `v/normal/texture` order.
+    pub fn to_token(&self) -> String {
+        match (self.normal, self.texture) {
+            (None, None) => format!("{}", self.vertex),
+            (Some(n), None) => format!("{}/{}", self.vertex, n),
+            (None, Some(t)) => format!("{}//{}", self.vertex, t),
+            (Some(n), Some(t)) => format!("{}/{}/{}", self.vertex, n, t),
+        }
+    }
+
+    /// Converts relative (negative) indices into absolute 1-based indices.\
+    /// The totals are the number of vertices, normals and textures that have been seen so far, which means
+    /// that `-1` refers to the last vertex (normal, texture) seen so far.\
+    /// Each index is resolved independently and positive indices are left unchanged.\
+    /// Example: resolving `-1/-2/-3` with totals `(10, 20, 30)` yields `10/19/28`.
+    pub fn resolve(
+        &self,
+        total_vertices: usize,
+        total_normals: usize,
+        total_textures: usize,
+    ) -> Self {
+        Self {
+            vertex: resolve_index(self.vertex, total_vertices),
+            normal: self.normal.map(|x| resolve_index(x, total_normals)),
+            texture: self.texture.map(|x| resolve_index(x, total_textures)),
+        }
+    }
 }
 
-impl ToString for Entity {
-    fn to_string(&self) -> String {
-        let mut result = String::new();
-        FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &self);
-        result
+/// Describes a vertex in a line.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LineVertex {
+    /// The vertex index itself.
+    pub vertex: i64,
+    /// The texture map info for the vertex (optional).
+    pub texture: Option<i64>,
+}
+
+impl LineVertex {
+    pub fn new(vertex: i64) -> Self {
+        Self {
+            vertex,
+            texture: None,
+        }
+    }
+
+    pub fn new2(vertex: i64, texture: Option<i64>) -> Self {
+        Self { vertex, texture }
+    }
+
+    /// Converts relative (negative) indices into absolute 1-based indices like `FaceVertex::resolve`.
+    pub fn resolve(&self, total_vertices: usize, total_textures: usize) -> Self {
+        Self {
+            vertex: resolve_index(self.vertex, total_vertices),
+            texture: self.texture.map(|x| resolve_index(x, total_textures)),
+        }
+    }
+}
+
+/// Describes a segment of a 2D curve in a `trim`, `hole` or `scrv` statement.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CurveReference {
+    /// The starting parameter value of the segment.
+    pub u_start: f64,
+    /// The ending parameter value of the segment.
+    pub u_end: f64,
+    /// The index of the referenced `curv2` statement.
+    pub curve: i64,
+}
+
+impl CurveReference {
+    pub fn new(u_start: f64, u_end: f64, curve: i64) -> Self {
+        Self {
+            u_start,
+            u_end,
+            curve,
+        }
+    }
+}
+
+/// Builds `Face` entities vertex by vertex.
+///
+/// ```
+/// use wavefront_rs::obj::entity::{Entity, FaceBuilder, FaceVertex};
+///
+/// let face = FaceBuilder::new()
+///     .vertex_full(1, 4, 7)
+///     .vertex_full(2, 5, 8)
+///     .vertex_full(3, 6, 9)
+///     .build();
+/// assert_eq!(
+///     Entity::Face {
+///         vertices: vec![
+///             FaceVertex::new2(1, Some(7), Some(4)),
+///             FaceVertex::new2(2, Some(8), Some(5)),
+///             FaceVertex::new2(3, Some(9), Some(6)),
+///         ],
+///     },
+///     face
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct FaceBuilder {
+    vertices: Vec<FaceVertex>,
+}
+
+impl FaceBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a vertex without normal and texture.
+    pub fn vertex(self, vertex: i64) -> Self {
+        self.push(FaceVertex::new(vertex))
+    }
+
+    /// Adds a vertex with a texture.
+    pub fn vertex_texture(self, vertex: i64, texture: i64) -> Self {
+        self.push(FaceVertex::new2(vertex, None, Some(texture)))
+    }
+
+    /// Adds a vertex with a normal.
+    pub fn vertex_normal(self, vertex: i64, normal: i64) -> Self {
+        self.push(FaceVertex::new2(vertex, Some(normal), None))
+    }
+
+    /// Adds a vertex with a texture and a normal.
+    pub fn vertex_full(self, vertex: i64, texture: i64, normal: i64) -> Self {
+        self.push(FaceVertex::new2(vertex, Some(normal), Some(texture)))
+    }
+
+    /// Creates the `Face` entity from all added vertices.
+    pub fn build(self) -> Entity {
+        Entity::Face {
+            vertices: self.vertices,
+        }
+    }
+
+    fn push(mut self, vertex: FaceVertex) -> Self {
+        self.vertices.push(vertex);
+        self
+    }
+}
+
+/// Resolves the relative indices of a sequence of entities by counting the declarations seen so far.
+#[derive(Default)]
+pub(crate) struct IndexResolver {
+    pub(crate) vertices: usize,
+    pub(crate) normals: usize,
+    pub(crate) textures: usize,
+    pub(crate) parameters: usize,
+}
+
+impl IndexResolver {
+    /// Counts the given entity if it is a declaration.
+    pub(crate) fn count(&mut self, e: &Entity) {
+        match e {
+            Entity::Vertex { .. } => self.vertices += 1,
+            Entity::VertexNormal { .. } => self.normals += 1,
+            Entity::VertexTexture { .. } => self.textures += 1,
+            Entity::VertexParameter { .. } => self.parameters += 1,
+            _ => {}
+        }
+    }
+
+    /// Returns the category and value of the first index of the given `Face` that is zero or does not refer to
+    /// a declaration seen so far, or `None` if all indices are in range or the entity is no `Face`.
+    pub(crate) fn face_out_of_range(&self, e: &Entity) -> Option<(&'static str, i64)> {
+        let in_range = |i: i64, total: usize| i != 0 && i.unsigned_abs() as usize <= total;
+        match e {
+            Entity::Face { vertices } => vertices.iter().find_map(|v| {
+                if !in_range(v.vertex, self.vertices) {
+                    Some(("vertex", v.vertex))
+                } else if let Some(i) = v.normal.filter(|i| !in_range(*i, self.normals)) {
+                    Some(("normal", i))
+                } else {
+                    v.texture
+                        .filter(|i| !in_range(*i, self.textures))
+                        .map(|i| ("texture", i))
+                }
+            }),
+            _ => None,
+        }
+    }
+
+    /// Counts the given entity if it is a declaration and returns it with all its indices resolved.
+    pub(crate) fn resolve(&mut self, e: Entity) -> Entity {
+        let resolve = |x: Vec<i64>, total| x.into_iter().map(|i| resolve_index(i, total)).collect();
+        let resolve_face = |x: Vec<FaceVertex>, s: &Self| {
+            x.iter()
+                .map(|v| v.resolve(s.vertices, s.normals, s.textures))
+                .collect()
+        };
+        self.count(&e);
+        match e {
+            Entity::Face { vertices } => Entity::Face {
+                vertices: resolve_face(vertices, self),
+            },
+            Entity::Surface {
+                s_start,
+                s_end,
+                t_start,
+                t_end,
+                vertices,
+            } => Entity::Surface {
+                s_start,
+                s_end,
+                t_start,
+                t_end,
+                vertices: resolve_face(vertices, self),
+            },
+            Entity::Line { vertices } => Entity::Line {
+                vertices: vertices
+                    .iter()
+                    .map(|v| v.resolve(self.vertices, self.textures))
+                    .collect(),
+            },
+            Entity::Point { vertices } => Entity::Point {
+                vertices: resolve(vertices, self.vertices),
+            },
+            Entity::Curve {
+                u_start,
+                u_end,
+                vertices,
+            } => Entity::Curve {
+                u_start,
+                u_end,
+                vertices: resolve(vertices, self.vertices),
+            },
+            Entity::Curve2D { vertices } => Entity::Curve2D {
+                vertices: resolve(vertices, self.parameters),
+            },
+            Entity::SpecialPoint { vertices } => Entity::SpecialPoint {
+                vertices: resolve(vertices, self.parameters),
+            },
+            e => e,
+        }
+    }
+}
+
+/// Counts the bytes written to it without storing them.
+struct ByteCounter {
+    len: usize,
+}
+
+impl Write for ByteCounter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        self.len += buf.len();
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+fn resolve_index(index: i64, total: usize) -> i64 {
+    if index < 0 {
+        total as i64 + index + 1
+    } else {
+        index
+    }
+}
+
+impl fmt::Display for Entity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut result = Vec::new();
+        FormatWriter::write(&mut result, self).map_err(|_| fmt::Error)?;
+        f.pad(std::str::from_utf8(&result).map_err(|_| fmt::Error)?)
+    }
+}
+
+impl FromStr for Entity {
+    type Err = ReaderError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ReadLexer::read_line(&mut BufReader::new(Cursor::new(s)))
     }
 }
 
@@ -156,8 +847,8 @@ impl From<Format> for Entity {
     }
 }
 
-impl Into<Format> for Entity {
-    fn into(self) -> String {
-        self.to_string()
+impl From<Entity> for Format {
+    fn from(input: Entity) -> Self {
+        input.to_string()
     }
 }
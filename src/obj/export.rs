@@ -0,0 +1,116 @@
+//! Contains conversions of OBJ documents into other formats.
+//!
+
+use crate::error::WriterError;
+use crate::obj::entity::{Entity, IndexResolver};
+use crate::obj::geometry::{face_normal, normalize, triangulate};
+use std::io::Write;
+
+/// The corner positions of a triangle.
+pub type Triangle = [[f32; 3]; 3];
+
+/// Describes a triangle together with its flat normal like a facet of an STL file.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StlFacet {
+    /// The unit normal of the triangle or `[0.0, 0.0, 0.0]` if the triangle is degenerate.
+    pub normal: [f32; 3],
+    pub vertices: Triangle,
+}
+
+/// Same as `to_stl_facets` but only returns the triangles.
+pub fn to_stl_triangles(entities: &[Entity]) -> Vec<Triangle> {
+    to_stl_facets(entities)
+        .into_iter()
+        .map(|x| x.vertices)
+        .collect()
+}
+
+/// Fan-triangulates all `Face` entities (see `geometry::triangulate`) and resolves their vertex indices into the
+/// positions of the referenced `Vertex` entities.\
+/// The normal of each triangle is computed from its positions in counter-clockwise winding order.
+/// Relative indices are resolved and triangles with an index that is out of range are skipped.
+pub fn to_stl_facets(entities: &[Entity]) -> Vec<StlFacet> {
+    let positions = positions(entities);
+    let mut resolver = IndexResolver::default();
+    let mut result = Vec::new();
+    for e in entities {
+        for triangle in triangulate(&resolver.resolve(e.clone())) {
+            let points = match triangle {
+                Entity::Face { vertices } => vertices
+                    .iter()
+                    .map(|v| position(&positions, v.vertex))
+                    .collect::<Option<Vec<_>>>(),
+                _ => None,
+            };
+            if let Some(points) = points {
+                let normal = face_normal(&points).and_then(normalize).unwrap_or([0.0; 3]);
+                result.push(StlFacet {
+                    normal: normal.map(|x| x as f32),
+                    vertices: [0, 1, 2].map(|i| points[i].map(|x| x as f32)),
+                });
+            }
+        }
+    }
+    result
+}
+
+/// Writes all `Vertex` and `Face` entities as ASCII PLY document to the given `Write` trait.\
+/// The vertex list contains the `x`, `y` and `z` of every vertex and the face list contains every face
+/// fan-triangulated (see `geometry::triangulate`) with 0-based vertex indices. Relative indices are resolved and
+/// triangles with an index that is out of range are skipped, so that documents without vertices result in
+/// empty lists.\
+/// Will return `Ok(())` if successful or a `WriterError` (if writing failed).
+pub fn write_ply<W: Write>(writer: &mut W, entities: &[Entity]) -> Result<(), WriterError> {
+    let positions = positions(entities);
+    let mut resolver = IndexResolver::default();
+    let mut faces = Vec::new();
+    for e in entities {
+        for triangle in triangulate(&resolver.resolve(e.clone())) {
+            if let Entity::Face { vertices } = triangle {
+                let indices = vertices
+                    .iter()
+                    .map(|v| position(&positions, v.vertex).map(|_| v.vertex - 1))
+                    .collect::<Option<Vec<_>>>();
+                faces.extend(indices);
+            }
+        }
+    }
+    writeln!(writer, "ply")?;
+    writeln!(writer, "format ascii 1.0")?;
+    writeln!(writer, "element vertex {}", positions.len())?;
+    for axis in &["x", "y", "z"] {
+        writeln!(writer, "property float {}", axis)?;
+    }
+    writeln!(writer, "element face {}", faces.len())?;
+    writeln!(writer, "property list uchar int vertex_indices")?;
+    writeln!(writer, "end_header")?;
+    for [x, y, z] in positions {
+        writeln!(writer, "{} {} {}", x, y, z)?;
+    }
+    for face in faces {
+        writeln!(writer, "3 {} {} {}", face[0], face[1], face[2])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// The positions of all `Vertex` entities in declaration order.
+fn positions(entities: &[Entity]) -> Vec<[f64; 3]> {
+    entities
+        .iter()
+        .filter_map(|e| match e {
+            Entity::Vertex { x, y, z, .. } => Some([*x, *y, *z]),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The position of the given resolved 1-based index or `None` if it is out of range.
+fn position(positions: &[[f64; 3]], index: i64) -> Option<[f64; 3]> {
+    if index < 1 {
+        None
+    } else {
+        positions.get(index as usize - 1).copied()
+    }
+}
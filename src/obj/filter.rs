@@ -0,0 +1,45 @@
+//! Contains helpers to select entities of an OBJ document.
+//!
+
+use crate::obj::entity::Entity;
+
+/// Whether the given entity is a `Vertex`, `VertexNormal`, `VertexTexture`, `Face`, `Line` or `Point`.\
+/// Can be used as iterator adapter, e.g. `entities.iter().filter(|x| is_geometry(x))`.
+pub fn is_geometry(e: &Entity) -> bool {
+    matches!(
+        e,
+        Entity::Vertex { .. }
+            | Entity::VertexNormal { .. }
+            | Entity::VertexTexture { .. }
+            | Entity::Face { .. }
+            | Entity::Line { .. }
+            | Entity::Point { .. }
+    )
+}
+
+/// Returns the geometry entities (see `is_geometry`) of the given entities in order, which drops comments,
+/// groups, materials and all other statements.\
+/// Indices stay valid because no declaration is dropped.
+pub fn geometry_only(entities: &[Entity]) -> Vec<Entity> {
+    entities
+        .iter()
+        .filter(|x| is_geometry(x))
+        .cloned()
+        .collect()
+}
+
+/// Collapses each run of adjacent `Comment` entities into a single `Comment` whose lines are joined by `\n`.\
+/// Writing such a comment splits it into one `#` line per line again. All other entities are left unchanged.
+pub fn merge_comments(entities: &[Entity]) -> Vec<Entity> {
+    let mut result: Vec<Entity> = Vec::new();
+    for e in entities {
+        match (result.last_mut(), e) {
+            (Some(Entity::Comment { content }), Entity::Comment { content: x }) => {
+                content.push('\n');
+                content.push_str(x);
+            }
+            _ => result.push(e.clone()),
+        }
+    }
+    result
+}
@@ -1,114 +1,342 @@
 //! Contains the logic to transform entities to OBJ formatted strings.
 //!
 
-use crate::obj::entity::Entity;
+use crate::error::WriterError;
+use crate::obj::entity::{Entity, FaceVertex};
 use std::io::Write;
 
+/// The line terminator that is written after each entity.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum LineEnding {
+    /// No line terminator is written.
+    #[default]
+    None,
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    CrLf,
+}
+
+/// Options that control how entities are written.
+#[derive(Debug, Clone, Default)]
+pub struct FormatOptions {
+    /// The number of decimal places of float fields or `None` for the default formatting.
+    pub float_precision: Option<usize>,
+    /// The line terminator that is written after each entity.
+    pub line_ending: LineEnding,
+    /// Writes optional components even if they are not set.\
+    /// Missing `w` of vertices and vertex parameters defaults to `1.0`, missing `v` and `w` of textures
+    /// and missing `v` of vertex parameters default to `0.0`.
+    pub always_emit_optional: bool,
+    /// Right-aligns float fields to the given width (in characters) or writes them unpadded if `None`.\
+    /// Combined with `float_precision` the fields of e.g. vertices line up in columns, which makes debug dumps
+    /// easier to read but produces larger files. Fields that are wider than the given width are not truncated.\
+    /// Example (width 6, 2 decimals): `v   0.10  -1.20   2.30`
+    pub column_width: Option<usize>,
+}
+
 /// Will write entities to a `Write` trait.
 pub struct FormatWriter {}
 
 impl FormatWriter {
-    /// Writes the given entity to the given `Write` trait as OBJ format representation of that `Entity`.
-    pub fn write<W: Write>(writer: &mut W, e: &Entity) {
+    /// Writes the given entity to the given `Write` trait as OBJ format representation of that `Entity`.\
+    /// Will return `Ok(())` if successful or a `WriterError` (if writing failed).
+    pub fn write<W: Write>(writer: &mut W, e: &Entity) -> Result<(), WriterError> {
+        Self::write_with(writer, e, &FormatOptions::default())
+    }
+
+    /// Writes the given entity like `write` followed by a `\n` line terminator, so that entities which are
+    /// written back to back form a valid multi-line OBJ document.
+    pub fn write_line<W: Write>(writer: &mut W, e: &Entity) -> Result<(), WriterError> {
+        let options = FormatOptions {
+            line_ending: LineEnding::Lf,
+            ..Default::default()
+        };
+        Self::write_with(writer, e, &options)
+    }
+
+    /// Writes the given entity like `write` but with all float fields formatted with the given number of
+    /// decimal places.\
+    /// Example (3 decimals): `v 0.100 1.200 2.300`
+    pub fn write_with_precision<W: Write>(
+        writer: &mut W,
+        e: &Entity,
+        decimals: usize,
+    ) -> Result<(), WriterError> {
+        let options = FormatOptions {
+            float_precision: Some(decimals),
+            ..Default::default()
+        };
+        Self::write_with(writer, e, &options)
+    }
+
+    /// Writes the given entity like `write` but customized by the given `FormatOptions`.
+    pub fn write_with<W: Write>(
+        writer: &mut W,
+        e: &Entity,
+        options: &FormatOptions,
+    ) -> Result<(), WriterError> {
+        Ok(Self::write_entity(writer, e, options)?)
+    }
+
+    pub(crate) fn write_entity<W: Write>(
+        writer: &mut W,
+        e: &Entity,
+        options: &FormatOptions,
+    ) -> std::io::Result<()> {
+        let f = |x: &f64| match options.column_width {
+            Some(width) => format!("{:>1$}", float(*x, options.float_precision), width),
+            None => float(*x, options.float_precision),
+        };
+        let always = options.always_emit_optional;
         match e {
             Entity::Comment { content } => {
-                writer
-                    .write_all(format!("{} {}", e.token(), content).as_ref())
-                    .unwrap();
+                let separator = match options.line_ending {
+                    LineEnding::CrLf => "\r\n",
+                    _ => "\n",
+                };
+                for (i, part) in content.trim_end().split('\n').enumerate() {
+                    if i > 0 {
+                        writer.write_all(separator.as_ref())?;
+                    }
+                    writer.write_all(e.token().as_ref())?;
+                    if !part.trim_end().is_empty() {
+                        writer.write_all(format!(" {}", part.trim_end()).as_ref())?;
+                    }
+                }
             }
             Entity::Face { vertices } => {
-                writer.write_all(e.token().as_ref()).unwrap();
+                writer.write_all(e.token().as_ref())?;
                 for v in vertices {
-                    writer.write_all(" ".as_ref()).unwrap();
-                    writer.write_all(format!("{}", v.vertex).as_ref()).unwrap();
-                    if let Some(x) = v.normal {
-                        writer.write_all(format!("/{}", x).as_ref()).unwrap();
-                    }
-                    if let Some(x) = v.texture {
-                        if v.normal.is_none() {
-                            writer.write_all("/".as_ref()).unwrap();
-                        }
-                        writer.write_all(format!("/{}", x).as_ref()).unwrap();
-                    }
+                    Self::write_face_vertex(writer, v)?;
                 }
             }
             Entity::Point { vertices } => {
-                writer.write_all(e.token().as_ref()).unwrap();
+                writer.write_all(e.token().as_ref())?;
                 for v in vertices {
-                    writer.write_all(format!(" {}", v).as_ref()).unwrap();
+                    writer.write_all(format!(" {}", v).as_ref())?;
                 }
             }
             Entity::Line { vertices } => {
-                writer.write_all(e.token().as_ref()).unwrap();
+                writer.write_all(e.token().as_ref())?;
                 for v in vertices {
-                    writer.write_all(format!(" {}", v).as_ref()).unwrap();
+                    writer.write_all(format!(" {}", v.vertex).as_ref())?;
+                    if let Some(x) = v.texture {
+                        writer.write_all(format!("/{}", x).as_ref())?;
+                    }
                 }
             }
-            Entity::Group { name } => {
-                writer
-                    .write_all(format!("{} {}", e.token(), name).as_ref())
-                    .unwrap();
+            Entity::Curve {
+                u_start,
+                u_end,
+                vertices,
+            } => {
+                writer.write_all(format!("{} {} {}", e.token(), f(u_start), f(u_end)).as_ref())?;
+                for v in vertices {
+                    writer.write_all(format!(" {}", v).as_ref())?;
+                }
+            }
+            Entity::Curve2D { vertices } => {
+                writer.write_all(e.token().as_ref())?;
+                for v in vertices {
+                    writer.write_all(format!(" {}", v).as_ref())?;
+                }
+            }
+            Entity::Surface {
+                s_start,
+                s_end,
+                t_start,
+                t_end,
+                vertices,
+            } => {
+                writer.write_all(
+                    format!(
+                        "{} {} {} {} {}",
+                        e.token(),
+                        f(s_start),
+                        f(s_end),
+                        f(t_start),
+                        f(t_end)
+                    )
+                    .as_ref(),
+                )?;
+                for v in vertices {
+                    Self::write_face_vertex(writer, v)?;
+                }
+            }
+            Entity::CurveSurfaceType { rational, kind } => {
+                writer.write_all(e.token().as_ref())?;
+                if *rational {
+                    writer.write_all(" rat".as_ref())?;
+                }
+                writer.write_all(format!(" {}", kind).as_ref())?;
+            }
+            Entity::Parm { direction, values } => {
+                writer.write_all(format!("{} {}", e.token(), direction).as_ref())?;
+                for v in values {
+                    writer.write_all(format!(" {}", f(v)).as_ref())?;
+                }
+            }
+            Entity::Degree { u, v } | Entity::Step { u, v } => {
+                writer.write_all(format!("{} {}", e.token(), u).as_ref())?;
+                if let Some(v) = v {
+                    writer.write_all(format!(" {}", v).as_ref())?;
+                }
+            }
+            Entity::Trim { curves } | Entity::Hole { curves } | Entity::SpecialCurve { curves } => {
+                writer.write_all(e.token().as_ref())?;
+                for c in curves {
+                    writer.write_all(
+                        format!(" {} {} {}", f(&c.u_start), f(&c.u_end), c.curve).as_ref(),
+                    )?;
+                }
+            }
+            Entity::SpecialPoint { vertices } => {
+                writer.write_all(e.token().as_ref())?;
+                for v in vertices {
+                    writer.write_all(format!(" {}", v).as_ref())?;
+                }
+            }
+            Entity::End => {
+                writer.write_all(e.token().as_ref())?;
+            }
+            Entity::Bevel { enabled }
+            | Entity::ColorInterpolation { enabled }
+            | Entity::DissolveInterpolation { enabled } => {
+                let value = if *enabled { "on" } else { "off" };
+                writer.write_all(format!("{} {}", e.token(), value).as_ref())?;
+            }
+            Entity::LevelOfDetail { level } => {
+                writer.write_all(format!("{} {}", e.token(), level).as_ref())?;
+            }
+            Entity::ShadowObject { name } | Entity::TraceObject { name } => {
+                writer.write_all(e.token().as_ref())?;
+                Self::write_file(writer, name)?;
+            }
+            Entity::Unknown { raw } => {
+                writer.write_all(raw.trim_end().as_ref())?;
+            }
+            Entity::Group { names } => {
+                writer.write_all(e.token().as_ref())?;
+                for name in names {
+                    Self::write_name(writer, name)?;
+                }
             }
             Entity::Mtllib { name } => {
-                writer
-                    .write_all(format!("{} {}", e.token(), name).as_ref())
-                    .unwrap();
+                writer.write_all(e.token().as_ref())?;
+                Self::write_file(writer, name)?;
             }
             Entity::Object { name } => {
-                writer
-                    .write_all(format!("{} {}", e.token(), name).as_ref())
-                    .unwrap();
+                writer.write_all(e.token().as_ref())?;
+                Self::write_name(writer, name)?;
             }
-            Entity::SmoothingGroup { name } => {
-                writer
-                    .write_all(format!("{} {}", e.token(), name).as_ref())
-                    .unwrap();
+            Entity::SmoothingGroup { group } => {
+                writer.write_all(format!("{} {}", e.token(), group).as_ref())?;
             }
             Entity::MergingGroup { name } => {
-                writer
-                    .write_all(format!("{} {}", e.token(), name).as_ref())
-                    .unwrap();
+                writer.write_all(e.token().as_ref())?;
+                Self::write_name(writer, name)?;
             }
             Entity::Usemtl { name } => {
-                writer
-                    .write_all(format!("{} {}", e.token(), name).as_ref())
-                    .unwrap();
+                writer.write_all(e.token().as_ref())?;
+                Self::write_name(writer, name)?;
             }
-            Entity::Vertex { x, y, z, w } => {
-                writer
-                    .write_all(format!("{} {} {} {}", e.token(), x, y, z).as_ref())
-                    .unwrap();
-                if let Some(v) = w {
-                    writer.write_all(format!(" {}", v).as_ref()).unwrap();
+            Entity::Maplib { name } => {
+                writer.write_all(e.token().as_ref())?;
+                Self::write_file(writer, name)?;
+            }
+            Entity::Usemap { name } => {
+                writer.write_all(e.token().as_ref())?;
+                Self::write_name(writer, name)?;
+            }
+            Entity::Vertex { x, y, z, w, color } => {
+                writer.write_all(format!("{} {} {} {}", e.token(), f(x), f(y), f(z)).as_ref())?;
+                match w {
+                    Some(v) => writer.write_all(format!(" {}", f(v)).as_ref())?,
+                    None if always => writer.write_all(format!(" {}", f(&1.0)).as_ref())?,
+                    None => {}
+                }
+                if let Some([r, g, b]) = color {
+                    writer.write_all(format!(" {} {} {}", f(r), f(g), f(b)).as_ref())?;
                 }
             }
             Entity::VertexNormal { x, y, z } => {
-                writer
-                    .write_all(format!("{} {} {} {}", e.token(), x, y, z).as_ref())
-                    .unwrap();
+                writer.write_all(format!("{} {} {} {}", e.token(), f(x), f(y), f(z)).as_ref())?;
             }
             Entity::VertexTexture { u, v, w } => {
-                writer
-                    .write_all(format!("{} {}", e.token(), u).as_ref())
-                    .unwrap();
-                if let Some(v) = v {
-                    writer.write_all(format!(" {}", v).as_ref()).unwrap();
+                writer.write_all(format!("{} {}", e.token(), f(u)).as_ref())?;
+                if always {
+                    let v = v.unwrap_or(0.0);
+                    let w = w.unwrap_or(0.0);
+                    writer.write_all(format!(" {} {}", f(&v), f(&w)).as_ref())?;
+                } else if let Some(v) = v {
+                    writer.write_all(format!(" {}", f(v)).as_ref())?;
                     if let Some(w) = w {
-                        writer.write_all(format!(" {}", w).as_ref()).unwrap();
+                        writer.write_all(format!(" {}", f(w)).as_ref())?;
                     }
                 }
             }
             Entity::VertexParameter { u, v, w } => {
-                writer
-                    .write_all(format!("{} {}", e.token(), u).as_ref())
-                    .unwrap();
-                if let Some(v) = v {
-                    writer.write_all(format!(" {}", v).as_ref()).unwrap();
+                writer.write_all(format!("{} {}", e.token(), f(u)).as_ref())?;
+                if always {
+                    let v = v.unwrap_or(0.0);
+                    let w = w.unwrap_or(1.0);
+                    writer.write_all(format!(" {} {}", f(&v), f(&w)).as_ref())?;
+                } else if let Some(v) = v {
+                    writer.write_all(format!(" {}", f(v)).as_ref())?;
                     if let Some(w) = w {
-                        writer.write_all(format!(" {}", w).as_ref()).unwrap();
+                        writer.write_all(format!(" {}", f(w)).as_ref())?;
                     }
                 }
             }
         }
+        match options.line_ending {
+            LineEnding::None => {}
+            LineEnding::Lf => writer.write_all(b"\n")?,
+            LineEnding::CrLf => writer.write_all(b"\r\n")?,
+        }
+        Ok(())
+    }
+
+    /// Writes each whitespace separated part of the given name preceded by a single space, so that neither
+    /// empty names nor surrounding or repeated whitespace lead to extra spaces.
+    fn write_name<W: Write>(writer: &mut W, name: &str) -> std::io::Result<()> {
+        for part in name.split_whitespace() {
+            writer.write_all(format!(" {}", part).as_ref())?;
+        }
+        Ok(())
+    }
+
+    /// Writes the given file name preceded by a single space unless it is empty.\
+    /// Embedded whitespace is kept, because it is part of the file name.
+    fn write_file<W: Write>(writer: &mut W, name: &str) -> std::io::Result<()> {
+        let name = name.trim();
+        if !name.is_empty() {
+            writer.write_all(format!(" {}", name).as_ref())?;
+        }
+        Ok(())
+    }
+
+    fn write_face_vertex<W: Write>(writer: &mut W, v: &FaceVertex) -> std::io::Result<()> {
+        writer.write_all(" ".as_ref())?;
+        writer.write_all(format!("{}", v.vertex).as_ref())?;
+        if let Some(x) = v.normal {
+            writer.write_all(format!("/{}", x).as_ref())?;
+        }
+        if let Some(x) = v.texture {
+            if v.normal.is_none() {
+                writer.write_all("/".as_ref())?;
+            }
+            writer.write_all(format!("/{}", x).as_ref())?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats the given float with the given number of decimal places or with the default formatting.
+pub(crate) fn float(value: f64, precision: Option<usize>) -> String {
+    match precision {
+        Some(p) => format!("{:.*}", p, value),
+        None => format!("{}", value),
     }
 }
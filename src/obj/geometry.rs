@@ -0,0 +1,252 @@
+//! Contains geometric helpers that operate on entities.
+//!
+
+use crate::obj::entity::{Entity, FaceVertex};
+
+/// Fan-triangulates the given `Face` of n vertices into n - 2 triangular `Face` entities.\
+/// Every triangle shares the first vertex of the face and each `FaceVertex` keeps its vertex, normal and
+/// texture indices.\
+/// Returns an empty vec for entities that are no faces and for degenerate faces with less than 3 vertices.
+pub fn triangulate(face: &Entity) -> Vec<Entity> {
+    match face {
+        Entity::Face { vertices } if vertices.len() >= 3 => (1..vertices.len() - 1)
+            .map(|i| Entity::Face {
+                vertices: vec![
+                    vertices[0].clone(),
+                    vertices[i].clone(),
+                    vertices[i + 1].clone(),
+                ],
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Returns the edges of the given `Face` as pairs of vertex indices `(v0, v1), (v1, v2), ..., (vn, v0)` in the
+/// winding order of the face.\
+/// The indices are returned as they are written in the face, which means that relative indices are not resolved.\
+/// Returns an empty vec for entities that are no faces and for degenerate faces with less than 3 vertices.
+pub fn face_edges(face: &Entity) -> Vec<(i64, i64)> {
+    match face {
+        Entity::Face { vertices } if vertices.len() >= 3 => vertices
+            .iter()
+            .zip(vertices.iter().cycle().skip(1))
+            .map(|(a, b)| (a.vertex, b.vertex))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Splits the given 4-vertex `Face` into the two triangles `(0, 1, 2)` and `(0, 2, 3)`, which keeps the winding
+/// order of the quad.\
+/// Each `FaceVertex` keeps its vertex, normal and texture indices.\
+/// Returns `None` for entities that are no faces with exactly 4 vertices.
+pub fn split_quad(face: &Entity) -> Option<(Entity, Entity)> {
+    match face {
+        Entity::Face { vertices } if vertices.len() == 4 => Some((
+            Entity::Face {
+                vertices: vec![
+                    vertices[0].clone(),
+                    vertices[1].clone(),
+                    vertices[2].clone(),
+                ],
+            },
+            Entity::Face {
+                vertices: vec![
+                    vertices[0].clone(),
+                    vertices[2].clone(),
+                    vertices[3].clone(),
+                ],
+            },
+        )),
+        _ => None,
+    }
+}
+
+/// Describes an axis-aligned bounding box.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BoundingBox {
+    /// The minimum `x`, `y` and `z` values.
+    pub min: [f64; 3],
+    /// The maximum `x`, `y` and `z` values.
+    pub max: [f64; 3],
+}
+
+impl BoundingBox {
+    /// The center point of the box.
+    pub fn center(&self) -> [f64; 3] {
+        [
+            (self.min[0] + self.max[0]) / 2.0,
+            (self.min[1] + self.max[1]) / 2.0,
+            (self.min[2] + self.max[2]) / 2.0,
+        ]
+    }
+
+    /// The extents of the box along the `x`, `y` and `z` axis.
+    pub fn size(&self) -> [f64; 3] {
+        [
+            self.max[0] - self.min[0],
+            self.max[1] - self.min[1],
+            self.max[2] - self.min[2],
+        ]
+    }
+}
+
+/// Computes the axis-aligned bounding box over all `Vertex` entities.\
+/// Only `x`, `y` and `z` are considered while `w` is ignored.\
+/// Returns `None` if there are no vertices.
+pub fn bounding_box(entities: &[Entity]) -> Option<BoundingBox> {
+    let mut result: Option<BoundingBox> = None;
+    for e in entities {
+        if let Entity::Vertex { x, y, z, .. } = e {
+            let p = [*x, *y, *z];
+            match result.as_mut() {
+                Some(b) => {
+                    for (i, v) in p.iter().enumerate() {
+                        b.min[i] = b.min[i].min(*v);
+                        b.max[i] = b.max[i].max(*v);
+                    }
+                }
+                None => result = Some(BoundingBox { min: p, max: p }),
+            }
+        }
+    }
+    result
+}
+
+/// Computes vertex normals for all faces that do not reference any normal.\
+/// The normal of each such face is computed from its vertex positions and added to each of its vertices, weighted
+/// by the area of the face. The averaged normals are appended as new `VertexNormal` entities (one per vertex in
+/// ascending vertex order) and the faces are rewritten to reference them.\
+/// Degenerate (zero-area) faces do not contribute. Vertices that only belong to degenerate faces and indices that
+/// are out of range do not get a normal. Faces that already reference normals are left unchanged.
+pub fn compute_normals(entities: &[Entity]) -> Vec<Entity> {
+    let positions: Vec<[f64; 3]> = entities
+        .iter()
+        .filter_map(|e| match e {
+            Entity::Vertex { x, y, z, .. } => Some([*x, *y, *z]),
+            _ => None,
+        })
+        .collect();
+    let position = |v: &FaceVertex| slot(v).and_then(|i| positions.get(i).copied());
+
+    let mut faces = Vec::new();
+    let mut sums = vec![[0.0; 3]; positions.len()];
+    let mut vertices = 0;
+    let mut normals = 0;
+    for e in entities {
+        match e {
+            Entity::Vertex { .. } => vertices += 1,
+            Entity::VertexNormal { .. } => normals += 1,
+            Entity::Face { vertices: x } if x.iter().all(|v| v.normal.is_none()) => {
+                let resolved: Vec<FaceVertex> =
+                    x.iter().map(|v| v.resolve(vertices, 0, 0)).collect();
+                let points: Option<Vec<[f64; 3]>> = resolved.iter().map(position).collect();
+                if let Some(n) = points.as_deref().and_then(face_normal) {
+                    for v in &resolved {
+                        let sum = &mut sums[v.vertex as usize - 1];
+                        for (s, n) in sum.iter_mut().zip(n.iter()) {
+                            *s += n;
+                        }
+                    }
+                }
+                faces.push(resolved);
+            }
+            _ => {}
+        }
+    }
+
+    let mut indices = vec![None; positions.len()];
+    let mut added = Vec::new();
+    for (i, sum) in sums.iter().enumerate() {
+        if let Some([x, y, z]) = normalize(*sum) {
+            added.push(Entity::VertexNormal { x, y, z });
+            indices[i] = Some((normals + added.len()) as i64);
+        }
+    }
+
+    let mut faces = faces.into_iter();
+    let mut result: Vec<Entity> = entities
+        .iter()
+        .map(|e| match e {
+            Entity::Face { vertices: x } if x.iter().all(|v| v.normal.is_none()) => {
+                let resolved = faces.next().unwrap();
+                Entity::Face {
+                    vertices: x
+                        .iter()
+                        .zip(resolved)
+                        .map(|(v, r)| FaceVertex {
+                            normal: slot(&r).and_then(|i| indices.get(i).copied().flatten()),
+                            ..v.clone()
+                        })
+                        .collect(),
+                }
+            }
+            e => e.clone(),
+        })
+        .collect();
+    result.extend(added);
+    result
+}
+
+/// The 0-based position of the vertex of the given resolved `FaceVertex`.
+fn slot(v: &FaceVertex) -> Option<usize> {
+    if v.vertex < 1 {
+        None
+    } else {
+        Some(v.vertex as usize - 1)
+    }
+}
+
+/// Computes the area-weighted normal of the given polygon or `None` if it is degenerate.
+pub(crate) fn face_normal(points: &[[f64; 3]]) -> Option<[f64; 3]> {
+    let mut n = [0.0; 3];
+    for i in 1..points.len().saturating_sub(1) {
+        let a = sub(points[i], points[0]);
+        let b = sub(points[i + 1], points[0]);
+        n[0] += a[1] * b[2] - a[2] * b[1];
+        n[1] += a[2] * b[0] - a[0] * b[2];
+        n[2] += a[0] * b[1] - a[1] * b[0];
+    }
+    if n.iter().all(|x| *x == 0.0) {
+        None
+    } else {
+        Some(n)
+    }
+}
+
+fn sub(a: [f64; 3], b: [f64; 3]) -> [f64; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+pub(crate) fn normalize(v: [f64; 3]) -> Option<[f64; 3]> {
+    let length = (v[0] * v[0] + v[1] * v[1] + v[2] * v[2]).sqrt();
+    if length > 0.0 && length.is_finite() {
+        Some([v[0] / length, v[1] / length, v[2] / length])
+    } else {
+        None
+    }
+}
+
+/// Reverses the order of the vertices of every `Face`, which turns clockwise into counter-clockwise winding and
+/// vice versa.\
+/// Each vertex keeps its normal and texture index. All other entities are left unchanged.
+pub fn flip_winding(entities: &mut [Entity]) {
+    for e in entities.iter_mut() {
+        if let Entity::Face { vertices } = e {
+            vertices.reverse();
+        }
+    }
+}
+
+/// Same as `flip_winding` but also negates every `VertexNormal`, so that the normals point to the new front side.
+pub fn flip_winding_with_normals(entities: &mut [Entity]) {
+    flip_winding(entities);
+    for e in entities.iter_mut() {
+        if let Entity::VertexNormal { x, y, z } = e {
+            *x = -*x;
+            *y = -*y;
+            *z = -*z;
+        }
+    }
+}
@@ -1,199 +1,721 @@
-use crate::error::Error;
-use crate::obj::entity::{Entity, FaceVertex};
+use crate::error::{ReaderError, ReaderErrorKind};
+use crate::obj::entity::{CurveReference, Entity, FaceVertex, LineVertex, SmoothingGroup};
+use crate::obj::read_lexer::ReadOptions;
 
 pub struct LineParser {}
 
 impl LineParser {
+    /// Removes all trailing line breaks (`\n` and `\r`) of the given line, so that files with `\r\n` line endings
+    /// parse the same as files with `\n` line endings.
+    pub fn strip_line_ending(line: &mut String) {
+        let len = line.trim_end_matches(&['\r', '\n'][..]).len();
+        line.truncate(len);
+    }
+
+    /// Removes a leading UTF-8 byte order mark (`\u{FEFF}`) of the given line, which some editors write at the
+    /// start of a file.
+    pub fn strip_bom(line: &mut String) {
+        if line.starts_with('\u{FEFF}') {
+            line.drain(..'\u{FEFF}'.len_utf8());
+        }
+    }
+
+    /// Returns the given line without its trailing line continuation (`\\`) or `None` if it is not continued.
+    pub fn strip_continuation(line: &str) -> Option<&str> {
+        line.trim_end().strip_suffix('\\')
+    }
+
+    /// Removes the trailing line continuation of the given line and appends the separator for the next line.\
+    /// Returns `false` if the line is not continued.
+    pub fn begin_continuation(line: &mut String) -> bool {
+        match Self::strip_continuation(line) {
+            Some(x) => {
+                let len = x.len();
+                line.truncate(len);
+                line.push(' ');
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Parses the given line after stripping a trailing comment (everything from the first `#` on) unless the
+    /// line is a comment itself.\
+    /// The stripped comment is dropped, except for lines that are preserved as `Entity::Unknown`, which keep
+    /// the given line verbatim.
+    pub fn parse(line: &str, options: &ReadOptions) -> Result<Entity, ReaderError> {
+        Self::parse_with_warnings(line, options, &mut Vec::new())
+    }
+
+    /// Same as `parse` but appends a warning for each field that was filled in or discarded by lenient parsing
+    /// to the given warnings.
+    pub fn parse_with_warnings(
+        line: &str,
+        options: &ReadOptions,
+        warnings: &mut Vec<ReaderError>,
+    ) -> Result<Entity, ReaderError> {
+        let raw = line;
+        let line = match line.find('#') {
+            Some(_) if line.trim_start().starts_with('#') => line,
+            Some(x) => line[..x].trim_end(),
+            None => line,
+        };
+        if options.comma_separators && line.contains(',') && Self::has_numeric_fields(line) {
+            Self::parse_fields(&line.replace(',', " "), raw, options, warnings)
+        } else {
+            Self::parse_fields(line, raw, options, warnings)
+        }
+    }
+
+    /// Whether the fields of the statement of the given line are numbers or indices, in which case commas are
+    /// separators with `ReadOptions::comma_separators`. Names, files and unknown statements are not affected.
+    fn has_numeric_fields(line: &str) -> bool {
+        let token = line
+            .split(|x: char| x.is_whitespace() || x == ',')
+            .find(|x| !x.is_empty())
+            .unwrap_or_default();
+        let mut buffer = [0; 16];
+        matches!(
+            Self::lowercase(token, &mut buffer),
+            "v" | "vn"
+                | "vt"
+                | "vp"
+                | "f"
+                | "l"
+                | "p"
+                | "curv"
+                | "curv2"
+                | "surf"
+                | "parm"
+                | "deg"
+                | "step"
+                | "trim"
+                | "hole"
+                | "scrv"
+                | "sp"
+                | "lod"
+        )
+    }
+
+    fn parse_fields(
+        line: &str,
+        raw: &str,
+        options: &ReadOptions,
+        warnings: &mut Vec<ReaderError>,
+    ) -> Result<Entity, ReaderError> {
+        let mut split = line.split_whitespace();
+        match split.next() {
+            Some(x) if x.starts_with('#') => {
+                Self::parse_line(&mut split, "#", line, raw, options, warnings)
+            }
+            Some(x) => Self::parse_line(&mut split, x, line, raw, options, warnings),
+            None => Err(Self::error(
+                ReaderErrorKind::MissingField,
+                line,
+                "invalid line",
+            )),
+        }
+    }
+
+    /// Parses the fields of the given line. `raw` is the line as it was read, which is captured by
+    /// `Entity::Unknown`.
     pub fn parse_line(
         split: &mut std::str::SplitWhitespace,
         token: &str,
         line: &str,
-    ) -> Result<Entity, Error> {
-        match token.to_lowercase().as_str() {
-            "#" => Ok(Entity::Comment {
-                content: line.trim_start_matches("# ").to_owned(),
-            }),
+        raw: &str,
+        options: &ReadOptions,
+        warnings: &mut Vec<ReaderError>,
+    ) -> Result<Entity, ReaderError> {
+        let mut buffer = [0; 16];
+        match Self::lowercase(token, &mut buffer) {
+            "#" => {
+                let content = &line.trim_start()[1..];
+                Ok(Entity::Comment {
+                    content: content.strip_prefix(' ').unwrap_or(content).to_owned(),
+                })
+            }
             "o" => Ok(Entity::Object {
-                name: line.trim_start_matches("o ").to_owned(),
+                name: Self::remainder(split, line).to_owned(),
             }),
             "g" => Ok(Entity::Group {
-                name: line.trim_start_matches("g ").to_owned(),
-            }),
-            "s" => Ok(Entity::SmoothingGroup {
-                name: line.trim_start_matches("s ").to_owned(),
+                names: split.map(|x| x.to_owned()).collect(),
             }),
+            "s" => Self::parse_s(split, line),
             "mg" => Ok(Entity::MergingGroup {
-                name: line.trim_start_matches("mg ").to_owned(),
-            }),
-            "v" => Self::parse_v(split),
-            "vn" => Self::parse_vn(split),
-            "vt" => Self::parse_vt_vp(true, split),
-            "vp" => Self::parse_vt_vp(false, split),
-            "f" => Self::parse_face(split),
-            "l" => Self::parse_polyline(split),
-            "p" => Self::parse_point(split),
-            "mtllib" => {
-                if let Some(x) = split.next() {
-                    Ok(Entity::Mtllib { name: x.to_owned() })
-                } else {
-                    Err(Error::new(
-                        format!("could not parse line \"{}\"", token).as_ref(),
-                    ))
-                }
+                name: Self::remainder(split, line).to_owned(),
+            }),
+            "v" => Self::parse_v(split, line, options, warnings),
+            "vn" => Self::parse_vn(split, line, options, warnings),
+            "vt" => Self::parse_vt_vp(true, split, line, options, warnings),
+            "vp" => Self::parse_vt_vp(false, split, line, options, warnings),
+            "f" => Self::parse_face(split, line),
+            "l" => Self::parse_polyline(split, line),
+            "p" => Self::parse_point(split, line),
+            "curv" => Self::parse_curve(split, line, options),
+            "curv2" => Self::parse_curve2d(split, line),
+            "surf" => Self::parse_surface(split, line, options),
+            "cstype" => Self::parse_cstype(split, line),
+            "deg" => {
+                let (u, v) = Self::parse_uv_indices(split, token, line)?;
+                Ok(Entity::Degree { u, v })
             }
-            "usemtl" => {
-                if let Some(x) = split.next() {
-                    Ok(Entity::Usemtl { name: x.to_owned() })
-                } else {
-                    Err(Error::new(
-                        format!("could not parse line \"{}\"", token).as_ref(),
-                    ))
-                }
+            "parm" => Self::parse_parm(split, line, options),
+            "step" => {
+                let (u, v) = Self::parse_uv_indices(split, token, line)?;
+                Ok(Entity::Step { u, v })
             }
-            _ => Err(Error::new(format!("unknown token \"{}\"", token).as_ref())),
+            "trim" => Ok(Entity::Trim {
+                curves: Self::parse_curve_references(split, token, line, options)?,
+            }),
+            "hole" => Ok(Entity::Hole {
+                curves: Self::parse_curve_references(split, token, line, options)?,
+            }),
+            "scrv" => Ok(Entity::SpecialCurve {
+                curves: Self::parse_curve_references(split, token, line, options)?,
+            }),
+            "sp" => Ok(Entity::SpecialPoint {
+                vertices: Self::parse_indices(split, token, line)?,
+            }),
+            "end" => Ok(Entity::End),
+            "bevel" => Ok(Entity::Bevel {
+                enabled: Self::next_switch(split, token, line)?,
+            }),
+            "c_interp" => Ok(Entity::ColorInterpolation {
+                enabled: Self::next_switch(split, token, line)?,
+            }),
+            "d_interp" => Ok(Entity::DissolveInterpolation {
+                enabled: Self::next_switch(split, token, line)?,
+            }),
+            "lod" => Self::parse_lod(split, line),
+            "shadow_obj" => Ok(Entity::ShadowObject {
+                name: Self::next_file(split, token, line)?,
+            }),
+            "trace_obj" => Ok(Entity::TraceObject {
+                name: Self::next_file(split, token, line)?,
+            }),
+            "mtllib" => Ok(Entity::Mtllib {
+                name: Self::next_file(split, token, line)?,
+            }),
+            "usemtl" => Ok(Entity::Usemtl {
+                name: Self::next_name(split, token, line)?,
+            }),
+            "maplib" => Ok(Entity::Maplib {
+                name: Self::next_file(split, token, line)?,
+            }),
+            "usemap" => Ok(Entity::Usemap {
+                name: Self::next_name(split, token, line)?,
+            }),
+            _ if options.preserve_unknown => Ok(Entity::Unknown {
+                raw: raw.trim_end_matches(&['\r', '\n'][..]).to_owned(),
+            }),
+            _ => Err(Self::error(
+                ReaderErrorKind::UnknownToken,
+                line,
+                format!("unknown token \"{}\"", token).as_ref(),
+            )),
         }
     }
 
-    fn parse_v(split: &mut std::str::SplitWhitespace) -> Result<Entity, Error> {
-        let xs = split.next();
-        let ys = split.next();
-        let zs = split.next();
-        let ws = split.next();
-        if xs == None || ys == None || zs == None {
-            return Err(Error::new("invalid data for v"));
+    /// Lowercases the given token into the given buffer to match it without allocating.\
+    /// Tokens that do not fit the buffer cannot be known and are returned unchanged.
+    fn lowercase<'a>(token: &'a str, buffer: &'a mut [u8; 16]) -> &'a str {
+        match buffer.get_mut(..token.len()) {
+            Some(x) => {
+                x.copy_from_slice(token.as_bytes());
+                x.make_ascii_lowercase();
+                std::str::from_utf8(x).unwrap_or(token)
+            }
+            None => token,
         }
-        let x = xs.unwrap().parse::<f64>();
-        let y = ys.unwrap().parse::<f64>();
-        let z = zs.unwrap().parse::<f64>();
-        let w = match ws {
-            Some(v) => match v.parse::<f64>() {
-                Ok(v) => Some(v),
-                Err(_) => return Err(Error::new("invalid data for v")),
-            },
-            None => None,
+    }
+
+    fn error(kind: ReaderErrorKind, line: &str, msg: &str) -> ReaderError {
+        ReaderError::new(kind, line.trim_end(), msg)
+    }
+
+    /// Returns the rest of the given line starting with the next field, so that file names keep their
+    /// embedded spaces.
+    fn next_file(
+        split: &mut std::str::SplitWhitespace,
+        token: &str,
+        line: &str,
+    ) -> Result<String, ReaderError> {
+        match Self::remainder(split, line) {
+            "" => Err(Self::error(
+                ReaderErrorKind::MissingField,
+                line,
+                format!("could not parse line \"{}\"", token).as_ref(),
+            )),
+            x => Ok(x.to_owned()),
+        }
+    }
+
+    /// Consumes all remaining fields and returns the rest of the given line starting with the next field or an
+    /// empty string if there are no fields left.
+    fn remainder<'a>(split: &mut std::str::SplitWhitespace, line: &'a str) -> &'a str {
+        match split.next() {
+            Some(x) => {
+                let start = x.as_ptr() as usize - line.as_ptr() as usize;
+                split.for_each(drop);
+                line[start..].trim_end()
+            }
+            None => "",
+        }
+    }
+
+    fn next_name(
+        split: &mut std::str::SplitWhitespace,
+        token: &str,
+        line: &str,
+    ) -> Result<String, ReaderError> {
+        match split.next() {
+            Some(x) => Ok(x.to_owned()),
+            None => Err(Self::error(
+                ReaderErrorKind::MissingField,
+                line,
+                format!("could not parse line \"{}\"", token).as_ref(),
+            )),
+        }
+    }
+
+    /// Parses the next field as `on` (`true`) or `off` (`false`) ignoring its case.
+    fn next_switch(
+        split: &mut std::str::SplitWhitespace,
+        token: &str,
+        line: &str,
+    ) -> Result<bool, ReaderError> {
+        match split.next() {
+            Some(x) if x.eq_ignore_ascii_case("on") => Ok(true),
+            Some(x) if x.eq_ignore_ascii_case("off") => Ok(false),
+            Some(_) => Err(Self::error(
+                ReaderErrorKind::InvalidNumber,
+                line,
+                format!("expected on or off for {}", token).as_ref(),
+            )),
+            None => Err(Self::error(
+                ReaderErrorKind::MissingField,
+                line,
+                format!("invalid data for {}", token).as_ref(),
+            )),
+        }
+    }
+
+    /// Parses the given value with `.` as decimal separator or, if `ReadOptions::decimal_comma` is set, with
+    /// `,` as decimal separator. Values that contain both separators are rejected in either case.
+    fn parse_f64(
+        value: &str,
+        token: &str,
+        line: &str,
+        options: &ReadOptions,
+    ) -> Result<f64, ReaderError> {
+        if value.contains('.') && value.contains(',') {
+            return Err(Self::error(
+                ReaderErrorKind::InvalidNumber,
+                line,
+                format!("mixed decimal separators in {}", token).as_ref(),
+            ));
+        }
+        let result = if options.decimal_comma && value.contains(',') {
+            value.replace(',', ".").parse::<f64>()
+        } else {
+            value.parse::<f64>()
         };
-        if x.is_err() || y.is_err() || z.is_err() {
-            return Err(Error::new("invalid data for v"));
-        }
-        Ok(Entity::Vertex {
-            x: x.unwrap(),
-            y: y.unwrap(),
-            z: z.unwrap(),
-            w,
+        result.map_err(|_| {
+            Self::error(
+                ReaderErrorKind::InvalidNumber,
+                line,
+                format!("invalid data for {}", token).as_ref(),
+            )
         })
     }
 
-    fn parse_vt_vp(is_vt: bool, split: &mut std::str::SplitWhitespace) -> Result<Entity, Error> {
-        let us = split.next();
-        let vs = split.next();
-        let ws = split.next();
-        if us == None {
-            return Err(Error::new(
-                format!("invalid data for {}", if is_vt { "vt" } else { "vp" }).as_ref(),
-            ));
+    fn parse_index(value: &str, token: &str, line: &str) -> Result<i64, ReaderError> {
+        value.parse::<i64>().map_err(|_| {
+            Self::error(
+                ReaderErrorKind::InvalidNumber,
+                line,
+                format!("invalid index for {}", token).as_ref(),
+            )
+        })
+    }
+
+    fn parse_indices(
+        split: &mut std::str::SplitWhitespace,
+        token: &str,
+        line: &str,
+    ) -> Result<Vec<i64>, ReaderError> {
+        split.map(|x| Self::parse_index(x, token, line)).collect()
+    }
+
+    fn next_f64(
+        split: &mut std::str::SplitWhitespace,
+        token: &str,
+        line: &str,
+        options: &ReadOptions,
+    ) -> Result<f64, ReaderError> {
+        match split.next() {
+            Some(x) => Self::parse_f64(x, token, line, options),
+            None => Err(Self::error(
+                ReaderErrorKind::MissingField,
+                line,
+                format!("invalid data for {}", token).as_ref(),
+            )),
         }
-        let u = us.unwrap().parse::<f64>();
-        let v = match vs {
-            Some(v) => match v.parse::<f64>() {
-                Ok(v) => Some(v),
-                Err(_) => {
-                    return Err(Error::new(
-                        format!("invalid data for {}", if is_vt { "vt" } else { "vp" }).as_ref(),
-                    ))
-                }
-            },
-            None => None,
+    }
+
+    fn next_optional_f64(
+        split: &mut std::str::SplitWhitespace,
+        token: &str,
+        line: &str,
+        options: &ReadOptions,
+    ) -> Result<Option<f64>, ReaderError> {
+        match split.next() {
+            Some(x) => Ok(Some(Self::parse_f64(x, token, line, options)?)),
+            None => Ok(None),
+        }
+    }
+
+    fn parse_v(
+        split: &mut std::str::SplitWhitespace,
+        line: &str,
+        options: &ReadOptions,
+        warnings: &mut Vec<ReaderError>,
+    ) -> Result<Entity, ReaderError> {
+        let x = Self::next_f64(split, "v", line, options)?;
+        let (y, z) = if options.lenient_vertices {
+            let y = Self::next_optional_f64(split, "v", line, options)?;
+            let z = Self::next_optional_f64(split, "v", line, options)?;
+            if z.is_none() {
+                warnings.push(Self::error(
+                    ReaderErrorKind::MissingField,
+                    line,
+                    "missing coordinates of v are set to 0",
+                ));
+            }
+            (y.unwrap_or(0.0), z.unwrap_or(0.0))
+        } else {
+            (
+                Self::next_f64(split, "v", line, options)?,
+                Self::next_f64(split, "v", line, options)?,
+            )
         };
-        let w = match ws {
-            Some(v) => match v.parse::<f64>() {
-                Ok(v) => Some(v),
-                Err(_) => {
-                    return Err(Error::new(
-                        format!("invalid data for {}", if is_vt { "vt" } else { "vp" }).as_ref(),
-                    ))
-                }
-            },
-            None => None,
+        let mut rest = [0.0; 4];
+        let mut count = 0;
+        while count < rest.len() {
+            match Self::next_optional_f64(split, "v", line, options)? {
+                Some(x) => rest[count] = x,
+                None => break,
+            }
+            count += 1;
+        }
+        let (w, color) = match (count, rest) {
+            (0, _) => (None, None),
+            (3, [r, g, b, _]) => (None, Some([r, g, b])),
+            (4, [w, r, g, b]) => (Some(w), Some([r, g, b])),
+            (_, [w, ..]) => (Some(w), None),
         };
-        if u.is_err() {
-            return Err(Error::new(
-                format!("invalid data for {}", if is_vt { "vt" } else { "vp" }).as_ref(),
-            ));
+        let extra = if count == 2 { 1 } else { 0 };
+        Self::check_extra_values(extra + split.count(), "v", line, options, warnings)?;
+        if let Some(w) = w {
+            if options.strict_w && !(w > 0.0 && w.is_finite()) {
+                return Err(Self::error(
+                    ReaderErrorKind::InvalidNumber,
+                    line,
+                    format!("w of v must be positive and finite but is {}", w).as_ref(),
+                ));
+            }
         }
+        Ok(Entity::Vertex { x, y, z, w, color })
+    }
 
+    fn parse_vt_vp(
+        is_vt: bool,
+        split: &mut std::str::SplitWhitespace,
+        line: &str,
+        options: &ReadOptions,
+        warnings: &mut Vec<ReaderError>,
+    ) -> Result<Entity, ReaderError> {
+        let token = if is_vt { "vt" } else { "vp" };
+        let u = Self::next_f64(split, token, line, options)?;
+        let v = Self::next_optional_f64(split, token, line, options)?;
+        let w = Self::next_optional_f64(split, token, line, options)?;
+        Self::check_extra_values(split.count(), token, line, options, warnings)?;
         if is_vt {
-            Ok(Entity::VertexTexture {
-                u: u.unwrap(),
-                v,
-                w,
-            })
+            Ok(Entity::VertexTexture { u, v, w })
         } else {
-            Ok(Entity::VertexParameter {
-                u: u.unwrap(),
-                v,
-                w,
-            })
-        }
-    }
-
-    fn parse_vn(split: &mut std::str::SplitWhitespace) -> Result<Entity, Error> {
-        let xs = split.next();
-        let ys = split.next();
-        let zs = split.next();
-        if xs == None || ys == None || zs == None {
-            return Err(Error::new("invalid data for vn"));
-        }
-        let x = xs.unwrap().parse::<f64>();
-        let y = ys.unwrap().parse::<f64>();
-        let z = zs.unwrap().parse::<f64>();
-        if x.is_err() || y.is_err() || z.is_err() {
-            return Err(Error::new("invalid data for vn"));
-        }
-        Ok(Entity::VertexNormal {
-            x: x.unwrap(),
-            y: y.unwrap(),
-            z: z.unwrap(),
-        })
+            Ok(Entity::VertexParameter { u, v, w })
+        }
+    }
+
+    fn parse_s(split: &mut std::str::SplitWhitespace, line: &str) -> Result<Entity, ReaderError> {
+        let group = match split.next() {
+            Some(x) if x.eq_ignore_ascii_case("off") => SmoothingGroup::Off,
+            Some(x) => SmoothingGroup::Group(x.parse::<u32>().map_err(|_| {
+                Self::error(
+                    ReaderErrorKind::InvalidNumber,
+                    line,
+                    "invalid smoothing group for s",
+                )
+            })?),
+            None => {
+                return Err(Self::error(
+                    ReaderErrorKind::MissingField,
+                    line,
+                    "invalid data for s",
+                ))
+            }
+        };
+        Ok(Entity::SmoothingGroup { group })
+    }
+
+    fn parse_vn(
+        split: &mut std::str::SplitWhitespace,
+        line: &str,
+        options: &ReadOptions,
+        warnings: &mut Vec<ReaderError>,
+    ) -> Result<Entity, ReaderError> {
+        let x = Self::next_f64(split, "vn", line, options)?;
+        let y = Self::next_f64(split, "vn", line, options)?;
+        let z = Self::next_f64(split, "vn", line, options)?;
+        Self::check_extra_values(split.count(), "vn", line, options, warnings)?;
+        Ok(Entity::VertexNormal { x, y, z })
     }
 
-    fn parse_face(split: &mut std::str::SplitWhitespace) -> Result<Entity, Error> {
+    /// Appends a warning to the given warnings if the given number of extra fields is not zero, or fails if
+    /// `ReadOptions::reject_extra_values` is set.
+    fn check_extra_values(
+        count: usize,
+        token: &str,
+        line: &str,
+        options: &ReadOptions,
+        warnings: &mut Vec<ReaderError>,
+    ) -> Result<(), ReaderError> {
+        if count == 0 {
+            return Ok(());
+        }
+        let error = Self::error(
+            ReaderErrorKind::InvalidNumber,
+            line,
+            format!("too many values for {}", token).as_ref(),
+        );
+        if options.reject_extra_values {
+            Err(error)
+        } else {
+            warnings.push(error);
+            Ok(())
+        }
+    }
+
+    fn parse_face(
+        split: &mut std::str::SplitWhitespace,
+        line: &str,
+    ) -> Result<Entity, ReaderError> {
         let mut face = Vec::new();
         for vnt in split {
-            let mut vnts = vnt.split('/');
-            if let Some(v) = vnts.next() {
-                let v_parsed = v.parse::<i64>().unwrap();
-                let mut vertex = FaceVertex::new(v_parsed);
-                if let Some(vn) = vnts.next() {
-                    if vn != "" {
-                        vertex.normal = Some(vn.parse::<i64>().unwrap());
-                    }
+            face.push(Self::parse_face_vertex(vnt, "f", line)?);
+        }
+        Ok(Entity::Face { vertices: face })
+    }
+
+    pub(crate) fn parse_face_vertex(
+        vnt: &str,
+        token: &str,
+        line: &str,
+    ) -> Result<FaceVertex, ReaderError> {
+        let mut vnts = vnt.split('/');
+        if let Some(v) = vnts.next() {
+            let mut vertex = FaceVertex::new(Self::parse_index(v, token, line)?);
+            if let Some(vn) = vnts.next() {
+                if !vn.is_empty() {
+                    vertex.normal = Some(Self::parse_index(vn, token, line)?);
                 }
-                if let Some(vt) = vnts.next() {
-                    if vt != "" {
-                        vertex.texture = Some(vt.parse::<i64>().unwrap());
-                    }
+            }
+            if let Some(vt) = vnts.next() {
+                if !vt.is_empty() {
+                    vertex.texture = Some(Self::parse_index(vt, token, line)?);
                 }
-                face.push(vertex);
-            } else {
-                return Err(Error::new("could not parse face"));
             }
+            Ok(vertex)
+        } else {
+            Err(Self::error(
+                ReaderErrorKind::MissingField,
+                line,
+                format!("could not parse vertex for {}", token).as_ref(),
+            ))
         }
-        Ok(Entity::Face { vertices: face })
     }
 
-    fn parse_polyline(split: &mut std::str::SplitWhitespace) -> Result<Entity, Error> {
+    fn parse_polyline(
+        split: &mut std::str::SplitWhitespace,
+        line: &str,
+    ) -> Result<Entity, ReaderError> {
         let mut vertices = Vec::new();
-        for x in split {
-            vertices.push(x.parse::<i64>().unwrap())
+        for vt in split {
+            let mut vts = vt.split('/');
+            let mut vertex =
+                LineVertex::new(Self::parse_index(vts.next().unwrap_or(""), "l", line)?);
+            if let Some(t) = vts.next().filter(|x| !x.is_empty()) {
+                vertex.texture = Some(Self::parse_index(t, "l", line)?);
+            }
+            vertices.push(vertex);
         }
         Ok(Entity::Line { vertices })
     }
 
-    fn parse_point(split: &mut std::str::SplitWhitespace) -> Result<Entity, Error> {
+    fn parse_point(
+        split: &mut std::str::SplitWhitespace,
+        line: &str,
+    ) -> Result<Entity, ReaderError> {
+        let vertices = Self::parse_indices(split, "p", line)?;
+        Ok(Entity::Point { vertices })
+    }
+
+    fn parse_curve(
+        split: &mut std::str::SplitWhitespace,
+        line: &str,
+        options: &ReadOptions,
+    ) -> Result<Entity, ReaderError> {
+        let u_start = Self::next_f64(split, "curv", line, options)?;
+        let u_end = Self::next_f64(split, "curv", line, options)?;
+        let vertices = Self::parse_indices(split, "curv", line)?;
+        Ok(Entity::Curve {
+            u_start,
+            u_end,
+            vertices,
+        })
+    }
+
+    fn parse_curve2d(
+        split: &mut std::str::SplitWhitespace,
+        line: &str,
+    ) -> Result<Entity, ReaderError> {
+        let vertices = Self::parse_indices(split, "curv2", line)?;
+        Ok(Entity::Curve2D { vertices })
+    }
+
+    /// Parses the remaining fields as triples of `u_start`, `u_end` and `curv2` index.
+    fn parse_curve_references(
+        split: &mut std::str::SplitWhitespace,
+        token: &str,
+        line: &str,
+        options: &ReadOptions,
+    ) -> Result<Vec<CurveReference>, ReaderError> {
+        let mut curves = Vec::new();
+        while let Some(x) = split.next() {
+            let u_start = Self::parse_f64(x, token, line, options)?;
+            let u_end = Self::next_f64(split, token, line, options)?;
+            let curve = match split.next() {
+                Some(x) => Self::parse_index(x, token, line)?,
+                None => {
+                    return Err(Self::error(
+                        ReaderErrorKind::MissingField,
+                        line,
+                        format!("invalid data for {}", token).as_ref(),
+                    ))
+                }
+            };
+            curves.push(CurveReference::new(u_start, u_end, curve));
+        }
+        Ok(curves)
+    }
+
+    fn parse_surface(
+        split: &mut std::str::SplitWhitespace,
+        line: &str,
+        options: &ReadOptions,
+    ) -> Result<Entity, ReaderError> {
+        let s_start = Self::next_f64(split, "surf", line, options)?;
+        let s_end = Self::next_f64(split, "surf", line, options)?;
+        let t_start = Self::next_f64(split, "surf", line, options)?;
+        let t_end = Self::next_f64(split, "surf", line, options)?;
         let mut vertices = Vec::new();
-        for x in split {
-            vertices.push(x.parse::<i64>().unwrap())
+        for vnt in split {
+            vertices.push(Self::parse_face_vertex(vnt, "surf", line)?);
+        }
+        Ok(Entity::Surface {
+            s_start,
+            s_end,
+            t_start,
+            t_end,
+            vertices,
+        })
+    }
+
+    fn parse_cstype(
+        split: &mut std::str::SplitWhitespace,
+        line: &str,
+    ) -> Result<Entity, ReaderError> {
+        let mut kind = Self::next_name(split, "cstype", line)?;
+        let rational = kind.eq_ignore_ascii_case("rat");
+        if rational {
+            kind = Self::next_name(split, "cstype", line)?;
+        }
+        Ok(Entity::CurveSurfaceType { rational, kind })
+    }
+
+    /// Parses the remaining fields as a required `u` and an optional `v` value like in `deg` and `step`.
+    fn parse_uv_indices(
+        split: &mut std::str::SplitWhitespace,
+        token: &str,
+        line: &str,
+    ) -> Result<(i64, Option<i64>), ReaderError> {
+        let u = match split.next() {
+            Some(x) => Self::parse_index(x, token, line)?,
+            None => {
+                return Err(Self::error(
+                    ReaderErrorKind::MissingField,
+                    line,
+                    format!("invalid data for {}", token).as_ref(),
+                ))
+            }
+        };
+        let v = match split.next() {
+            Some(x) => Some(Self::parse_index(x, token, line)?),
+            None => None,
+        };
+        Ok((u, v))
+    }
+
+    fn parse_parm(
+        split: &mut std::str::SplitWhitespace,
+        line: &str,
+        options: &ReadOptions,
+    ) -> Result<Entity, ReaderError> {
+        let direction = match split.next() {
+            Some(x) if x.eq_ignore_ascii_case("u") => 'u',
+            Some(x) if x.eq_ignore_ascii_case("v") => 'v',
+            Some(_) => {
+                return Err(Self::error(
+                    ReaderErrorKind::InvalidNumber,
+                    line,
+                    "expected u or v for parm",
+                ))
+            }
+            None => {
+                return Err(Self::error(
+                    ReaderErrorKind::MissingField,
+                    line,
+                    "invalid data for parm",
+                ))
+            }
+        };
+        let values = split
+            .map(|x| Self::parse_f64(x, "parm", line, options))
+            .collect::<Result<_, _>>()?;
+        Ok(Entity::Parm { direction, values })
+    }
+
+    fn parse_lod(split: &mut std::str::SplitWhitespace, line: &str) -> Result<Entity, ReaderError> {
+        match split.next() {
+            Some(x) => Ok(Entity::LevelOfDetail {
+                level: Self::parse_index(x, "lod", line)?,
+            }),
+            None => Err(Self::error(
+                ReaderErrorKind::MissingField,
+                line,
+                "invalid data for lod",
+            )),
         }
-        Ok(Entity::Point { vertices })
     }
 }
@@ -0,0 +1,553 @@
+//! Contains a structured representation of an OBJ document that groups its elements by object.
+//!
+
+use crate::error::{ValidationError, ValidationErrorKind};
+use crate::mtl::model::MtlModel;
+use crate::obj::entity::{
+    CurveReference, Entity, FaceVertex, IndexResolver, LineVertex, SmoothingGroup,
+};
+use std::collections::HashMap;
+
+/// Groups the elements of an OBJ document by their `Object` and keeps track of the active `Group`, `Usemtl`,
+/// `SmoothingGroup`, `MergingGroup` and `Usemap` of each element.\
+/// All indices of the elements are resolved to absolute 1-based indices into the vertices (normals, textures)
+/// of the whole model.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObjModel {
+    material_libraries: Vec<String>,
+    map_libraries: Vec<String>,
+    other: Vec<Entity>,
+    vertices: Vec<Entity>,
+    normals: Vec<Entity>,
+    textures: Vec<Entity>,
+    objects: Vec<Object>,
+}
+
+/// An object of an `ObjModel` that starts with an `o` statement.\
+/// Elements before the first `o` statement belong to an object with an empty name.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Object {
+    name: String,
+    vertices: Vec<Entity>,
+    elements: Vec<Element>,
+    other: Vec<Entity>,
+}
+
+/// The flat vertex attribute arrays and triangle indices of an `ObjModel` as returned by
+/// `ObjModel::index_buffer_with_attributes`, which can be uploaded to a GPU as they are.\
+/// The attribute arrays have the same length, so that each index refers to the position, normal and texture at
+/// the same position.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct IndexBuffer {
+    pub positions: Vec<[f64; 3]>,
+    /// The normals or `[0.0, 0.0, 0.0]` for face vertices without a (valid) normal.
+    pub normals: Vec<[f64; 3]>,
+    /// The texture coordinates (`u`, `v`) or `[0.0, 0.0]` for face vertices without (valid) texture
+    /// coordinates. A missing `v` is `0.0`.
+    pub textures: Vec<[f64; 2]>,
+    /// Three indices per triangle.
+    pub indices: Vec<u32>,
+}
+
+/// A `Face`, `Line` or `Point` of an `Object` together with the groups, material, smoothing group, merging group
+/// and texture map that were active when it was declared.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Element {
+    groups: Vec<String>,
+    material: Option<String>,
+    smoothing_group: Option<SmoothingGroup>,
+    merging_group: Option<String>,
+    map: Option<String>,
+    entity: Entity,
+}
+
+impl ObjModel {
+    /// Walks the given entities in order and tracks the active `o`, `g`, `usemtl`, `s`, `mg` and `usemap` state
+    /// and the referenced `mtllib` and `maplib` files.\
+    /// New objects reset the active group but keep all other state. All other entities (e.g. free-form
+    /// statements, comments and `Unknown` lines) are kept in document order with the active object.
+    pub fn from_entities(entities: &[Entity]) -> Self {
+        let mut model = Self::default();
+        let mut resolver = IndexResolver::default();
+        let mut groups = Vec::new();
+        let mut material = None;
+        let mut smoothing_group = None;
+        let mut merging_group = None;
+        let mut map = None;
+        for e in entities {
+            let e = resolver.resolve(e.clone());
+            match e {
+                Entity::Object { name } => {
+                    groups = Vec::new();
+                    model.objects.push(Object {
+                        name,
+                        ..Default::default()
+                    });
+                }
+                Entity::Group { names } => groups = names,
+                Entity::Usemtl { name } => material = Some(name),
+                Entity::SmoothingGroup { group } => smoothing_group = Some(group),
+                Entity::MergingGroup { name } => merging_group = Some(name),
+                Entity::Usemap { name } => map = Some(name),
+                Entity::Mtllib { name } => model.material_libraries.push(name),
+                Entity::Maplib { name } => model.map_libraries.push(name),
+                Entity::Vertex { .. } => {
+                    model.current().vertices.push(e.clone());
+                    model.vertices.push(e);
+                }
+                Entity::VertexNormal { .. } => model.normals.push(e),
+                Entity::VertexTexture { .. } => model.textures.push(e),
+                Entity::Face { .. } | Entity::Line { .. } | Entity::Point { .. } => {
+                    model.current().elements.push(Element {
+                        groups: groups.clone(),
+                        material: material.clone(),
+                        smoothing_group,
+                        merging_group: merging_group.clone(),
+                        map: map.clone(),
+                        entity: e,
+                    });
+                }
+                e => match model.objects.last_mut() {
+                    Some(x) => x.other.push(e),
+                    None => model.other.push(e),
+                },
+            }
+        }
+        model
+    }
+
+    /// All objects in document order.
+    pub fn objects(&self) -> &[Object] {
+        &self.objects
+    }
+
+    /// The files of all `Mtllib` entities in document order.
+    pub fn material_libraries(&self) -> &[String] {
+        &self.material_libraries
+    }
+
+    /// The files of all `Maplib` entities in document order.
+    pub fn map_libraries(&self) -> &[String] {
+        &self.map_libraries
+    }
+
+    /// The entities before the first object that are neither elements nor state (see `Object::other`).
+    pub fn other(&self) -> &[Entity] {
+        &self.other
+    }
+
+    /// All `Vertex` entities of the model.
+    pub fn vertices(&self) -> &[Entity] {
+        &self.vertices
+    }
+
+    /// All `VertexNormal` entities of the model.
+    pub fn normals(&self) -> &[Entity] {
+        &self.normals
+    }
+
+    /// All `VertexTexture` entities of the model.
+    pub fn textures(&self) -> &[Entity] {
+        &self.textures
+    }
+
+    /// Yields the positions (`x`, `y`, `z`) of the vertices of every `Face` of all objects in document order.\
+    /// Faces with a vertex index that is out of range are skipped.
+    pub fn faces_with_positions(&self) -> impl Iterator<Item = Vec<[f64; 3]>> + '_ {
+        self.faces_with_positions_strict().filter_map(|x| x.ok())
+    }
+
+    /// Same as `faces_with_positions` but yields a `ValidationError` of kind `Vertex` for faces with a vertex
+    /// index that is out of range instead of skipping them.\
+    /// The entity of the error is the 0-based position of the face among all faces of the model.
+    pub fn faces_with_positions_strict(
+        &self,
+    ) -> impl Iterator<Item = Result<Vec<[f64; 3]>, ValidationError>> + '_ {
+        self.objects
+            .iter()
+            .flat_map(|x| x.faces())
+            .enumerate()
+            .map(move |(i, e)| match e {
+                Entity::Face { vertices } => vertices
+                    .iter()
+                    .map(|v| {
+                        self.position(v.vertex).ok_or_else(|| {
+                            ValidationError::new(ValidationErrorKind::Vertex, i, v.vertex)
+                        })
+                    })
+                    .collect(),
+                _ => Ok(Vec::new()),
+            })
+    }
+
+    /// The position of the vertex with the given 1-based index or `None` if it is out of range.
+    fn position(&self, index: i64) -> Option<[f64; 3]> {
+        match self.vertices.get((index - 1) as usize) {
+            Some(Entity::Vertex { x, y, z, .. }) if index > 0 => Some([*x, *y, *z]),
+            _ => None,
+        }
+    }
+
+    /// Triangulates all faces and returns the positions of the referenced vertices without duplicates together
+    /// with three indices into them per triangle, which is the layout of vertex and index buffers of GPU
+    /// pipelines.\
+    /// Faces are fan triangulated and triangles with a vertex index that is out of range are skipped. The
+    /// positions are ordered by their first use.
+    pub fn index_buffer(&self) -> (Vec<[f64; 3]>, Vec<u32>) {
+        let mut positions = Vec::new();
+        let mut indices = Vec::new();
+        let mut slots: HashMap<i64, u32> = HashMap::new();
+        for v in self.triangles().flatten() {
+            let slot = slots.entry(v.vertex).or_insert_with(|| {
+                positions.push(self.position(v.vertex).unwrap());
+                (positions.len() - 1) as u32
+            });
+            indices.push(*slot);
+        }
+        (positions, indices)
+    }
+
+    /// Same as `index_buffer` but also returns the normals and texture coordinates of the face vertices.\
+    /// Face vertices are only shared if their vertex, normal and texture indices are equal, because the
+    /// attributes of a GPU vertex cannot be indexed separately.
+    pub fn index_buffer_with_attributes(&self) -> IndexBuffer {
+        let mut result = IndexBuffer::default();
+        let mut slots: HashMap<(i64, Option<i64>, Option<i64>), u32> = HashMap::new();
+        for v in self.triangles().flatten() {
+            let slot = slots
+                .entry((v.vertex, v.normal, v.texture))
+                .or_insert_with(|| {
+                    result.positions.push(self.position(v.vertex).unwrap());
+                    result
+                        .normals
+                        .push(self.normal(v.normal).unwrap_or([0.0; 3]));
+                    result
+                        .textures
+                        .push(self.texture(v.texture).unwrap_or([0.0; 2]));
+                    (result.positions.len() - 1) as u32
+                });
+            result.indices.push(*slot);
+        }
+        result
+    }
+
+    /// Fan triangulates all faces and skips triangles with a vertex index that is out of range.
+    fn triangles(&self) -> impl Iterator<Item = [&FaceVertex; 3]> + '_ {
+        self.objects
+            .iter()
+            .flat_map(|x| x.faces())
+            .flat_map(|e| match e {
+                Entity::Face { vertices } if vertices.len() >= 3 => (1..vertices.len() - 1)
+                    .map(|i| [&vertices[0], &vertices[i], &vertices[i + 1]])
+                    .collect(),
+                _ => Vec::new(),
+            })
+            .filter(move |t| t.iter().all(|v| self.position(v.vertex).is_some()))
+    }
+
+    /// The normal with the given 1-based index or `None` if there is none or it is out of range.
+    fn normal(&self, index: Option<i64>) -> Option<[f64; 3]> {
+        let index = index?;
+        match self.normals.get((index - 1) as usize) {
+            Some(Entity::VertexNormal { x, y, z }) if index > 0 => Some([*x, *y, *z]),
+            _ => None,
+        }
+    }
+
+    /// The texture coordinates with the given 1-based index or `None` if there are none or it is out of range.
+    fn texture(&self, index: Option<i64>) -> Option<[f64; 2]> {
+        let index = index?;
+        match self.textures.get((index - 1) as usize) {
+            Some(Entity::VertexTexture { u, v, .. }) if index > 0 => Some([*u, v.unwrap_or(0.0)]),
+            _ => None,
+        }
+    }
+
+    /// Converts the model back into entities with the entities of `other` and all `Mtllib` and `Maplib` entities
+    /// first, followed by all `Vertex`, `VertexNormal` and `VertexTexture` entities and the elements of each
+    /// object grouped by their material.\
+    /// The other entities of an object (see `Object::other`) are emitted in document order before its elements,
+    /// so that free-form statements keep their order but not their position relative to the elements.\
+    /// The materials of an object are ordered by their first use and each group is preceded by a single `Usemtl`
+    /// unless the material is already active. A `Group`, `SmoothingGroup`, `MergingGroup` or `Usemap` is emitted
+    /// whenever the respective state changes, where a state that was not set yet is emitted as `off`. Objects
+    /// with an empty name are emitted as a bare `o`, except for the elements before the first `o` statement.
+    pub fn to_entities_by_material(&self) -> Vec<Entity> {
+        let mut result = self.other.clone();
+        result.extend(
+            self.material_libraries
+                .iter()
+                .map(|x| Entity::Mtllib { name: x.clone() }),
+        );
+        result.extend(
+            self.map_libraries
+                .iter()
+                .map(|x| Entity::Maplib { name: x.clone() }),
+        );
+        result.extend(self.vertices.iter().cloned());
+        result.extend(self.normals.iter().cloned());
+        result.extend(self.textures.iter().cloned());
+        let mut active = None;
+        let mut smoothing_group = None;
+        let mut merging_group = None;
+        let mut map = None;
+        for (i, object) in self.objects.iter().enumerate() {
+            if i > 0 || !object.name.is_empty() {
+                result.push(Entity::Object {
+                    name: object.name.clone(),
+                });
+            }
+            result.extend(object.other.iter().cloned());
+            let mut materials = Vec::new();
+            for x in &object.elements {
+                if !materials.contains(&x.material()) {
+                    materials.push(x.material());
+                }
+            }
+            let mut groups: &[String] = &[];
+            for material in materials {
+                if let Some(name) = material.filter(|_| material != active) {
+                    result.push(Entity::Usemtl {
+                        name: name.to_owned(),
+                    });
+                    active = material;
+                }
+                for x in object.elements.iter().filter(|x| x.material() == material) {
+                    if x.groups != groups {
+                        result.push(Entity::Group {
+                            names: x.groups.clone(),
+                        });
+                        groups = &x.groups;
+                    }
+                    if x.smoothing_group != smoothing_group {
+                        result.push(Entity::SmoothingGroup {
+                            group: x.smoothing_group.unwrap_or(SmoothingGroup::Off),
+                        });
+                        smoothing_group = x.smoothing_group;
+                    }
+                    if x.merging_group != merging_group {
+                        result.push(Entity::MergingGroup {
+                            name: x.merging_group.clone().unwrap_or_else(|| "off".to_owned()),
+                        });
+                        merging_group = x.merging_group.clone();
+                    }
+                    if x.map != map {
+                        result.push(Entity::Usemap {
+                            name: x.map.clone().unwrap_or_else(|| "off".to_owned()),
+                        });
+                        map = x.map.clone();
+                    }
+                    result.push(x.entity.clone());
+                }
+            }
+        }
+        result
+    }
+
+    fn current(&mut self) -> &mut Object {
+        if self.objects.is_empty() {
+            self.objects.push(Object::default());
+        }
+        self.objects.last_mut().unwrap()
+    }
+}
+
+impl Object {
+    /// The name of the object.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The `Vertex` entities that were declared while this object was active.
+    pub fn vertices(&self) -> &[Entity] {
+        &self.vertices
+    }
+
+    /// The entities of the object that are neither elements nor state (e.g. `vp`, `cstype`, `curv`, `surf`,
+    /// `end`, comments and `Unknown` lines) in document order with resolved indices.
+    pub fn other(&self) -> &[Entity] {
+        &self.other
+    }
+
+    /// All elements of the object in document order.
+    pub fn elements(&self) -> &[Element] {
+        &self.elements
+    }
+
+    /// The `Face` entities of the object in document order.
+    pub fn faces(&self) -> impl Iterator<Item = &Entity> {
+        self.elements
+            .iter()
+            .map(|x| &x.entity)
+            .filter(|x| matches!(x, Entity::Face { .. }))
+    }
+}
+
+impl Element {
+    /// The name of the first active group or `None` if no group was active.
+    pub fn group(&self) -> Option<&str> {
+        self.groups.first().map(|x| x.as_str())
+    }
+
+    /// The names of all active groups, which is empty if no group was active.
+    pub fn groups(&self) -> &[String] {
+        &self.groups
+    }
+
+    /// The name of the active material or `None` if no material was active.
+    pub fn material(&self) -> Option<&str> {
+        self.material.as_deref()
+    }
+
+    /// The active smoothing group or `None` if no `s` statement preceded the element.
+    pub fn smoothing_group(&self) -> Option<SmoothingGroup> {
+        self.smoothing_group
+    }
+
+    /// The name of the active merging group or `None` if no `mg` statement preceded the element.
+    pub fn merging_group(&self) -> Option<&str> {
+        self.merging_group.as_deref()
+    }
+
+    /// The name of the active texture map or `None` if no `usemap` statement preceded the element.
+    pub fn map(&self) -> Option<&str> {
+        self.map.as_deref()
+    }
+
+    /// The `Face`, `Line` or `Point` entity with resolved indices.
+    pub fn entity(&self) -> &Entity {
+        &self.entity
+    }
+}
+
+/// Concatenates the given documents into one.\
+/// The absolute (positive) indices of every document are offset by the number of vertices (normals, textures,
+/// vertex parameters, 2D curves) of all previous documents so that they keep referring to the same
+/// declarations.
+/// Relative (negative) indices stay valid and are left unchanged, same as all other entities.
+pub fn merge(docs: &[Vec<Entity>]) -> Vec<Entity> {
+    let mut result = Vec::new();
+    let mut offsets = [0; 5];
+    for doc in docs {
+        let mut counts = [0; 5];
+        for e in doc {
+            let [vertices, normals, textures, parameters, curves] = offsets;
+            let v = |x: &Vec<i64>, by| x.iter().map(|i| offset(*i, by)).collect();
+            let curve = |x: &Vec<CurveReference>| {
+                x.iter()
+                    .map(|c| CurveReference::new(c.u_start, c.u_end, offset(c.curve, curves)))
+                    .collect()
+            };
+            let face = |x: &Vec<FaceVertex>| {
+                x.iter()
+                    .map(|v| FaceVertex {
+                        vertex: offset(v.vertex, vertices),
+                        normal: v.normal.map(|i| offset(i, normals)),
+                        texture: v.texture.map(|i| offset(i, textures)),
+                    })
+                    .collect()
+            };
+            result.push(match e {
+                Entity::Vertex { .. } => {
+                    counts[0] += 1;
+                    e.clone()
+                }
+                Entity::VertexNormal { .. } => {
+                    counts[1] += 1;
+                    e.clone()
+                }
+                Entity::VertexTexture { .. } => {
+                    counts[2] += 1;
+                    e.clone()
+                }
+                Entity::VertexParameter { .. } => {
+                    counts[3] += 1;
+                    e.clone()
+                }
+                Entity::Face { vertices: x } => Entity::Face { vertices: face(x) },
+                Entity::Surface {
+                    s_start,
+                    s_end,
+                    t_start,
+                    t_end,
+                    vertices: x,
+                } => Entity::Surface {
+                    s_start: *s_start,
+                    s_end: *s_end,
+                    t_start: *t_start,
+                    t_end: *t_end,
+                    vertices: face(x),
+                },
+                Entity::Line { vertices: x } => Entity::Line {
+                    vertices: x
+                        .iter()
+                        .map(|v| LineVertex {
+                            vertex: offset(v.vertex, vertices),
+                            texture: v.texture.map(|i| offset(i, textures)),
+                        })
+                        .collect(),
+                },
+                Entity::Point { vertices: x } => Entity::Point {
+                    vertices: v(x, vertices),
+                },
+                Entity::Curve {
+                    u_start,
+                    u_end,
+                    vertices: x,
+                } => Entity::Curve {
+                    u_start: *u_start,
+                    u_end: *u_end,
+                    vertices: v(x, vertices),
+                },
+                Entity::Curve2D { vertices: x } => {
+                    counts[4] += 1;
+                    Entity::Curve2D {
+                        vertices: v(x, parameters),
+                    }
+                }
+                Entity::Trim { curves: x } => Entity::Trim { curves: curve(x) },
+                Entity::Hole { curves: x } => Entity::Hole { curves: curve(x) },
+                Entity::SpecialCurve { curves: x } => Entity::SpecialCurve { curves: curve(x) },
+                Entity::SpecialPoint { vertices: x } => Entity::SpecialPoint {
+                    vertices: v(x, parameters),
+                },
+                e => e.clone(),
+            });
+        }
+        for (o, c) in offsets.iter_mut().zip(counts.iter()) {
+            *o += c;
+        }
+    }
+    result
+}
+
+/// Checks that every material referenced by a `Usemtl` is declared in the given material library.\
+/// Will return `Ok(())` if all materials are declared or the names of the missing materials in order of their
+/// first reference, each listed once.
+pub fn resolve_materials(obj: &[Entity], mtl: &MtlModel) -> Result<(), Vec<String>> {
+    let mut missing: Vec<String> = Vec::new();
+    for e in obj {
+        if let Entity::Usemtl { name } = e {
+            if mtl.material(name).is_none() && !missing.contains(name) {
+                missing.push(name.clone());
+            }
+        }
+    }
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(missing)
+    }
+}
+
+fn offset(index: i64, by: i64) -> i64 {
+    if index > 0 {
+        index + by
+    } else {
+        index
+    }
+}
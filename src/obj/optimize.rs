@@ -0,0 +1,302 @@
+//! Contains passes that reduce the size of an OBJ document without changing its geometry.
+//!
+
+use crate::obj::entity::{Entity, FaceVertex, IndexResolver, LineVertex};
+use std::collections::HashMap;
+
+/// Contains the number of declarations that have been removed by a pass.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Removed {
+    pub vertices: usize,
+    pub normals: usize,
+    pub textures: usize,
+}
+
+/// Same as `dedup_vertices_with_stats` but only returns the entities.
+pub fn dedup_vertices(entities: &[Entity], epsilon: f64) -> Vec<Entity> {
+    dedup_vertices_with_stats(entities, epsilon).0
+}
+
+/// Merges all `Vertex`, `VertexNormal` and `VertexTexture` declarations that lie within `epsilon` (euclidean
+/// distance) of an earlier declaration of the same kind into that earlier declaration.\
+/// Vertices are only merged if their `w` and `color` are equal and textures if they set the same components.
+/// The duplicates are dropped and all indices of `Face`, `Surface`, `Line`, `Point` and `Curve` entities are
+/// rewritten to the remaining declarations, which resolves relative indices into absolute ones. Indices that
+/// are out of range are left unchanged.\
+/// Returns the entities together with the number of removed declarations.
+pub fn dedup_vertices_with_stats(entities: &[Entity], epsilon: f64) -> (Vec<Entity>, Removed) {
+    let mut grids = [Grid::new(epsilon), Grid::new(epsilon), Grid::new(epsilon)];
+    let mut remaps = [Remap::default(), Remap::default(), Remap::default()];
+    for e in entities {
+        let (kind, point, signature) = match e {
+            Entity::Vertex { x, y, z, w, color } => {
+                let c = |i: usize| color.map(|x| x[i].to_bits());
+                (0, [*x, *y, *z], [w.map(|x| x.to_bits()), c(0), c(1), c(2)])
+            }
+            Entity::VertexNormal { x, y, z } => (1, [*x, *y, *z], [None; 4]),
+            Entity::VertexTexture { u, v, w } => (
+                2,
+                [*u, v.unwrap_or(0.0), w.unwrap_or(0.0)],
+                [v.map(|_| 0), w.map(|_| 0), None, None],
+            ),
+            _ => continue,
+        };
+        let remap = &mut remaps[kind];
+        match grids[kind].find(point, signature) {
+            Some(target) => remap.drop(target),
+            None => {
+                let target = remap.keep();
+                grids[kind].insert(point, signature, target);
+            }
+        }
+    }
+    let removed = Removed {
+        vertices: remaps[0].removed(),
+        normals: remaps[1].removed(),
+        textures: remaps[2].removed(),
+    };
+    (rewrite(entities, &remaps), removed)
+}
+
+/// Same as `remove_unused_vertices_with_stats` but only returns the entities.
+pub fn remove_unused_vertices(entities: &[Entity]) -> Vec<Entity> {
+    remove_unused_vertices_with_stats(entities).0
+}
+
+/// Removes all `Vertex`, `VertexNormal` and `VertexTexture` declarations that are not referenced by any `Face`,
+/// `Surface`, `Line`, `Point` or `Curve` entity.\
+/// The remaining declarations are compacted and all indices are rewritten accordingly, which resolves relative
+/// indices into absolute ones. Indices that are out of range are left unchanged.\
+/// Returns the entities together with the number of removed declarations.
+pub fn remove_unused_vertices_with_stats(entities: &[Entity]) -> (Vec<Entity>, Removed) {
+    let mut used: [Vec<bool>; 3] = Default::default();
+    for e in entities {
+        match e {
+            Entity::Vertex { .. } => used[0].push(false),
+            Entity::VertexNormal { .. } => used[1].push(false),
+            Entity::VertexTexture { .. } => used[2].push(false),
+            _ => {}
+        }
+    }
+    let mut resolver = IndexResolver::default();
+    for e in entities {
+        for (kind, indices) in references(&resolver.resolve(e.clone())) {
+            for i in indices.into_iter().filter(|i| *i > 0) {
+                if let Some(x) = used[kind].get_mut(i as usize - 1) {
+                    *x = true;
+                }
+            }
+        }
+    }
+    let remaps = used.map(|x| {
+        let mut remap = Remap::default();
+        for used in x {
+            if used {
+                remap.keep();
+            } else {
+                remap.drop(0);
+            }
+        }
+        remap
+    });
+    let removed = Removed {
+        vertices: remaps[0].removed(),
+        normals: remaps[1].removed(),
+        textures: remaps[2].removed(),
+    };
+    (rewrite(entities, &remaps), removed)
+}
+
+/// Returns the indices of the given resolved entity grouped by the kind of declaration they refer to (vertices,
+/// normals, textures).
+fn references(e: &Entity) -> Vec<(usize, Vec<i64>)> {
+    match e {
+        Entity::Face { vertices } | Entity::Surface { vertices, .. } => vec![
+            (0, vertices.iter().map(|x| x.vertex).collect()),
+            (1, vertices.iter().filter_map(|x| x.normal).collect()),
+            (2, vertices.iter().filter_map(|x| x.texture).collect()),
+        ],
+        Entity::Line { vertices } => vec![
+            (0, vertices.iter().map(|x| x.vertex).collect()),
+            (2, vertices.iter().filter_map(|x| x.texture).collect()),
+        ],
+        Entity::Point { vertices } | Entity::Curve { vertices, .. } => vec![(0, vertices.clone())],
+        _ => Vec::new(),
+    }
+}
+
+/// Maps the 1-based indices of one kind of declaration to their new 1-based indices.
+#[derive(Default)]
+struct Remap {
+    targets: Vec<i64>,
+    keep: Vec<bool>,
+    kept: i64,
+}
+
+impl Remap {
+    /// Keeps the next declaration and returns its new index.
+    fn keep(&mut self) -> i64 {
+        self.kept += 1;
+        self.targets.push(self.kept);
+        self.keep.push(true);
+        self.kept
+    }
+
+    /// Drops the next declaration and redirects its references to the given new index.
+    fn drop(&mut self, target: i64) {
+        self.targets.push(target);
+        self.keep.push(false);
+    }
+
+    fn removed(&self) -> usize {
+        self.keep.iter().filter(|x| !**x).count()
+    }
+
+    fn map(&self, index: i64) -> i64 {
+        match self.targets.get((index - 1) as usize) {
+            Some(x) if index > 0 => *x,
+            _ => index,
+        }
+    }
+}
+
+/// The components of a declaration that have to be equal for it to be merged, compared by their bits.
+type Signature = [Option<u64>; 4];
+
+/// A point together with its signature and the new index that references to it are redirected to.
+type Entry = ([f64; 3], Signature, i64);
+
+/// Finds points within a distance by hashing them into cells with the size of that distance.
+struct Grid {
+    epsilon: f64,
+    cells: HashMap<[i64; 3], Vec<Entry>>,
+}
+
+impl Grid {
+    fn new(epsilon: f64) -> Self {
+        Self {
+            epsilon,
+            cells: HashMap::new(),
+        }
+    }
+
+    fn cell(&self, point: [f64; 3]) -> [i64; 3] {
+        if self.epsilon > 0.0 {
+            point.map(|x| (x / self.epsilon).floor() as i64)
+        } else {
+            point.map(|x| (x + 0.0).to_bits() as i64)
+        }
+    }
+
+    /// Returns the target of the first inserted point within the distance that has the same signature.
+    fn find(&self, point: [f64; 3], signature: Signature) -> Option<i64> {
+        let [x, y, z] = self.cell(point);
+        let distance = |p: &[f64; 3]| {
+            ((p[0] - point[0]).powi(2) + (p[1] - point[1]).powi(2) + (p[2] - point[2]).powi(2))
+                .sqrt()
+        };
+        let mut result: Option<i64> = None;
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let key = [x.wrapping_add(dx), y.wrapping_add(dy), z.wrapping_add(dz)];
+                    for (p, s, target) in self.cells.get(&key).into_iter().flatten() {
+                        if *s == signature
+                            && distance(p) <= self.epsilon
+                            && result.is_none_or(|x| *target < x)
+                        {
+                            result = Some(*target);
+                        }
+                    }
+                }
+            }
+        }
+        result
+    }
+
+    fn insert(&mut self, point: [f64; 3], signature: Signature, target: i64) {
+        let key = self.cell(point);
+        self.cells
+            .entry(key)
+            .or_default()
+            .push((point, signature, target));
+    }
+}
+
+/// Drops the declarations that are not kept by the given remaps (vertices, normals, textures) and rewrites all
+/// indices that refer to them.
+fn rewrite(entities: &[Entity], remaps: &[Remap; 3]) -> Vec<Entity> {
+    let [vertices, normals, textures] = remaps;
+    let mut resolver = IndexResolver::default();
+    let mut counts = [0; 3];
+    let mut result = Vec::new();
+    let v = |x: Vec<i64>| x.into_iter().map(|i| vertices.map(i)).collect();
+    let face = |x: Vec<FaceVertex>| {
+        x.into_iter()
+            .map(|x| FaceVertex {
+                vertex: vertices.map(x.vertex),
+                normal: x.normal.map(|i| normals.map(i)),
+                texture: x.texture.map(|i| textures.map(i)),
+            })
+            .collect()
+    };
+    let line = |x: Vec<LineVertex>| {
+        x.into_iter()
+            .map(|x| LineVertex {
+                vertex: vertices.map(x.vertex),
+                texture: x.texture.map(|i| textures.map(i)),
+            })
+            .collect()
+    };
+    for e in entities {
+        let kind = match e {
+            Entity::Vertex { .. } => Some(0),
+            Entity::VertexNormal { .. } => Some(1),
+            Entity::VertexTexture { .. } => Some(2),
+            _ => None,
+        };
+        let e = resolver.resolve(e.clone());
+        if let Some(kind) = kind {
+            counts[kind] += 1;
+            if !remaps[kind].keep[counts[kind] - 1] {
+                continue;
+            }
+        }
+        result.push(match e {
+            Entity::Face { vertices } => Entity::Face {
+                vertices: face(vertices),
+            },
+            Entity::Surface {
+                s_start,
+                s_end,
+                t_start,
+                t_end,
+                vertices,
+            } => Entity::Surface {
+                s_start,
+                s_end,
+                t_start,
+                t_end,
+                vertices: face(vertices),
+            },
+            Entity::Line { vertices } => Entity::Line {
+                vertices: line(vertices),
+            },
+            Entity::Point { vertices } => Entity::Point {
+                vertices: v(vertices),
+            },
+            Entity::Curve {
+                u_start,
+                u_end,
+                vertices,
+            } => Entity::Curve {
+                u_start,
+                u_end,
+                vertices: v(vertices),
+            },
+            e => e,
+        });
+    }
+    result
+}
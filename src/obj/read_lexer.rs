@@ -1,50 +1,105 @@
 //! Contains logic to read entities from a `BufRead` that returns OBJ formatted strings.
 //!
 
-use crate::error::Error;
+use crate::error::{ReaderError, ReaderErrorKind};
 use crate::obj::entity::Entity;
 use crate::obj::line_parser::LineParser;
+use crate::obj::reader::EntityIterator;
 use std::io::BufRead;
 use std::result::Result;
 
+/// Options that control how lines are parsed.
+#[derive(Debug, Clone, Default)]
+pub struct ReadOptions {
+    /// Captures lines with unknown tokens verbatim (including a trailing comment) as `Entity::Unknown` instead
+    /// of failing.\
+    /// Trailing comments of all other statements (e.g. `v 1 2 3 # origin`) are dropped.
+    pub preserve_unknown: bool,
+    /// Rejects `Vertex` entities whose `w` is set but not positive and finite (e.g. `v 1 2 3 0`).
+    pub strict_w: bool,
+    /// Drops `Entity::Comment` values when reading multiple entities (see `EntityIterator`) instead of yielding
+    /// them.
+    pub skip_comments: bool,
+    /// Rejects `Face` entities with an index that is zero or exceeds the number of vertices (normals, textures)
+    /// declared before them with a `ReaderError` of kind `IndexOutOfRange`.\
+    /// Only applies when reading multiple entities (see `EntityIterator`), which counts the declarations while
+    /// reading.
+    pub validate_face_indices: bool,
+    /// Treats commas as field separators in addition to whitespace (e.g. `v 1.0, 2.0, 3.0`), which is written
+    /// by some non-standard exporters.\
+    /// Only applies to statements with number or index fields (e.g. `v`, `f` or `curv`), so that comments,
+    /// names (e.g. `usemtl a,b`), files (e.g. `mtllib my,file.mtl`) and unknown statements are left unchanged.
+    pub comma_separators: bool,
+    /// Parses floats with `,` instead of `.` as decimal separator (e.g. `v 1,5 2,0 0`), which is written by
+    /// exporters that use a European locale.\
+    /// Floats are parsed with `.` as decimal separator by default. Values that contain both separators (e.g.
+    /// `1.000,5`) are rejected with a `ReaderError` of kind `InvalidNumber` in either case. Has no effect in
+    /// combination with `comma_separators`, which splits such values first.
+    pub decimal_comma: bool,
+    /// Fills missing `y` and `z` of `Vertex` entities (e.g. `v 1.0 2.0` of 2D geometry) with `0.0` instead of
+    /// failing with a `ReaderError` of kind `MissingField`.\
+    /// Each filled line is reported as warning when reading multiple entities (see `EntityIterator::warnings`).
+    pub lenient_vertices: bool,
+    /// Rejects extra trailing fields of `Vertex` (more than `x`, `y`, `z`, `w` and color, or a fifth value
+    /// without color), `VertexNormal` (more than `x`, `y` and `z`), `VertexTexture` and `VertexParameter`
+    /// entities (more than `u`, `v` and `w`) with a `ReaderError` of kind `InvalidNumber`.\
+    /// By default such fields (e.g. of `vn 0 0 1 0` or `v 1 2 3 1 0`) are discarded and each such line is reported as warning
+    /// when reading multiple entities (see `EntityIterator::warnings`).
+    pub reject_extra_values: bool,
+}
+
 /// Will read from a given `BufRead` and parse entities.
 pub struct ReadLexer {}
 
 impl ReadLexer {
     /// Will read from the given `BufRead`as long as it is not EOF.\
     /// When an entity is parsed, the given callback is invoked and the entity is inserted into it as parameter.\
-    /// Will return `Ok(())` if successful or an `Error` (if parsing failed).
-    pub fn read_to_end<R: BufRead>(reader: &mut R, callback: impl Fn(Entity)) -> Result<(), Error> {
-        for l in reader.lines() {
-            let s: String = l?;
-            let mut split = s.split_whitespace();
-            if let Some(x) = split.next() {
-                match LineParser::parse_line(&mut split, x, s.as_ref()) {
-                    Ok(x) => callback(x),
-                    Err(x) => return Err(x),
-                }
-            }
+    /// Will return `Ok(())` if successful or a `ReaderError` (if parsing failed).
+    pub fn read_to_end<R: BufRead>(
+        reader: &mut R,
+        callback: impl Fn(Entity),
+    ) -> Result<(), ReaderError> {
+        for e in Self::entities(reader) {
+            callback(e?);
         }
         Ok(())
     }
 
     /// Will read from the given `BufRead` until the first encountered linebreak.\
-    /// Will return `Ok(Entity)` if successful or an `Error` (if parsing failed).
-    pub fn read_line<R: BufRead>(reader: &mut R) -> Result<Entity, Error> {
+    /// The line break itself (`\n` or `\r\n`) is not part of the parsed line.\
+    /// Lines ending with a backslash (`\\`) are continued by the following line.\
+    /// Will return `Ok(Entity)` if successful or a `ReaderError` (if parsing failed).
+    pub fn read_line<R: BufRead>(reader: &mut R) -> Result<Entity, ReaderError> {
+        Self::read_line_with(reader, &ReadOptions::default())
+    }
+
+    /// Same as `read_line` but parses the line according to the given `ReadOptions`.
+    pub fn read_line_with<R: BufRead>(
+        reader: &mut R,
+        options: &ReadOptions,
+    ) -> Result<Entity, ReaderError> {
         let value = &mut String::new();
-        match reader.read_line(value) {
-            Ok(x) => {
-                if x > 0 {
-                    let mut split = value.split_whitespace();
-                    match split.next() {
-                        Some(x) => LineParser::parse_line(&mut split, x, value.as_ref()),
-                        None => Err(Error::new("invalid line")),
-                    }
-                } else {
-                    Err(Error::new("reached EOF"))
+        if reader.read_line(value)? > 0 {
+            LineParser::strip_line_ending(value);
+            while LineParser::begin_continuation(value) {
+                if reader.read_line(value)? == 0 {
+                    break;
                 }
+                LineParser::strip_line_ending(value);
             }
-            Err(x) => Err(Error::from(x)),
+            LineParser::parse(value, options)
+        } else {
+            Err(ReaderError::new(
+                ReaderErrorKind::EndOfFile,
+                "",
+                "reached EOF",
+            ))
         }
     }
+
+    /// Returns an iterator over all entities of the given `BufRead`.\
+    /// Blank lines are skipped and the iteration ends at EOF.
+    pub fn entities<R: BufRead>(reader: R) -> EntityIterator<R> {
+        EntityIterator::new(reader)
+    }
 }
@@ -0,0 +1,271 @@
+//! Contains logic to read whole OBJ documents at once or entity by entity.
+//!
+
+use crate::error::{ReaderError, ReaderErrorKind};
+use crate::obj::entity::{Entity, IndexResolver};
+use crate::obj::line_parser::LineParser;
+use crate::obj::read_lexer::ReadOptions;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+use std::result::Result;
+
+/// Will read from the given `BufRead` as long as it is not EOF and collect all parsed entities in file order.\
+/// Blank lines are skipped and lines ending with a backslash (`\\`) are continued by the following line.\
+/// A UTF-8 byte order mark at the start of the first line is ignored.\
+/// Will return `Ok(Vec<Entity>)` if successful or a `ReaderError` containing the failing line number.\
+/// Warnings of lenient parsing (e.g. discarded extra values) are dropped, see `read_all_with_warnings`.
+pub fn read_all<R: BufRead>(reader: &mut R) -> Result<Vec<Entity>, ReaderError> {
+    read_all_with(reader, &ReadOptions::default())
+}
+
+/// Same as `read_all` but opens the file at the given path and reads it buffered.\
+/// Will return a `ReaderError` of kind `Io` if the file cannot be opened.
+pub fn read_file<P: AsRef<Path>>(path: P) -> Result<Vec<Entity>, ReaderError> {
+    let file = File::open(path)?;
+    read_all(&mut BufReader::new(file))
+}
+
+/// Same as `read_all` but parses the lines according to the given `ReadOptions`.
+pub fn read_all_with<R: BufRead>(
+    reader: &mut R,
+    options: &ReadOptions,
+) -> Result<Vec<Entity>, ReaderError> {
+    EntityIterator::with_options(reader, options.clone()).collect()
+}
+
+/// Same as `read_all_with` but also returns the warnings of lenient parsing (see `EntityIterator::warnings`).
+pub fn read_all_with_warnings<R: BufRead>(
+    reader: &mut R,
+    options: &ReadOptions,
+) -> Result<(Vec<Entity>, Vec<ReaderError>), ReaderError> {
+    let mut iterator = EntityIterator::with_options(reader, options.clone());
+    let entities = iterator.by_ref().collect::<Result<Vec<_>, _>>()?;
+    Ok((entities, iterator.warnings))
+}
+
+/// Same as `read_all` but converts all relative (negative) indices into absolute 1-based indices.\
+/// The indices of `Face`, `Surface`, `Line`, `Point` and `Curve` entities are resolved against the vertices
+/// (normals, textures) declared before them and those of `Curve2D` against the vertex parameters, which means
+/// that the returned entities never contain relative indices.
+pub fn read_all_resolved<R: BufRead>(reader: &mut R) -> Result<Vec<Entity>, ReaderError> {
+    Reader::new(reader).resolve_relative(true).collect()
+}
+
+/// Same as `read_all` but parses the lines of the given string in parallel on the `rayon` thread pool.\
+/// The entities are returned in file order and if multiple lines fail, the error of the first one is returned.\
+/// Whether this is faster than `read_all` depends on the size of the document and the number of cores, run
+/// `make bench` to compare both on the current machine. On a single core with the 17 MiB benchmark document
+/// (100k `v`, `vn` and `vt` and 200k `f`), `par_read_all` took 270 ms compared to 238 ms of `read_all`.
+/// Multi-core machines have not been measured yet.\
+/// Requires the `rayon` feature.
+#[cfg(feature = "rayon")]
+pub fn par_read_all(input: &str) -> Result<Vec<Entity>, ReaderError> {
+    use rayon::prelude::*;
+    let options = ReadOptions::default();
+    let mut lines: Vec<(usize, std::borrow::Cow<str>)> = Vec::new();
+    let mut continued = false;
+    let input = input.strip_prefix('\u{FEFF}').unwrap_or(input);
+    for (i, x) in input.lines().enumerate() {
+        match lines.last_mut() {
+            Some((_, last)) if continued => last.to_mut().push_str(x),
+            _ => lines.push((i + 1, x.into())),
+        }
+        let (_, last) = lines.last_mut().unwrap();
+        continued = LineParser::strip_continuation(last).is_some();
+        if continued {
+            LineParser::begin_continuation(last.to_mut());
+        }
+    }
+    let results: Vec<Option<Result<Entity, ReaderError>>> = lines
+        .par_iter()
+        .map(|(i, x)| parse_non_blank(x, *i, &options, &mut Vec::new()))
+        .collect();
+    results.into_iter().flatten().collect()
+}
+
+fn parse_non_blank(
+    line: &str,
+    number: usize,
+    options: &ReadOptions,
+    warnings: &mut Vec<ReaderError>,
+) -> Option<Result<Entity, ReaderError>> {
+    if line.trim().is_empty() {
+        return None;
+    }
+    let mut found = Vec::new();
+    let result = LineParser::parse_with_warnings(line, options, &mut found);
+    warnings.extend(found.into_iter().map(|x| x.with_line(number)));
+    Some(result.map_err(|e| e.with_line(number)))
+}
+
+/// Iterates over the entities of a `BufRead`, yielding one parsed `Entity` per non-empty line.\
+/// A UTF-8 byte order mark at the start of the first line is ignored.\
+/// Comments are skipped as well if `ReadOptions::skip_comments` is set.\
+/// Blank lines are skipped, continued lines are joined and `None` is returned at EOF.\
+/// Errors of continued lines report the number of the first physical line.\
+/// Fields that are filled in or discarded by lenient parsing (e.g. `ReadOptions::lenient_vertices` or
+/// discarded extra values) are collected as warnings, see `warnings`.\
+/// All lines are read into the same buffer, so that reading does not allocate per line.
+pub struct EntityIterator<R: BufRead> {
+    reader: R,
+    buffer: String,
+    line: usize,
+    options: ReadOptions,
+    counts: IndexResolver,
+    warnings: Vec<ReaderError>,
+}
+
+impl<R: BufRead> EntityIterator<R> {
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, ReadOptions::default())
+    }
+
+    /// Creates an iterator that parses the lines according to the given `ReadOptions`.
+    pub fn with_options(reader: R, options: ReadOptions) -> Self {
+        Self {
+            reader,
+            buffer: String::new(),
+            line: 0,
+            options,
+            counts: IndexResolver::default(),
+            warnings: Vec::new(),
+        }
+    }
+
+    /// The warnings of the lines read so far in file order, each containing the line number.
+    pub fn warnings(&self) -> &[ReaderError] {
+        &self.warnings
+    }
+
+    /// Counts the given entity and fails if it is a `Face` with an index that is out of range.
+    fn check(&mut self, e: Entity, line: usize) -> Result<Entity, ReaderError> {
+        self.counts.count(&e);
+        match self.counts.face_out_of_range(&e) {
+            Some((kind, index)) => Err(ReaderError::new(
+                ReaderErrorKind::IndexOutOfRange,
+                self.buffer.trim_end(),
+                format!("{} index {} of f is out of range", kind, index).as_ref(),
+            )
+            .with_line(line)),
+            None => Ok(e),
+        }
+    }
+
+    /// Appends the next line without its line break to the buffer.\
+    /// Returns `Ok(false)` at EOF.
+    fn read_line(&mut self) -> std::io::Result<bool> {
+        if self.reader.read_line(&mut self.buffer)? == 0 {
+            return Ok(false);
+        }
+        LineParser::strip_line_ending(&mut self.buffer);
+        Ok(true)
+    }
+}
+
+impl<R: BufRead> Iterator for EntityIterator<R> {
+    type Item = Result<Entity, ReaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.buffer.clear();
+            self.line += 1;
+            match self.read_line() {
+                Ok(true) => {}
+                Ok(false) => return None,
+                Err(x) => return Some(Err(ReaderError::from(x).with_line(self.line))),
+            }
+            if self.line == 1 {
+                LineParser::strip_bom(&mut self.buffer);
+            }
+            let first = self.line;
+            while LineParser::begin_continuation(&mut self.buffer) {
+                match self.read_line() {
+                    Ok(true) => {}
+                    Ok(false) => break,
+                    Err(x) => return Some(Err(ReaderError::from(x).with_line(self.line + 1))),
+                }
+                self.line += 1;
+            }
+            match parse_non_blank(&self.buffer, first, &self.options, &mut self.warnings) {
+                Some(Ok(Entity::Comment { .. })) if self.options.skip_comments => {}
+                Some(Ok(e)) if self.options.validate_face_indices => {
+                    return Some(self.check(e, first))
+                }
+                Some(x) => return Some(x),
+                None => {}
+            }
+        }
+    }
+}
+
+/// Reads the entities of a `BufRead` like `EntityIterator` while keeping track of the number of vertices
+/// (normals, textures, vertex parameters) declared so far.\
+/// Relative (negative) indices are resolved into absolute 1-based indices on the fly if enabled with
+/// `resolve_relative`.
+pub struct Reader<R: BufRead> {
+    entities: EntityIterator<R>,
+    counts: IndexResolver,
+    resolve: bool,
+}
+
+impl<R: BufRead> Reader<R> {
+    pub fn new(reader: R) -> Self {
+        Self::with_options(reader, ReadOptions::default())
+    }
+
+    /// Creates a reader that parses the lines according to the given `ReadOptions`.
+    pub fn with_options(reader: R, options: ReadOptions) -> Self {
+        Self {
+            entities: EntityIterator::with_options(reader, options),
+            counts: IndexResolver::default(),
+            resolve: false,
+        }
+    }
+
+    /// Sets whether relative indices of the read entities are resolved into absolute indices.
+    pub fn resolve_relative(mut self, resolve: bool) -> Self {
+        self.resolve = resolve;
+        self
+    }
+
+    /// The number of `Vertex` entities read so far.
+    pub fn vertices(&self) -> usize {
+        self.counts.vertices
+    }
+
+    /// The number of `VertexNormal` entities read so far.
+    pub fn normals(&self) -> usize {
+        self.counts.normals
+    }
+
+    /// The number of `VertexTexture` entities read so far.
+    pub fn textures(&self) -> usize {
+        self.counts.textures
+    }
+
+    /// The number of `VertexParameter` entities read so far.
+    pub fn parameters(&self) -> usize {
+        self.counts.parameters
+    }
+
+    /// The warnings of the lines read so far, see `EntityIterator::warnings`.
+    pub fn warnings(&self) -> &[ReaderError] {
+        self.entities.warnings()
+    }
+}
+
+impl<R: BufRead> Iterator for Reader<R> {
+    type Item = Result<Entity, ReaderError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.entities.next()?.map(|e| {
+            if self.resolve {
+                self.counts.resolve(e)
+            } else {
+                self.counts.count(&e);
+                e
+            }
+        }))
+    }
+}
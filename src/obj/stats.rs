@@ -0,0 +1,64 @@
+//! Contains helpers to summarize the entities of a document.
+//!
+
+use crate::obj::entity::Entity;
+use std::collections::HashMap;
+
+/// Counts the given entities by their `token()`.\
+/// `Entity::Unknown` values are counted by the token of their raw line.
+pub fn count_by_token(entities: &[Entity]) -> HashMap<&str, usize> {
+    let mut result = HashMap::new();
+    for e in entities {
+        *result.entry(e.token()).or_insert(0) += 1;
+    }
+    result
+}
+
+/// Contains the number of the most common entities of a document.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EntityStats {
+    /// The number of `Vertex` entities.
+    pub vertices: usize,
+    /// The number of `VertexNormal` entities.
+    pub normals: usize,
+    /// The number of `VertexTexture` entities.
+    pub textures: usize,
+    /// The number of `VertexParameter` entities.
+    pub parameters: usize,
+    /// The number of `Face` entities.
+    pub faces: usize,
+    /// The number of `Line` entities.
+    pub lines: usize,
+    /// The number of `Point` entities.
+    pub points: usize,
+    /// The number of `Object` entities.
+    pub objects: usize,
+    /// The number of `Group` entities.
+    pub groups: usize,
+    /// The number of `Comment` entities.
+    pub comments: usize,
+}
+
+impl EntityStats {
+    /// Counts the given entities. Entities without a field of their own are not counted.
+    pub fn from_entities(entities: &[Entity]) -> Self {
+        let mut result = Self::default();
+        for e in entities {
+            match e {
+                Entity::Vertex { .. } => result.vertices += 1,
+                Entity::VertexNormal { .. } => result.normals += 1,
+                Entity::VertexTexture { .. } => result.textures += 1,
+                Entity::VertexParameter { .. } => result.parameters += 1,
+                Entity::Face { .. } => result.faces += 1,
+                Entity::Line { .. } => result.lines += 1,
+                Entity::Point { .. } => result.points += 1,
+                Entity::Object { .. } => result.objects += 1,
+                Entity::Group { .. } => result.groups += 1,
+                Entity::Comment { .. } => result.comments += 1,
+                _ => {}
+            }
+        }
+        result
+    }
+}
@@ -1,9 +1,10 @@
 //! Contains logic to read entities from OBJ formatted strings.
 //!
 
-use crate::error::Error;
+use crate::error::ReaderError;
 use crate::obj::entity::Entity;
 use crate::obj::line_parser::LineParser;
+use crate::obj::read_lexer::ReadOptions;
 use std::result::Result;
 
 /// Will parse obj formatted string.
@@ -13,11 +14,7 @@ pub struct StringLexer {}
 impl StringLexer {
     /// Tries to read parse an `Entity` from the given string until the first encountered linebreak.\
     /// For parsing multiple entities, consider using the `ReadLexer`.
-    pub fn read_line(line: &str) -> Result<Entity, Error> {
-        let mut split = line.split_whitespace();
-        match split.next() {
-            Some(x) => LineParser::parse_line(&mut split, x, line),
-            None => Err(Error::new("invalid line")),
-        }
+    pub fn read_line(line: &str) -> Result<Entity, ReaderError> {
+        LineParser::parse(line, &ReadOptions::default())
     }
 }
@@ -0,0 +1,79 @@
+//! Contains in-place transformations of the vertices of an OBJ document.
+//!
+
+use crate::obj::entity::Entity;
+use crate::obj::geometry::bounding_box;
+
+/// Multiplies `x`, `y` and `z` of every `Vertex` with the respective component of the given factor.\
+/// `w` and all other entities are left unchanged.
+pub fn scale(entities: &mut [Entity], factor: [f64; 3]) {
+    for e in entities.iter_mut() {
+        if let Entity::Vertex { x, y, z, .. } = e {
+            *x *= factor[0];
+            *y *= factor[1];
+            *z *= factor[2];
+        }
+    }
+}
+
+/// Same as `scale` but also transforms every `VertexNormal` so that it stays perpendicular to the scaled
+/// surface.\
+/// Normals are divided by the factor and renormalized to unit length. Normals that become zero or not finite
+/// (e.g. for a factor of `0`) are left unchanged.
+pub fn scale_with_normals(entities: &mut [Entity], factor: [f64; 3]) {
+    scale(entities, factor);
+    for e in entities.iter_mut() {
+        if let Entity::VertexNormal { x, y, z } = e {
+            let n = [*x / factor[0], *y / factor[1], *z / factor[2]];
+            let length = (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt();
+            if length > 0.0 && length.is_finite() {
+                *x = n[0] / length;
+                *y = n[1] / length;
+                *z = n[2] / length;
+            }
+        }
+    }
+}
+
+/// Adds the given offset to `x`, `y` and `z` of every `Vertex`.\
+/// `w` and all other entities are left unchanged.
+pub fn translate(entities: &mut [Entity], offset: [f64; 3]) {
+    for e in entities.iter_mut() {
+        if let Entity::Vertex { x, y, z, .. } = e {
+            *x += offset[0];
+            *y += offset[1];
+            *z += offset[2];
+        }
+    }
+}
+
+/// Moves all vertices so that the center of their bounding box lies at the origin.\
+/// Returns the applied offset (the negated center), which can be passed to `translate` with inverted sign to
+/// undo the operation, or `[0.0; 3]` if there are no vertices.
+pub fn center(entities: &mut [Entity]) -> [f64; 3] {
+    let offset = match bounding_box(entities) {
+        Some(b) => {
+            let c = b.center();
+            [-c[0], -c[1], -c[2]]
+        }
+        None => return [0.0; 3],
+    };
+    translate(entities, offset);
+    offset
+}
+
+/// Centers all vertices at the origin and scales them uniformly so that the largest extent of their bounding box
+/// becomes `1`, which means that they fit into the cube from `-0.5` to `0.5`.\
+/// Normals stay valid because the scaling is uniform. Vertices that all lie on the same point are only centered
+/// and documents without vertices are left unchanged.
+pub fn normalize_to_unit_cube(entities: &mut [Entity]) {
+    let b = match bounding_box(entities) {
+        Some(x) => x,
+        None => return,
+    };
+    center(entities);
+    let extent = (0..3).map(|i| b.max[i] - b.min[i]).fold(0.0, f64::max);
+    if extent > 0.0 {
+        scale(entities, [1.0 / extent; 3]);
+    }
+}
@@ -0,0 +1,107 @@
+//! Contains the logic to check the references between entities of a document.
+//!
+
+use crate::error::{ValidationError, ValidationErrorKind};
+use crate::obj::entity::Entity;
+
+/// Checks that every index of the `Face`, `Line` and `Point` entities refers to an existing `Vertex`,
+/// `VertexNormal` or `VertexTexture`.\
+/// Positive indices must not exceed the number of declarations in the whole document. Negative indices are
+/// resolved against the declarations seen so far (see `FaceVertex::resolve`) and must not point before the
+/// first one. Index `0` is always invalid.\
+/// Returns all found errors in document order or an empty vec if the document is valid.
+pub fn validate(entities: &[Entity]) -> Vec<ValidationError> {
+    let mut totals = Counts::default();
+    for e in entities {
+        totals.count(e);
+    }
+    let mut seen = Counts::default();
+    let mut errors = Vec::new();
+    for (position, e) in entities.iter().enumerate() {
+        seen.count(e);
+        let mut check = |kind, index, declared, total| {
+            if !is_valid(index, declared, total) {
+                errors.push(ValidationError::new(kind, position, index));
+            }
+        };
+        match e {
+            Entity::Face { vertices } => {
+                for v in vertices {
+                    check(
+                        ValidationErrorKind::Vertex,
+                        v.vertex,
+                        seen.vertices,
+                        totals.vertices,
+                    );
+                    if let Some(x) = v.normal {
+                        check(ValidationErrorKind::Normal, x, seen.normals, totals.normals);
+                    }
+                    if let Some(x) = v.texture {
+                        check(
+                            ValidationErrorKind::Texture,
+                            x,
+                            seen.textures,
+                            totals.textures,
+                        );
+                    }
+                }
+            }
+            Entity::Line { vertices } => {
+                for v in vertices {
+                    check(
+                        ValidationErrorKind::Vertex,
+                        v.vertex,
+                        seen.vertices,
+                        totals.vertices,
+                    );
+                    if let Some(x) = v.texture {
+                        check(
+                            ValidationErrorKind::Texture,
+                            x,
+                            seen.textures,
+                            totals.textures,
+                        );
+                    }
+                }
+            }
+            Entity::Point { vertices } => {
+                for v in vertices {
+                    check(
+                        ValidationErrorKind::Vertex,
+                        *v,
+                        seen.vertices,
+                        totals.vertices,
+                    );
+                }
+            }
+            _ => {}
+        }
+    }
+    errors
+}
+
+#[derive(Default)]
+struct Counts {
+    vertices: usize,
+    normals: usize,
+    textures: usize,
+}
+
+impl Counts {
+    fn count(&mut self, e: &Entity) {
+        match e {
+            Entity::Vertex { .. } => self.vertices += 1,
+            Entity::VertexNormal { .. } => self.normals += 1,
+            Entity::VertexTexture { .. } => self.textures += 1,
+            _ => {}
+        }
+    }
+}
+
+fn is_valid(index: i64, declared: usize, total: usize) -> bool {
+    if index < 0 {
+        declared as i64 + index >= 0
+    } else {
+        index >= 1 && index as usize <= total
+    }
+}
@@ -0,0 +1,50 @@
+//! Contains logic to write whole OBJ documents at once.
+//!
+
+use crate::error::WriterError;
+use crate::obj::entity::Entity;
+use crate::obj::format_writer::{FormatOptions, FormatWriter, LineEnding};
+use crate::obj::model::ObjModel;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::result::Result;
+
+/// Writes all given entities to the given `Write` trait, each on its own line with a terminating `\n`.\
+/// The writer is flushed afterwards.\
+/// Will return `Ok(())` if successful or a `WriterError` (if writing failed).
+pub fn write_all<W: Write>(writer: &mut W, entities: &[Entity]) -> Result<(), WriterError> {
+    write_iter(writer, entities.iter().cloned())
+}
+
+/// Same as `write_all` but writes the entities yielded by the given iterator one by one, so that they do not have
+/// to be collected first.
+pub fn write_iter<W: Write, I: IntoIterator<Item = Entity>>(
+    writer: &mut W,
+    iter: I,
+) -> Result<(), WriterError> {
+    let options = FormatOptions {
+        line_ending: LineEnding::Lf,
+        ..Default::default()
+    };
+    for e in iter {
+        FormatWriter::write_entity(writer, &e, &options)?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Same as `write_all` but creates (or truncates) the file at the given path and writes it buffered.
+pub fn write_file<P: AsRef<Path>>(path: P, entities: &[Entity]) -> Result<(), WriterError> {
+    let file = File::create(path)?;
+    write_all(&mut BufWriter::new(file), entities)
+}
+
+/// Same as `write_all` but writes the given model with its elements grouped by material (see
+/// `ObjModel::to_entities_by_material`), which needs only one `usemtl` per material and object.
+pub fn write_model_by_material<W: Write>(
+    writer: &mut W,
+    model: &ObjModel,
+) -> Result<(), WriterError> {
+    write_all(writer, &model.to_entities_by_material())
+}
@@ -0,0 +1,107 @@
+use std::fs::File;
+use std::io::{BufReader, Read};
+
+extern crate wavefront_rs;
+use wavefront_rs::error::ReaderErrorKind;
+use wavefront_rs::mtl::entity::{Entity, TextureMapOptions};
+use wavefront_rs::mtl::read_lexer::ReadLexer;
+use wavefront_rs::mtl::reader::read_file;
+use wavefront_rs::mtl::writer::{write_file, Writer};
+
+#[test]
+fn test_materials_smoke() {
+    let file = File::open("./tests/mtl/integration/resources/materials.mtl").unwrap();
+    ReadLexer::read_to_end(&mut BufReader::new(file), |_| {}).unwrap();
+}
+
+#[test]
+fn test_materials_read_write() {
+    let source = File::open("./tests/mtl/integration/resources/materials.mtl").unwrap();
+    let mut source_content = String::new();
+    File::open("./tests/mtl/integration/resources/materials.mtl")
+        .unwrap()
+        .read_to_string(&mut source_content)
+        .unwrap();
+    let dest = std::cell::RefCell::new(Vec::new());
+    ReadLexer::read_to_end(&mut BufReader::new(source), |e| {
+        let mut local_writer = dest.borrow_mut();
+        Writer::write(&mut *local_writer, &e).unwrap();
+        local_writer.push(b'\n');
+    })
+    .unwrap();
+    assert_eq!(
+        source_content.replace("\n\n", "\n"),
+        String::from_utf8(dest.take()).unwrap()
+    );
+}
+
+#[test]
+fn test_materials_entity_round_trip() {
+    let dest = std::cell::RefCell::new(Vec::new());
+    let file = File::open("./tests/mtl/integration/resources/materials.mtl").unwrap();
+    ReadLexer::read_to_end(&mut BufReader::new(file), |e| dest.borrow_mut().push(e)).unwrap();
+    for e in dest.take() {
+        let mut written = Vec::new();
+        Writer::write(&mut written, &e).unwrap();
+        assert_eq!(
+            e,
+            ReadLexer::read_line(&mut BufReader::new(written.as_slice())).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_materials_read_write_file() {
+    let source_content =
+        std::fs::read_to_string("./tests/mtl/integration/resources/materials.mtl").unwrap();
+    let entities = read_file("./tests/mtl/integration/resources/materials.mtl").unwrap();
+    let path = std::env::temp_dir().join("wavefront_rs_materials.mtl");
+    write_file(&path, &entities).unwrap();
+    let dest_content = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(source_content.replace("\n\n", "\n"), dest_content);
+}
+
+#[test]
+fn test_read_file_missing() {
+    let error = read_file("./tests/mtl/integration/resources/missing.mtl").unwrap_err();
+    assert_eq!(ReaderErrorKind::Io, error.kind());
+}
+
+#[test]
+fn test_crlf_read_line() {
+    let file = File::open("./tests/mtl/integration/resources/crlf.mtl").unwrap();
+    let mut reader = BufReader::new(file);
+    assert_eq!(
+        Entity::MaterialName {
+            name: "shiny".to_owned()
+        },
+        ReadLexer::read_line(&mut reader).unwrap()
+    );
+    assert_eq!(
+        Entity::SpecularHighlights { value: 3.4 },
+        ReadLexer::read_line(&mut reader).unwrap()
+    );
+    assert_eq!(
+        Entity::TextureMapDiffuse {
+            file: "shiny.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        ReadLexer::read_line(&mut reader).unwrap()
+    );
+}
+
+#[test]
+fn test_bom_read_file() {
+    assert_eq!(
+        vec![
+            Entity::Comment {
+                content: "shiny".to_owned()
+            },
+            Entity::MaterialName {
+                name: "shiny".to_owned()
+            },
+        ],
+        read_file("./tests/mtl/integration/resources/bom.mtl").unwrap()
+    );
+}
@@ -0,0 +1,635 @@
+extern crate wavefront_rs;
+use wavefront_rs::mtl::entity::*;
+
+#[test]
+fn test_token_comment() {
+    assert_eq!(
+        "#",
+        Entity::Comment {
+            content: "token".to_owned(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_material_name() {
+    assert_eq!(
+        "newmtl",
+        Entity::MaterialName {
+            name: "token".to_owned(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_ambient_color() {
+    assert_eq!(
+        "Ka",
+        Entity::AmbientColor {
+            r: 0.1,
+            g: 0.2,
+            b: 0.3,
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_diffuse_color() {
+    assert_eq!(
+        "Kd",
+        Entity::DiffuseColor {
+            r: 0.1,
+            g: 0.2,
+            b: 0.3,
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_specular_color() {
+    assert_eq!(
+        "Ks",
+        Entity::SpecularColor {
+            r: 0.1,
+            g: 0.2,
+            b: 0.3,
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_specular_highlights() {
+    assert_eq!("Ns", Entity::SpecularHighlights { value: 10.5 }.token());
+}
+
+#[test]
+fn test_token_optical_density() {
+    assert_eq!("Ni", Entity::OpticalDensity { value: 1.5 }.token());
+}
+
+#[test]
+fn test_token_sharpness() {
+    assert_eq!("sharpness", Entity::Sharpness { value: 60.0 }.token());
+}
+
+#[test]
+fn test_token_dissolve() {
+    assert_eq!("d", Entity::Dissolve { value: 0.9 }.token());
+}
+
+#[test]
+fn test_token_inverted_dissolve() {
+    assert_eq!("Tr", Entity::InvertedDissolve { value: 0.1 }.token());
+}
+
+#[test]
+fn test_token_illum() {
+    assert_eq!(
+        "illum",
+        Entity::Illum {
+            mode: IllumModel::HighlightOn,
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_texture_map_ambient() {
+    assert_eq!(
+        "map_Ka",
+        Entity::TextureMapAmbient {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_texture_map_diffuse() {
+    assert_eq!(
+        "map_Kd",
+        Entity::TextureMapDiffuse {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_texture_map_specular() {
+    assert_eq!(
+        "map_Ks",
+        Entity::TextureMapSpecular {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_texture_map_specular_highlights() {
+    assert_eq!(
+        "map_Ns",
+        Entity::TextureMapSpecularHighlights {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_texture_map_alpha() {
+    assert_eq!(
+        "map_d",
+        Entity::TextureMapAlpha {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_bump_map() {
+    assert_eq!(
+        "bump",
+        Entity::BumpMap {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_displacement_map() {
+    assert_eq!(
+        "disp",
+        Entity::DisplacementMap {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_stencil_decal_map() {
+    assert_eq!(
+        "decal",
+        Entity::StencilDecalMap {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_spherical_reflection_map() {
+    assert_eq!(
+        "refl",
+        Entity::SphericalReflectionMap {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_reflection_map() {
+    assert_eq!(
+        "refl",
+        Entity::ReflectionMap {
+            kind: ReflectionType::Sphere,
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_reflection_type_names() {
+    for name in &[
+        "sphere",
+        "cube_top",
+        "cube_bottom",
+        "cube_front",
+        "cube_back",
+        "cube_left",
+        "cube_right",
+    ] {
+        let kind = ReflectionType::from_name(name).unwrap();
+        assert_eq!(*name, kind.name());
+        assert_eq!(*name, kind.to_string());
+    }
+    assert_eq!(None, ReflectionType::from_name("cube"));
+}
+
+#[test]
+fn test_token_transmission_filter_color_rgb() {
+    assert_eq!(
+        "Tf",
+        Entity::TransmissionFilterColorRGB {
+            r: 0.1,
+            g: 0.2,
+            b: 0.3,
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_from_str_diffuse_color() {
+    assert_eq!(
+        Entity::DiffuseColor {
+            r: 1.0,
+            g: 1.0,
+            b: 1.0,
+        },
+        "Kd 1 1 1".parse::<Entity>().unwrap()
+    );
+}
+
+#[test]
+fn test_from_str_invalid() {
+    assert_eq!(
+        wavefront_rs::error::ReaderErrorKind::InvalidNumber,
+        "Kd 1 x 1".parse::<Entity>().unwrap_err().kind()
+    );
+}
+
+#[test]
+fn test_display() {
+    let entity = Entity::TextureMapDiffuse {
+        file: "token.png".to_owned(),
+        options: TextureMapOptions {
+            scale: Some(vec![2.0, 2.0, 1.0]),
+            order: vec!["-s".to_owned()],
+            ..Default::default()
+        },
+    };
+    assert_eq!("map_Kd -s 2 2 1 token.png", entity.to_string());
+    assert_eq!(entity, entity.to_string().parse::<Entity>().unwrap());
+}
+
+#[test]
+fn test_display_alignment() {
+    let entity = Entity::Illum {
+        mode: IllumModel::HighlightOn,
+    };
+    assert_eq!("[illum 2   ]", format!("[{:<10}]", entity));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_json_round_trip() {
+    let entities = vec![
+        Entity::Comment {
+            content: "token".to_owned(),
+        },
+        Entity::MaterialName {
+            name: "token".to_owned(),
+        },
+        Entity::AmbientColor {
+            r: 0.1,
+            g: 0.2,
+            b: 0.3,
+        },
+        Entity::DiffuseColor {
+            r: 0.1,
+            g: 0.2,
+            b: 0.3,
+        },
+        Entity::SpecularColor {
+            r: 0.1,
+            g: 0.2,
+            b: 0.3,
+        },
+        Entity::SpecularHighlights { value: 10.0 },
+        Entity::OpticalDensity { value: 1.5 },
+        Entity::Dissolve { value: 0.9 },
+        Entity::InvertedDissolve { value: 0.1 },
+        Entity::Illum {
+            mode: IllumModel::HighlightOn,
+        },
+        Entity::TextureMapAmbient {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        Entity::TextureMapDiffuse {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        Entity::TextureMapSpecular {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        Entity::TextureMapSpecularHighlights {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        Entity::TextureMapAlpha {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        Entity::BumpMap {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        Entity::DisplacementMap {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        Entity::StencilDecalMap {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        Entity::SphericalReflectionMap {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        Entity::TransmissionFilterColorRGB {
+            r: 0.1,
+            g: 0.2,
+            b: 0.3,
+        },
+    ];
+    let json = serde_json::to_string(&entities).unwrap();
+    assert_eq!(
+        entities,
+        serde_json::from_str::<Vec<Entity>>(&json).unwrap()
+    );
+}
+
+#[test]
+fn test_token_emissive_color() {
+    assert_eq!(
+        "Ke",
+        Entity::EmissiveColor {
+            r: 0.1,
+            g: 0.2,
+            b: 0.3,
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_roughness() {
+    assert_eq!("Pr", Entity::Roughness { value: 0.5 }.token());
+}
+
+#[test]
+fn test_token_metallic() {
+    assert_eq!("Pm", Entity::Metallic { value: 0.5 }.token());
+}
+
+#[test]
+fn test_token_sheen() {
+    assert_eq!("Ps", Entity::Sheen { value: 0.5 }.token());
+}
+
+#[test]
+fn test_token_clearcoat_thickness() {
+    assert_eq!("Pc", Entity::ClearcoatThickness { value: 0.5 }.token());
+}
+
+#[test]
+fn test_token_clearcoat_roughness() {
+    assert_eq!("Pcr", Entity::ClearcoatRoughness { value: 0.5 }.token());
+}
+
+#[test]
+fn test_token_texture_map_roughness() {
+    assert_eq!(
+        "map_Pr",
+        Entity::TextureMapRoughness {
+            file: "roughness.png".to_owned(),
+            options: TextureMapOptions::default(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_texture_map_metallic() {
+    assert_eq!(
+        "map_Pm",
+        Entity::TextureMapMetallic {
+            file: "metallic.png".to_owned(),
+            options: TextureMapOptions::default(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_texture_map_sheen() {
+    assert_eq!(
+        "map_Ps",
+        Entity::TextureMapSheen {
+            file: "sheen.png".to_owned(),
+            options: TextureMapOptions::default(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_texture_map_emissive() {
+    assert_eq!(
+        "map_Ke",
+        Entity::TextureMapEmissive {
+            file: "emissive.png".to_owned(),
+            options: TextureMapOptions::default(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_normal_map() {
+    assert_eq!(
+        "norm",
+        Entity::NormalMap {
+            file: "normal.png".to_owned(),
+            options: TextureMapOptions::default(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_transmission_filter_spectral() {
+    assert_eq!(
+        "Tf",
+        Entity::TransmissionFilterSpectral {
+            file: "token.rfl".to_owned(),
+            factor: Some(0.5),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_transmission_filter_spectral_without_factor() {
+    assert_eq!(
+        "Tf",
+        Entity::TransmissionFilterSpectral {
+            file: "token.rfl".to_owned(),
+            factor: None,
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_transmission_filter_xyz() {
+    assert_eq!(
+        "Tf",
+        Entity::TransmissionFilterXYZ {
+            x: 0.1,
+            y: 0.2,
+            z: 0.3,
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_ambient_color_spectral() {
+    assert_eq!(
+        "Ka",
+        Entity::AmbientColorSpectral {
+            file: "token.rfl".to_owned(),
+            factor: Some(0.5),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_ambient_color_xyz() {
+    assert_eq!(
+        "Ka",
+        Entity::AmbientColorXYZ {
+            x: 0.1,
+            y: 0.2,
+            z: 0.3,
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_diffuse_color_spectral() {
+    assert_eq!(
+        "Kd",
+        Entity::DiffuseColorSpectral {
+            file: "token.rfl".to_owned(),
+            factor: Some(0.5),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_diffuse_color_xyz() {
+    assert_eq!(
+        "Kd",
+        Entity::DiffuseColorXYZ {
+            x: 0.1,
+            y: 0.2,
+            z: 0.3,
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_specular_color_spectral() {
+    assert_eq!(
+        "Ks",
+        Entity::SpecularColorSpectral {
+            file: "token.rfl".to_owned(),
+            factor: Some(0.5),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_token_specular_color_xyz() {
+    assert_eq!(
+        "Ks",
+        Entity::SpecularColorXYZ {
+            x: 0.1,
+            y: 0.2,
+            z: 0.3,
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_illum_model_mode() {
+    for mode in -1..=11 {
+        assert_eq!(mode, IllumModel::from(mode).mode());
+    }
+    assert_eq!(IllumModel::GlassNoRayTrace, IllumModel::from(9));
+    assert_eq!("11", IllumModel::Unknown(11).to_string());
+}
+
+#[test]
+fn test_to_string_illum_unknown() {
+    let entity = Entity::Illum {
+        mode: IllumModel::Unknown(12),
+    };
+    assert_eq!("illum 12", entity.to_string());
+}
+
+#[test]
+fn test_kind() {
+    for (input, kind) in [
+        ("# comment", EntityKind::Comment),
+        ("newmtl red", EntityKind::MaterialName),
+        ("Kd 1 0 0", EntityKind::DiffuseColor),
+        ("Kd spectral red.rfl", EntityKind::DiffuseColorSpectral),
+        ("map_Kd red.png", EntityKind::TextureMapDiffuse),
+        ("refl -type sphere sky.png", EntityKind::ReflectionMap),
+        ("refl sky.png", EntityKind::SphericalReflectionMap),
+    ] {
+        assert_eq!(kind, input.parse::<Entity>().unwrap().kind());
+    }
+}
+
+#[test]
+fn test_texture_map_file() {
+    for (input, expected) in [
+        ("map_Kd -s 2 2 1 diffuse.png", Some("diffuse.png")),
+        ("bump bump.png", Some("bump.png")),
+        ("disp disp.png", Some("disp.png")),
+        ("decal decal.png", Some("decal.png")),
+        ("refl sky.png", Some("sky.png")),
+        ("refl -type cube_top top.png", Some("top.png")),
+        ("Kd 1 1 1", None),
+        ("newmtl bronze", None),
+    ] {
+        assert_eq!(
+            expected,
+            input.parse::<Entity>().unwrap().texture_map_file()
+        );
+    }
+}
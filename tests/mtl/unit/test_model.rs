@@ -0,0 +1,67 @@
+extern crate wavefront_rs;
+use super::common::parse;
+use wavefront_rs::mtl::entity::*;
+use wavefront_rs::mtl::model::*;
+
+#[test]
+fn test_from_entities() {
+    let model = MtlModel::from_entities(&parse(&[
+        "# library",
+        "newmtl bronze",
+        "Ka 0.1 0.2 0.3",
+        "Kd 0.4 0.5 0.6",
+        "Ks 0.7 0.8 0.9",
+        "Ke 0 0 0",
+        "Ns 25.6",
+        "Ni 1.5",
+        "sharpness 60",
+        "d 0.9",
+        "illum 2",
+        "map_Kd bronze.png",
+        "bump -bm 0.5 bronze_bump.png",
+        "newmtl glass",
+        "Tr 0.75",
+        "Kd 0.1 0.1 0.1",
+        "Kd 0.2 0.2 0.2",
+    ]));
+    assert_eq!(
+        vec!["bronze", "glass"],
+        model.materials().map(|(x, _)| x).collect::<Vec<_>>()
+    );
+
+    let bronze = model.material("bronze").unwrap();
+    assert_eq!("bronze", bronze.name());
+    assert_eq!(11, bronze.entities().len());
+    assert_eq!(Some([0.1, 0.2, 0.3]), bronze.ambient_color());
+    assert_eq!(Some([0.4, 0.5, 0.6]), bronze.diffuse_color());
+    assert_eq!(Some([0.7, 0.8, 0.9]), bronze.specular_color());
+    assert_eq!(Some([0.0, 0.0, 0.0]), bronze.emissive_color());
+    assert_eq!(Some(25.6), bronze.specular_highlights());
+    assert_eq!(Some(1.5), bronze.optical_density());
+    assert_eq!(Some(60.0), bronze.sharpness());
+    assert_eq!(Some(0.9), bronze.dissolve());
+    assert_eq!(Some(IllumModel::HighlightOn), bronze.illum());
+    assert_eq!(Some("bronze.png"), bronze.texture_map("map_Kd"));
+    assert_eq!(Some("bronze_bump.png"), bronze.texture_map("BUMP"));
+    assert_eq!(None, bronze.texture_map("map_Ks"));
+    assert_eq!(None, bronze.texture_map("Kd"));
+    assert_eq!(
+        Some(&Entity::Illum {
+            mode: IllumModel::HighlightOn,
+        }),
+        bronze.get("illum")
+    );
+
+    let glass = model.material("glass").unwrap();
+    assert_eq!(Some(0.25), glass.dissolve());
+    assert_eq!(Some([0.2, 0.2, 0.2]), glass.diffuse_color());
+    assert_eq!(None, glass.ambient_color());
+    assert_eq!(None, glass.illum());
+    assert!(model.material("steel").is_none());
+}
+
+#[test]
+fn test_from_entities_empty() {
+    let model = MtlModel::from_entities(&parse(&["# no materials"]));
+    assert_eq!(0, model.materials().count());
+}
@@ -0,0 +1,768 @@
+extern crate wavefront_rs;
+use std::io::BufReader;
+use wavefront_rs::error::ReaderErrorKind;
+use wavefront_rs::mtl::entity::*;
+use wavefront_rs::mtl::read_lexer::*;
+
+#[test]
+fn test_read_line_comment() {
+    let stream = std::io::Cursor::new("# token");
+    assert_eq!(
+        Entity::Comment {
+            content: "token".to_owned(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_material_name() {
+    let stream = std::io::Cursor::new("newmtl token");
+    assert_eq!(
+        Entity::MaterialName {
+            name: "token".to_owned(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_ambient_color() {
+    let stream = std::io::Cursor::new("Ka 0.1 0.2 0.3");
+    assert_eq!(
+        Entity::AmbientColor {
+            r: 0.1,
+            g: 0.2,
+            b: 0.3,
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_diffuse_color() {
+    let stream = std::io::Cursor::new("Kd 0.1 0.2 0.3");
+    assert_eq!(
+        Entity::DiffuseColor {
+            r: 0.1,
+            g: 0.2,
+            b: 0.3,
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_specular_color() {
+    let stream = std::io::Cursor::new("Ks 0.1 0.2 0.3");
+    assert_eq!(
+        Entity::SpecularColor {
+            r: 0.1,
+            g: 0.2,
+            b: 0.3,
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_specular_highlights() {
+    let stream = std::io::Cursor::new("Ns 10.5");
+    assert_eq!(
+        Entity::SpecularHighlights { value: 10.5 },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_specular_highlights_integer() {
+    let stream = std::io::Cursor::new("Ns 96");
+    assert_eq!(
+        Entity::SpecularHighlights { value: 96.0 },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_optical_density() {
+    let stream = std::io::Cursor::new("Ni 1.5");
+    assert_eq!(
+        Entity::OpticalDensity { value: 1.5 },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_sharpness() {
+    for (input, value) in [("sharpness 60", 60.0), ("sharpness 12.5", 12.5)] {
+        assert_eq!(
+            Entity::Sharpness { value },
+            ReadLexer::read_line(&mut BufReader::new(input.as_bytes())).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_read_line_sharpness_invalid() {
+    let stream = std::io::Cursor::new("sharpness high");
+    let error = ReadLexer::read_line(&mut BufReader::new(stream)).unwrap_err();
+    assert_eq!(ReaderErrorKind::InvalidNumber, error.kind());
+}
+
+#[test]
+fn test_read_line_dissolve() {
+    let stream = std::io::Cursor::new("d 0.9");
+    assert_eq!(
+        Entity::Dissolve { value: 0.9 },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_inverted_dissolve() {
+    let stream = std::io::Cursor::new("Tr 0.1");
+    assert_eq!(
+        Entity::InvertedDissolve { value: 0.1 },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_illum() {
+    let stream = std::io::Cursor::new("illum 2");
+    assert_eq!(
+        Entity::Illum {
+            mode: IllumModel::HighlightOn,
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_texture_map_ambient() {
+    let stream = std::io::Cursor::new("map_Ka token.png");
+    assert_eq!(
+        Entity::TextureMapAmbient {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_texture_map_diffuse() {
+    let stream = std::io::Cursor::new("map_Kd token.png");
+    assert_eq!(
+        Entity::TextureMapDiffuse {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_texture_map_specular() {
+    let stream = std::io::Cursor::new("map_Ks token.png");
+    assert_eq!(
+        Entity::TextureMapSpecular {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_texture_map_specular_highlights() {
+    let stream = std::io::Cursor::new("map_Ns token.png");
+    assert_eq!(
+        Entity::TextureMapSpecularHighlights {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_texture_map_alpha() {
+    let stream = std::io::Cursor::new("map_d token.png");
+    assert_eq!(
+        Entity::TextureMapAlpha {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_bump_map() {
+    let stream = std::io::Cursor::new("bump token.png");
+    assert_eq!(
+        Entity::BumpMap {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_displacement_map() {
+    let stream = std::io::Cursor::new("disp token.png");
+    assert_eq!(
+        Entity::DisplacementMap {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_stencil_decal_map() {
+    let stream = std::io::Cursor::new("decal token.png");
+    assert_eq!(
+        Entity::StencilDecalMap {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_spherical_reflection_map() {
+    let stream = std::io::Cursor::new("refl token.png");
+    assert_eq!(
+        Entity::SphericalReflectionMap {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_reflection_map() {
+    let stream = std::io::Cursor::new("refl -type cube_top -clamp on -mm 0 1 top.png");
+    assert_eq!(
+        Entity::ReflectionMap {
+            kind: ReflectionType::CubeTop,
+            file: "top.png".to_owned(),
+            options: TextureMapOptions {
+                clamp: Some(true),
+                other: vec!["-mm".to_owned(), "0".to_owned(), "1".to_owned()],
+                order: vec!["-clamp".to_owned(), "-mm".to_owned()],
+                ..Default::default()
+            },
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+    let stream = std::io::Cursor::new("refl -type SPHERE sphere.png");
+    assert_eq!(
+        Entity::ReflectionMap {
+            kind: ReflectionType::Sphere,
+            file: "sphere.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_reflection_map_invalid_type() {
+    for (line, kind) in [
+        (
+            "refl -type cube_inside inside.png",
+            ReaderErrorKind::InvalidNumber,
+        ),
+        ("refl -type token.png", ReaderErrorKind::MissingField),
+        (
+            "refl -type -clamp on token.png",
+            ReaderErrorKind::MissingField,
+        ),
+    ] {
+        let stream = std::io::Cursor::new(line);
+        assert_eq!(
+            kind,
+            ReadLexer::read_line(&mut BufReader::new(stream))
+                .unwrap_err()
+                .kind()
+        );
+    }
+}
+
+#[test]
+fn test_read_line_transmission_filter_color_rgb() {
+    let stream = std::io::Cursor::new("Tf 0.1 0.2 0.3");
+    assert_eq!(
+        Entity::TransmissionFilterColorRGB {
+            r: 0.1,
+            g: 0.2,
+            b: 0.3,
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_whitespace_and_trailing_comment() {
+    let stream = std::io::Cursor::new("   Kd   0.1  0.2 0.3   # diffuse");
+    assert_eq!(
+        Entity::DiffuseColor {
+            r: 0.1,
+            g: 0.2,
+            b: 0.3,
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_unknown_token() {
+    let stream = std::io::Cursor::new("xyz 1");
+    assert_eq!(
+        ReaderErrorKind::UnknownToken,
+        ReadLexer::read_line(&mut BufReader::new(stream))
+            .unwrap_err()
+            .kind()
+    );
+}
+
+#[test]
+fn test_read_line_invalid_data() {
+    let stream = std::io::Cursor::new("Ka 0.1 x 0.3");
+    assert_eq!(
+        ReaderErrorKind::InvalidNumber,
+        ReadLexer::read_line(&mut BufReader::new(stream))
+            .unwrap_err()
+            .kind()
+    );
+}
+
+#[test]
+fn test_read_line_missing_field() {
+    let stream = std::io::Cursor::new("Ka 0.1 0.2");
+    assert_eq!(
+        ReaderErrorKind::MissingField,
+        ReadLexer::read_line(&mut BufReader::new(stream))
+            .unwrap_err()
+            .kind()
+    );
+}
+
+#[test]
+fn test_read_to_end_multiple() {
+    let stream = std::io::Cursor::new("newmtl token\n\nNs 10\nillum 2\n");
+    let entities = std::cell::RefCell::new(Vec::new());
+    ReadLexer::read_to_end(&mut BufReader::new(stream), |x| {
+        entities.borrow_mut().push(x);
+    })
+    .unwrap();
+    assert_eq!(
+        vec![
+            Entity::MaterialName {
+                name: "token".to_owned()
+            },
+            Entity::SpecularHighlights { value: 10.0 },
+            Entity::Illum {
+                mode: IllumModel::HighlightOn,
+            },
+        ],
+        entities.take()
+    );
+}
+
+#[test]
+fn test_read_line_emissive_color() {
+    let stream = std::io::Cursor::new("Ke 0.1 0.2 0.3");
+    assert_eq!(
+        Entity::EmissiveColor {
+            r: 0.1,
+            g: 0.2,
+            b: 0.3,
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_roughness() {
+    let stream = std::io::Cursor::new("Pr 0.5");
+    assert_eq!(
+        Entity::Roughness { value: 0.5 },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_metallic() {
+    let stream = std::io::Cursor::new("Pm 0.5");
+    assert_eq!(
+        Entity::Metallic { value: 0.5 },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_sheen() {
+    let stream = std::io::Cursor::new("Ps 0.5");
+    assert_eq!(
+        Entity::Sheen { value: 0.5 },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_clearcoat_thickness() {
+    let stream = std::io::Cursor::new("Pc 0.5");
+    assert_eq!(
+        Entity::ClearcoatThickness { value: 0.5 },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_clearcoat_roughness() {
+    let stream = std::io::Cursor::new("Pcr 0.5");
+    assert_eq!(
+        Entity::ClearcoatRoughness { value: 0.5 },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_texture_map_roughness() {
+    let stream = std::io::Cursor::new("map_Pr roughness.png");
+    assert_eq!(
+        Entity::TextureMapRoughness {
+            file: "roughness.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_texture_map_metallic() {
+    let stream = std::io::Cursor::new("map_Pm metallic.png");
+    assert_eq!(
+        Entity::TextureMapMetallic {
+            file: "metallic.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_texture_map_sheen() {
+    let stream = std::io::Cursor::new("map_Ps sheen.png");
+    assert_eq!(
+        Entity::TextureMapSheen {
+            file: "sheen.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_texture_map_emissive() {
+    let stream = std::io::Cursor::new("map_Ke emissive.png");
+    assert_eq!(
+        Entity::TextureMapEmissive {
+            file: "emissive.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_normal_map() {
+    let stream = std::io::Cursor::new("norm normal.png");
+    assert_eq!(
+        Entity::NormalMap {
+            file: "normal.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_texture_map_options() {
+    let stream = std::io::Cursor::new(
+        "map_Kd -blendu on -blendv off -clamp on -o 1 -0.5 0 -s 2 2 -t 0.1 0.2 0.3 token.png",
+    );
+    assert_eq!(
+        Entity::TextureMapDiffuse {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions {
+                blend_u: Some(true),
+                blend_v: Some(false),
+                clamp: Some(true),
+                offset: Some(vec![1.0, -0.5, 0.0]),
+                scale: Some(vec![2.0, 2.0]),
+                turbulence: Some(vec![0.1, 0.2, 0.3]),
+                order: ["-blendu", "-blendv", "-clamp", "-o", "-s", "-t"]
+                    .iter()
+                    .map(|x| (*x).to_owned())
+                    .collect(),
+                ..Default::default()
+            },
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_texture_map_bump_multiplier() {
+    let stream = std::io::Cursor::new("bump -bm 0.5 token.png");
+    assert_eq!(
+        Entity::BumpMap {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions {
+                bump_multiplier: Some(0.5),
+                order: vec!["-bm".to_owned()],
+                ..Default::default()
+            },
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_texture_map_other_options() {
+    let stream = std::io::Cursor::new("map_Kd -mm 0 1 -cc on token.png");
+    assert_eq!(
+        Entity::TextureMapDiffuse {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions {
+                other: vec![
+                    "-mm".to_owned(),
+                    "0".to_owned(),
+                    "1".to_owned(),
+                    "-cc".to_owned(),
+                    "on".to_owned(),
+                ],
+                order: vec!["-mm".to_owned(), "-cc".to_owned()],
+                ..Default::default()
+            },
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_texture_map_file_with_spaces() {
+    let stream = std::io::Cursor::new("map_Kd my texture.png");
+    assert_eq!(
+        Entity::TextureMapDiffuse {
+            file: "my texture.png".to_owned(),
+            options: TextureMapOptions::default(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_texture_map_options_and_file_with_spaces() {
+    let stream = std::io::Cursor::new("bump -bm 0.5 -o 1 2 -cc on my  bump map.png # comment");
+    assert_eq!(
+        Entity::BumpMap {
+            file: "my  bump map.png".to_owned(),
+            options: TextureMapOptions {
+                bump_multiplier: Some(0.5),
+                offset: Some(vec![1.0, 2.0]),
+                other: vec!["-cc".to_owned(), "on".to_owned()],
+                order: vec!["-bm".to_owned(), "-o".to_owned(), "-cc".to_owned()],
+                ..Default::default()
+            },
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_texture_map_invalid_option() {
+    let stream = std::io::Cursor::new("map_Kd -blendu maybe token.png");
+    let error = ReadLexer::read_line(&mut BufReader::new(stream)).unwrap_err();
+    assert_eq!(ReaderErrorKind::InvalidNumber, error.kind());
+}
+
+#[test]
+fn test_read_line_texture_map_missing_option_value() {
+    let stream = std::io::Cursor::new("map_Kd -s token.png");
+    let error = ReadLexer::read_line(&mut BufReader::new(stream)).unwrap_err();
+    assert_eq!(ReaderErrorKind::MissingField, error.kind());
+}
+
+#[test]
+fn test_read_line_transmission_filter_spectral() {
+    let stream = std::io::Cursor::new("Tf spectral token.rfl 0.5");
+    assert_eq!(
+        Entity::TransmissionFilterSpectral {
+            file: "token.rfl".to_owned(),
+            factor: Some(0.5),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_transmission_filter_spectral_without_factor() {
+    let stream = std::io::Cursor::new("Tf spectral token.rfl");
+    assert_eq!(
+        Entity::TransmissionFilterSpectral {
+            file: "token.rfl".to_owned(),
+            factor: None,
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_transmission_filter_xyz() {
+    let stream = std::io::Cursor::new("Tf xyz 0.1 0.2 0.3");
+    assert_eq!(
+        Entity::TransmissionFilterXYZ {
+            x: 0.1,
+            y: 0.2,
+            z: 0.3,
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_ambient_color_spectral() {
+    let stream = std::io::Cursor::new("Ka spectral token.rfl 0.5");
+    assert_eq!(
+        Entity::AmbientColorSpectral {
+            file: "token.rfl".to_owned(),
+            factor: Some(0.5),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_ambient_color_xyz() {
+    let stream = std::io::Cursor::new("Ka xyz 0.1 0.2 0.3");
+    assert_eq!(
+        Entity::AmbientColorXYZ {
+            x: 0.1,
+            y: 0.2,
+            z: 0.3,
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_diffuse_color_spectral() {
+    let stream = std::io::Cursor::new("Kd spectral token.rfl 0.5");
+    assert_eq!(
+        Entity::DiffuseColorSpectral {
+            file: "token.rfl".to_owned(),
+            factor: Some(0.5),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_diffuse_color_xyz() {
+    let stream = std::io::Cursor::new("Kd xyz 0.1 0.2 0.3");
+    assert_eq!(
+        Entity::DiffuseColorXYZ {
+            x: 0.1,
+            y: 0.2,
+            z: 0.3,
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_specular_color_spectral() {
+    let stream = std::io::Cursor::new("Ks spectral token.rfl 0.5");
+    assert_eq!(
+        Entity::SpecularColorSpectral {
+            file: "token.rfl".to_owned(),
+            factor: Some(0.5),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_specular_color_xyz() {
+    let stream = std::io::Cursor::new("Ks xyz 0.1 0.2 0.3");
+    assert_eq!(
+        Entity::SpecularColorXYZ {
+            x: 0.1,
+            y: 0.2,
+            z: 0.3,
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_texture_map_imfchan_texres() {
+    let stream = std::io::Cursor::new("bump -imfchan l -texres 512 -clamp off token.png");
+    assert_eq!(
+        Entity::BumpMap {
+            file: "token.png".to_owned(),
+            options: TextureMapOptions {
+                clamp: Some(false),
+                imfchan: Some('l'),
+                texres: Some(512),
+                order: vec![
+                    "-imfchan".to_owned(),
+                    "-texres".to_owned(),
+                    "-clamp".to_owned()
+                ],
+                ..Default::default()
+            },
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_texture_map_invalid_imfchan() {
+    let stream = std::io::Cursor::new("bump -imfchan x token.png");
+    let error = ReadLexer::read_line(&mut BufReader::new(stream)).unwrap_err();
+    assert_eq!(ReaderErrorKind::InvalidNumber, error.kind());
+}
+
+#[test]
+fn test_read_line_texture_map_invalid_texres() {
+    let stream = std::io::Cursor::new("bump -texres -1 token.png");
+    let error = ReadLexer::read_line(&mut BufReader::new(stream)).unwrap_err();
+    assert_eq!(ReaderErrorKind::InvalidNumber, error.kind());
+}
+
+#[test]
+fn test_read_line_illum_models() {
+    for (input, mode) in &[
+        ("illum 0", IllumModel::ColorNoAmbient),
+        ("illum 3", IllumModel::ReflectionRayTrace),
+        ("illum 10", IllumModel::ShadowsOnInvisible),
+        ("illum 11", IllumModel::Unknown(11)),
+        ("illum -1", IllumModel::Unknown(-1)),
+    ] {
+        assert_eq!(
+            Entity::Illum { mode: *mode },
+            ReadLexer::read_line(&mut BufReader::new(input.as_bytes())).unwrap()
+        );
+    }
+}
@@ -0,0 +1,24 @@
+extern crate wavefront_rs;
+use std::io::BufReader;
+use wavefront_rs::mtl::entity::*;
+use wavefront_rs::mtl::reader::*;
+
+#[test]
+fn test_read_all() {
+    let stream = std::io::Cursor::new("newmtl bronze\n\nNs 10\n");
+    assert_eq!(
+        vec![
+            Entity::MaterialName {
+                name: "bronze".to_owned()
+            },
+            Entity::SpecularHighlights { value: 10.0 },
+        ],
+        read_all(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_all_error_line() {
+    let stream = std::io::Cursor::new("newmtl bronze\n\nNs x\n");
+    assert_eq!(3, read_all(&mut BufReader::new(stream)).unwrap_err().line());
+}
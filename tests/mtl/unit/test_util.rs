@@ -0,0 +1,79 @@
+extern crate wavefront_rs;
+use super::common::parse;
+use wavefront_rs::mtl::util::*;
+
+#[test]
+fn test_normalize_transparency_dissolve() {
+    let mut entities = parse(&["newmtl glass", "Tr 0.25", "newmtl stone", "Kd 1 1 1"]);
+    normalize_transparency(&mut entities, Transparency::Dissolve);
+    assert_eq!(
+        parse(&["newmtl glass", "d 0.75", "newmtl stone", "Kd 1 1 1"]),
+        entities
+    );
+}
+
+#[test]
+fn test_normalize_transparency_inverted_dissolve() {
+    let mut entities = parse(&["newmtl glass", "d 0.75", "newmtl water", "Tr 0.5"]);
+    normalize_transparency(&mut entities, Transparency::InvertedDissolve);
+    assert_eq!(
+        parse(&["newmtl glass", "Tr 0.25", "newmtl water", "Tr 0.5"]),
+        entities
+    );
+}
+
+#[test]
+fn test_normalize_transparency_prefers_kept_representation() {
+    let mut entities = parse(&["newmtl glass", "Tr 0.5", "d 0.75", "Ns 10"]);
+    normalize_transparency(&mut entities, Transparency::Dissolve);
+    assert_eq!(parse(&["newmtl glass", "d 0.75", "Ns 10"]), entities);
+    let mut entities = parse(&["newmtl glass", "Tr 0.5", "d 0.75", "Ns 10"]);
+    normalize_transparency(&mut entities, Transparency::InvertedDissolve);
+    assert_eq!(parse(&["newmtl glass", "Tr 0.5", "Ns 10"]), entities);
+}
+
+#[test]
+fn test_normalize_transparency_mixed() {
+    let mut entities = parse(&[
+        "newmtl glass",
+        "d 0.5",
+        "Ka 1 1 1",
+        "Tr 0.5",
+        "newmtl water",
+        "d 0.25",
+        "Tr 0.5",
+        "Ns 10",
+        "d 0.75",
+    ]);
+    normalize_transparency(&mut entities, Transparency::Dissolve);
+    assert_eq!(
+        parse(&[
+            "newmtl glass",
+            "d 0.5",
+            "Ka 1 1 1",
+            "newmtl water",
+            "d 0.75",
+            "Ns 10",
+        ]),
+        entities
+    );
+}
+
+#[test]
+fn test_normalize_transparency_per_material() {
+    let mut entities = parse(&[
+        "d 0.5", "newmtl a", "Tr 0.1", "newmtl b", "Tr 0.2", "Tr 0.3",
+    ]);
+    normalize_transparency(&mut entities, Transparency::default());
+    assert_eq!(
+        parse(&["d 0.5", "newmtl a", "d 0.9", "newmtl b", "d 0.7"]),
+        entities
+    );
+}
+
+#[test]
+fn test_normalize_transparency_empty() {
+    let mut entities = Vec::new();
+    normalize_transparency(&mut entities, Transparency::Dissolve);
+    assert!(entities.is_empty());
+}
@@ -0,0 +1,128 @@
+extern crate wavefront_rs;
+use super::common::parse;
+use wavefront_rs::error::{ValidationError, ValidationErrorKind};
+use wavefront_rs::mtl::entity::*;
+use wavefront_rs::mtl::model::*;
+use wavefront_rs::mtl::validate::*;
+
+fn duplicates() -> Vec<Entity> {
+    parse(&[
+        "newmtl bronze",
+        "Kd 1 0 0",
+        "newmtl glass",
+        "newmtl bronze",
+        "Kd 0 1 0",
+        "newmtl bronze",
+    ])
+}
+
+#[test]
+fn test_validate() {
+    assert_eq!(
+        vec![
+            ValidationError::new(ValidationErrorKind::DuplicateMaterial, 3, 0).with_name("bronze"),
+            ValidationError::new(ValidationErrorKind::DuplicateMaterial, 5, 0).with_name("bronze"),
+        ],
+        validate(&duplicates())
+    );
+}
+
+#[test]
+fn test_validate_valid() {
+    assert!(validate(&parse(&["newmtl bronze", "newmtl glass"])).is_empty());
+}
+
+#[test]
+fn test_validation_error_display() {
+    let error = validate(&duplicates()).remove(0);
+    assert_eq!(Some("bronze"), error.name());
+    assert_eq!(
+        "entity 3: material \"bronze\" is already declared at entity 0",
+        error.to_string()
+    );
+}
+
+#[test]
+fn test_from_entities_strict() {
+    assert_eq!(
+        2,
+        MtlModel::from_entities_strict(&duplicates())
+            .unwrap_err()
+            .len()
+    );
+    let model = MtlModel::from_entities_strict(&parse(&["newmtl bronze", "Kd 1 0 0"])).unwrap();
+    assert_eq!(1, model.materials().count());
+}
+
+#[test]
+fn test_from_entities_strict_unknown_illum() {
+    let model = MtlModel::from_entities_strict(&parse(&["newmtl bronze", "illum 11"])).unwrap();
+    assert_eq!(1, model.materials().count());
+    let (_, warnings) = MtlModel::from_entities_lenient(&parse(&["newmtl bronze", "illum 11"]));
+    assert!(warnings.is_empty());
+}
+
+#[test]
+fn test_from_entities_lenient() {
+    let (model, warnings) = MtlModel::from_entities_lenient(&duplicates());
+    assert_eq!(2, warnings.len());
+    assert_eq!(
+        vec!["glass", "bronze"],
+        model.materials().map(|(x, _)| x).collect::<Vec<_>>()
+    );
+    assert!(model.material("bronze").unwrap().entities().is_empty());
+}
+
+#[test]
+fn test_validate_illum() {
+    let entities = parse(&["newmtl bronze", "illum 2", "illum 11"]);
+    assert!(validate(&entities).is_empty());
+    let errors = validate_illum(&entities);
+    assert_eq!(
+        vec![ValidationError::new(ValidationErrorKind::IllumModel, 2, 11)],
+        errors
+    );
+    assert_eq!(
+        "entity 2: illum mode 11 is out of range",
+        errors[0].to_string()
+    );
+}
+
+#[test]
+fn test_check_map_files() {
+    let dir = std::env::temp_dir().join("wavefront_rs_check_map_files");
+    std::fs::create_dir_all(dir.join("textures")).unwrap();
+    std::fs::write(dir.join("diffuse.png"), b"").unwrap();
+    std::fs::write(dir.join("textures").join("my bump.png"), b"").unwrap();
+    let entities = parse(&[
+        "newmtl bronze",
+        "map_Kd -o 0.5 diffuse.png",
+        "bump -bm 0.5 textures/my bump.png",
+        "disp missing.png",
+        "Kd 1 1 1",
+        "refl -type sphere textures",
+    ]);
+    let missing = check_map_files(&entities, &dir);
+    std::fs::remove_dir_all(&dir).unwrap();
+    assert_eq!(
+        vec![
+            MissingFile {
+                entity: 3,
+                file: "missing.png".to_owned(),
+                path: dir.join("missing.png"),
+            },
+            MissingFile {
+                entity: 5,
+                file: "textures".to_owned(),
+                path: dir.join("textures"),
+            },
+        ],
+        missing
+    );
+}
+
+#[test]
+fn test_check_map_files_without_maps() {
+    let entities = parse(&["newmtl bronze", "Kd 1 1 1", "Kd spectral bronze.rfl"]);
+    assert!(check_map_files(&entities, std::path::Path::new("does_not_exist")).is_empty());
+}
@@ -0,0 +1,711 @@
+extern crate wavefront_rs;
+use wavefront_rs::mtl::entity::*;
+use wavefront_rs::mtl::writer::*;
+
+#[test]
+fn test_write_comment() {
+    let entity = Entity::Comment {
+        content: "token".to_owned(),
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("# token", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_material_name() {
+    let entity = Entity::MaterialName {
+        name: "token".to_owned(),
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("newmtl token", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_ambient_color() {
+    let entity = Entity::AmbientColor {
+        r: 0.1,
+        g: 0.2,
+        b: 0.3,
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("Ka 0.1 0.2 0.3", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_diffuse_color() {
+    let entity = Entity::DiffuseColor {
+        r: 0.1,
+        g: 0.2,
+        b: 0.3,
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("Kd 0.1 0.2 0.3", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_specular_color() {
+    let entity = Entity::SpecularColor {
+        r: 0.1,
+        g: 0.2,
+        b: 0.3,
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("Ks 0.1 0.2 0.3", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_specular_highlights() {
+    let entity = Entity::SpecularHighlights { value: 10.5 };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("Ns 10.5", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_optical_density() {
+    let entity = Entity::OpticalDensity { value: 1.5 };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("Ni 1.5", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_sharpness() {
+    let entity = Entity::Sharpness { value: 60.0 };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("sharpness 60", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_dissolve() {
+    let entity = Entity::Dissolve { value: 0.9 };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("d 0.9", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_inverted_dissolve() {
+    let entity = Entity::InvertedDissolve { value: 0.1 };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("Tr 0.1", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_illum() {
+    let entity = Entity::Illum {
+        mode: IllumModel::HighlightOn,
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("illum 2", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_texture_map_ambient() {
+    let entity = Entity::TextureMapAmbient {
+        file: "token.png".to_owned(),
+        options: TextureMapOptions::default(),
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("map_Ka token.png", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_texture_map_diffuse() {
+    let entity = Entity::TextureMapDiffuse {
+        file: "token.png".to_owned(),
+        options: TextureMapOptions::default(),
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("map_Kd token.png", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_texture_map_specular() {
+    let entity = Entity::TextureMapSpecular {
+        file: "token.png".to_owned(),
+        options: TextureMapOptions::default(),
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("map_Ks token.png", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_texture_map_specular_highlights() {
+    let entity = Entity::TextureMapSpecularHighlights {
+        file: "token.png".to_owned(),
+        options: TextureMapOptions::default(),
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("map_Ns token.png", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_texture_map_alpha() {
+    let entity = Entity::TextureMapAlpha {
+        file: "token.png".to_owned(),
+        options: TextureMapOptions::default(),
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("map_d token.png", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_bump_map() {
+    let entity = Entity::BumpMap {
+        file: "token.png".to_owned(),
+        options: TextureMapOptions::default(),
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("bump token.png", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_displacement_map() {
+    let entity = Entity::DisplacementMap {
+        file: "token.png".to_owned(),
+        options: TextureMapOptions::default(),
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("disp token.png", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_stencil_decal_map() {
+    let entity = Entity::StencilDecalMap {
+        file: "token.png".to_owned(),
+        options: TextureMapOptions::default(),
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("decal token.png", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_reflection_map() {
+    let entity = Entity::ReflectionMap {
+        kind: ReflectionType::CubeBottom,
+        file: "bottom.png".to_owned(),
+        options: TextureMapOptions {
+            blend_u: Some(false),
+            ..Default::default()
+        },
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!(
+        "refl -type cube_bottom -blendu off bottom.png",
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn test_write_spherical_reflection_map() {
+    let entity = Entity::SphericalReflectionMap {
+        file: "token.png".to_owned(),
+        options: TextureMapOptions::default(),
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("refl token.png", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_transmission_filter_color_rgb() {
+    let entity = Entity::TransmissionFilterColorRGB {
+        r: 0.1,
+        g: 0.2,
+        b: 0.3,
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("Tf 0.1 0.2 0.3", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_with_precision_diffuse_color() {
+    let entity = Entity::DiffuseColor {
+        r: 0.1,
+        g: 0.25,
+        b: 1.0,
+    };
+    let mut result = Vec::new();
+    Writer::write_with_precision(&mut result, &entity, 3).unwrap();
+    assert_eq!("Kd 0.100 0.250 1.000", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_with_precision_illum() {
+    let entity = Entity::Illum {
+        mode: IllumModel::HighlightOn,
+    };
+    let mut result = Vec::new();
+    Writer::write_with_precision(&mut result, &entity, 3).unwrap();
+    assert_eq!("illum 2", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_emissive_color() {
+    let entity = Entity::EmissiveColor {
+        r: 0.1,
+        g: 0.2,
+        b: 0.3,
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("Ke 0.1 0.2 0.3", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_roughness() {
+    let entity = Entity::Roughness { value: 0.5 };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("Pr 0.5", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_metallic() {
+    let entity = Entity::Metallic { value: 0.5 };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("Pm 0.5", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_sheen() {
+    let entity = Entity::Sheen { value: 0.5 };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("Ps 0.5", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_clearcoat_thickness() {
+    let entity = Entity::ClearcoatThickness { value: 0.5 };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("Pc 0.5", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_clearcoat_roughness() {
+    let entity = Entity::ClearcoatRoughness { value: 0.5 };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("Pcr 0.5", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_texture_map_roughness() {
+    let entity = Entity::TextureMapRoughness {
+        file: "roughness.png".to_owned(),
+        options: TextureMapOptions::default(),
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("map_Pr roughness.png", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_texture_map_metallic() {
+    let entity = Entity::TextureMapMetallic {
+        file: "metallic.png".to_owned(),
+        options: TextureMapOptions::default(),
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("map_Pm metallic.png", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_texture_map_sheen() {
+    let entity = Entity::TextureMapSheen {
+        file: "sheen.png".to_owned(),
+        options: TextureMapOptions::default(),
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("map_Ps sheen.png", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_texture_map_emissive() {
+    let entity = Entity::TextureMapEmissive {
+        file: "emissive.png".to_owned(),
+        options: TextureMapOptions::default(),
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("map_Ke emissive.png", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_normal_map() {
+    let entity = Entity::NormalMap {
+        file: "normal.png".to_owned(),
+        options: TextureMapOptions::default(),
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("norm normal.png", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_texture_map_options() {
+    let entity = Entity::TextureMapDiffuse {
+        file: "token.png".to_owned(),
+        options: TextureMapOptions {
+            blend_u: Some(true),
+            blend_v: Some(false),
+            bump_multiplier: Some(0.5),
+            clamp: Some(true),
+            imfchan: Some('r'),
+            texres: Some(256),
+            offset: Some(vec![1.0, -0.5, 0.0]),
+            scale: Some(vec![2.0, 2.0, 1.0]),
+            turbulence: Some(vec![0.1, 0.2, 0.3]),
+            other: vec!["-cc".to_owned(), "on".to_owned()],
+            order: Vec::new(),
+        },
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!(
+        "map_Kd -blendu on -blendv off -bm 0.5 -clamp on -imfchan r -texres 256 -o 1 -0.5 0 -s 2 2 1 -t 0.1 0.2 0.3 -cc on token.png",
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn test_write_texture_map_options_in_read_order() {
+    for line in [
+        "map_Kd -s 2 2 2 -o 1 0 0 texture.png",
+        "map_Kd -s 2 2 texture.png",
+        "map_Kd -mm 0 1 -o 1 -cc on -bm 0.5 texture.png",
+        "bump -bm 0.5 -o 1 -0.5 -bm 2 texture.png",
+        "refl -type cube_top -s 2 2 -blendu off top.png",
+    ] {
+        let entity = line.parse::<Entity>().unwrap();
+        let mut result = Vec::new();
+        Writer::write(&mut result, &entity).unwrap();
+        let expected = line.replace("-bm 0.5 -o 1 -0.5 -bm 2", "-bm 2 -o 1 -0.5");
+        assert_eq!(expected, String::from_utf8(result).unwrap());
+    }
+}
+
+#[test]
+fn test_write_texture_map_options_not_in_order() {
+    let entity = Entity::TextureMapDiffuse {
+        file: "token.png".to_owned(),
+        options: TextureMapOptions {
+            scale: Some(vec![2.0]),
+            clamp: Some(true),
+            other: vec![
+                "-cc".to_owned(),
+                "on".to_owned(),
+                "-mm".to_owned(),
+                "0".to_owned(),
+            ],
+            order: vec!["-mm".to_owned(), "-s".to_owned()],
+            ..Default::default()
+        },
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!(
+        "map_Kd -mm 0 -s 2 -clamp on -cc on token.png",
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn test_write_transmission_filter_spectral() {
+    let entity = Entity::TransmissionFilterSpectral {
+        file: "token.rfl".to_owned(),
+        factor: Some(0.5),
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!(
+        "Tf spectral token.rfl 0.5",
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn test_write_transmission_filter_spectral_without_factor() {
+    let entity = Entity::TransmissionFilterSpectral {
+        file: "token.rfl".to_owned(),
+        factor: None,
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("Tf spectral token.rfl", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_transmission_filter_xyz() {
+    let entity = Entity::TransmissionFilterXYZ {
+        x: 0.1,
+        y: 0.2,
+        z: 0.3,
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("Tf xyz 0.1 0.2 0.3", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_ambient_color_spectral() {
+    let entity = Entity::AmbientColorSpectral {
+        file: "token.rfl".to_owned(),
+        factor: Some(0.5),
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!(
+        "Ka spectral token.rfl 0.5",
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn test_write_ambient_color_xyz() {
+    let entity = Entity::AmbientColorXYZ {
+        x: 0.1,
+        y: 0.2,
+        z: 0.3,
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("Ka xyz 0.1 0.2 0.3", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_diffuse_color_spectral() {
+    let entity = Entity::DiffuseColorSpectral {
+        file: "token.rfl".to_owned(),
+        factor: Some(0.5),
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!(
+        "Kd spectral token.rfl 0.5",
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn test_write_diffuse_color_xyz() {
+    let entity = Entity::DiffuseColorXYZ {
+        x: 0.1,
+        y: 0.2,
+        z: 0.3,
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("Kd xyz 0.1 0.2 0.3", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_specular_color_spectral() {
+    let entity = Entity::SpecularColorSpectral {
+        file: "token.rfl".to_owned(),
+        factor: Some(0.5),
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!(
+        "Ks spectral token.rfl 0.5",
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn test_write_specular_color_xyz() {
+    let entity = Entity::SpecularColorXYZ {
+        x: 0.1,
+        y: 0.2,
+        z: 0.3,
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("Ks xyz 0.1 0.2 0.3", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_comment_empty() {
+    let entity = Entity::Comment {
+        content: "".to_owned(),
+    };
+    let mut result = Vec::new();
+    Writer::write(&mut result, &entity).unwrap();
+    assert_eq!("#", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_with_options_clamp() {
+    let entity = Entity::DiffuseColor {
+        r: 1.5,
+        g: 0.5,
+        b: -0.25,
+    };
+    let mut result = Vec::new();
+    assert!(Writer::write_with_options(&mut result, &entity, &DisplayOptions::default()).unwrap());
+    assert_eq!("Kd 1 0.5 0", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_with_options_normalize() {
+    let entity = Entity::AmbientColor {
+        r: 255.0,
+        g: 127.5,
+        b: 0.0,
+    };
+    let options = DisplayOptions {
+        float_precision: Some(2),
+        colors: ColorRange::Normalize,
+        clamp_scalars: false,
+    };
+    let mut result = Vec::new();
+    assert!(Writer::write_with_options(&mut result, &entity, &options).unwrap());
+    assert_eq!("Ka 1.00 0.50 0.00", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_with_options_keep() {
+    let entity = Entity::TransmissionFilterColorRGB {
+        r: 255.0,
+        g: 255.0,
+        b: 255.0,
+    };
+    let options = DisplayOptions {
+        colors: ColorRange::Keep,
+        ..Default::default()
+    };
+    let mut result = Vec::new();
+    assert!(Writer::write_with_options(&mut result, &entity, &options).unwrap());
+    assert_eq!("Tf 255 255 255", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_with_options_in_range() {
+    let mut result = Vec::new();
+    let options = DisplayOptions::default();
+    let entity = Entity::SpecularColor {
+        r: 0.0,
+        g: 1.0,
+        b: 0.5,
+    };
+    assert!(!Writer::write_with_options(&mut result, &entity, &options).unwrap());
+    assert_eq!("Ks 0 1 0.5", String::from_utf8(result).unwrap());
+    let mut result = Vec::new();
+    let entity = Entity::SpecularHighlights { value: 250.0 };
+    assert!(!Writer::write_with_options(&mut result, &entity, &options).unwrap());
+    assert_eq!("Ns 250", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_with_options_clamp_scalars() {
+    let options = DisplayOptions {
+        clamp_scalars: true,
+        ..Default::default()
+    };
+    for (entity, expected, clamped) in [
+        (Entity::Dissolve { value: 1.5 }, "d 1", true),
+        (Entity::Dissolve { value: 0.5 }, "d 0.5", false),
+        (Entity::InvertedDissolve { value: -0.5 }, "Tr 0", true),
+        (
+            Entity::SpecularHighlights { value: 1200.0 },
+            "Ns 1000",
+            true,
+        ),
+        (Entity::SpecularHighlights { value: 250.0 }, "Ns 250", false),
+        (Entity::Sharpness { value: 2000.0 }, "sharpness 1000", true),
+        (Entity::Roughness { value: 2.0 }, "Pr 1", true),
+        (Entity::Metallic { value: -1.0 }, "Pm 0", true),
+        (Entity::Sheen { value: 1.1 }, "Ps 1", true),
+        (Entity::ClearcoatThickness { value: 3.0 }, "Pc 1", true),
+        (Entity::ClearcoatRoughness { value: 1.0 }, "Pcr 1", false),
+        (Entity::OpticalDensity { value: 1.5 }, "Ni 1.5", false),
+    ] {
+        let mut result = Vec::new();
+        assert_eq!(
+            clamped,
+            Writer::write_with_options(&mut result, &entity, &options).unwrap()
+        );
+        assert_eq!(expected, String::from_utf8(result).unwrap());
+    }
+}
+
+#[test]
+fn test_write_with_options_keeps_scalars_by_default() {
+    let entity = Entity::Dissolve { value: 1.5 };
+    let mut result = Vec::new();
+    assert!(!Writer::write_with_options(&mut result, &entity, &DisplayOptions::default()).unwrap());
+    assert_eq!("d 1.5", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_all_with_clamp_scalars() {
+    let entities = vec![
+        Entity::MaterialName {
+            name: "glass".to_owned(),
+        },
+        Entity::Dissolve { value: 1.5 },
+        Entity::SpecularHighlights { value: 10.0 },
+        Entity::Metallic { value: 1.25 },
+    ];
+    let options = DisplayOptions {
+        clamp_scalars: true,
+        ..Default::default()
+    };
+    let mut result = Vec::new();
+    assert_eq!(
+        vec![1, 3],
+        write_all_with(&mut result, &entities, &options).unwrap()
+    );
+    assert_eq!(
+        "newmtl glass\nd 1\nNs 10\nPm 1\n",
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn test_write_all_with() {
+    let entities = vec![
+        Entity::MaterialName {
+            name: "white".to_owned(),
+        },
+        Entity::EmissiveColor {
+            r: 2.0,
+            g: 1.0,
+            b: 1.0,
+        },
+    ];
+    let mut result = Vec::new();
+    assert_eq!(
+        vec![1],
+        write_all_with(&mut result, &entities, &DisplayOptions::default()).unwrap()
+    );
+    assert_eq!(
+        "newmtl white\nKe 1 1 1\n",
+        String::from_utf8(result).unwrap()
+    );
+}
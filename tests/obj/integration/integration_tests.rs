@@ -1,9 +1,13 @@
 use std::fs::File;
-use std::io::{BufReader, BufWriter, Read, Write};
+use std::io::{BufReader, Read, Write};
 
 extern crate wavefront_rs;
+use wavefront_rs::error::ReaderErrorKind;
+use wavefront_rs::obj::entity::Entity;
 use wavefront_rs::obj::format_writer::FormatWriter;
 use wavefront_rs::obj::read_lexer::ReadLexer;
+use wavefront_rs::obj::reader::{read_all, read_file};
+use wavefront_rs::obj::writer::{write_all, write_file};
 
 #[test]
 fn test_teapot_smoke() {
@@ -25,19 +29,17 @@ fn test_teapot_read_write() {
         .unwrap()
         .read_to_string(&mut source_content)
         .unwrap();
-    let mut dest = String::new();
-    {
-        let writer_arc = std::sync::Arc::new(std::sync::Mutex::new(BufWriter::new(unsafe {
-            dest.as_mut_vec()
-        })));
-        ReadLexer::read_to_end(&mut BufReader::new(source), |e| {
-            let mut local_writer = writer_arc.lock().unwrap();
-            FormatWriter::write(&mut *local_writer, &e);
-            local_writer.write_all(b"\n").unwrap();
-        })
-        .unwrap();
-    }
-    assert_eq!(source_content, dest);
+    let dest = std::sync::Mutex::new(Vec::new());
+    ReadLexer::read_to_end(&mut BufReader::new(source), |e| {
+        let mut local_writer = dest.lock().unwrap();
+        FormatWriter::write(&mut *local_writer, &e).unwrap();
+        local_writer.write_all(b"\n").unwrap();
+    })
+    .unwrap();
+    assert_eq!(
+        source_content,
+        String::from_utf8(dest.into_inner().unwrap()).unwrap()
+    );
 }
 
 #[test]
@@ -48,17 +50,150 @@ fn test_lamp_read_write() {
         .unwrap()
         .read_to_string(&mut source_content)
         .unwrap();
-    let mut dest = String::new();
-    {
-        let writer_arc = std::sync::Arc::new(std::sync::Mutex::new(BufWriter::new(unsafe {
-            dest.as_mut_vec()
-        })));
-        ReadLexer::read_to_end(&mut BufReader::new(source), |e| {
-            let mut local_writer = writer_arc.lock().unwrap();
-            FormatWriter::write(&mut *local_writer, &e);
-            local_writer.write_all(b"\n").unwrap();
-        })
-        .unwrap();
+    let dest = std::sync::Mutex::new(Vec::new());
+    ReadLexer::read_to_end(&mut BufReader::new(source), |e| {
+        let mut local_writer = dest.lock().unwrap();
+        FormatWriter::write(&mut *local_writer, &e).unwrap();
+        local_writer.write_all(b"\n").unwrap();
+    })
+    .unwrap();
+    assert_eq!(
+        source_content,
+        String::from_utf8(dest.into_inner().unwrap()).unwrap()
+    );
+}
+
+#[test]
+fn test_features_read_write() {
+    let source_content =
+        std::fs::read_to_string("./tests/obj/integration/resources/features.obj").unwrap();
+    let entities = read_all(&mut source_content.as_bytes()).unwrap();
+    let mut dest = Vec::new();
+    write_all(&mut dest, &entities).unwrap();
+    assert_eq!(source_content, String::from_utf8(dest).unwrap());
+}
+
+#[test]
+fn test_merging_group_read_write() {
+    let source = "  mg 1 2\nMG 1\nmg off\n";
+    let entities = read_all(&mut source.as_bytes()).unwrap();
+    let mut dest = Vec::new();
+    write_all(&mut dest, &entities).unwrap();
+    assert_eq!("mg 1 2\nmg 1\nmg off\n", String::from_utf8(dest).unwrap());
+}
+
+#[test]
+fn test_features_entity_round_trip() {
+    for path in &[
+        "./tests/obj/integration/resources/features.obj",
+        "./tests/obj/integration/resources/lamp.obj",
+        "./tests/obj/integration/resources/teapot.obj",
+    ] {
+        let file = File::open(path).unwrap();
+        for e in read_all(&mut BufReader::new(file)).unwrap() {
+            assert_eq!(e, e.to_string().parse::<Entity>().unwrap());
+        }
+    }
+}
+
+#[test]
+fn test_features_read_write_file() {
+    let source_content =
+        std::fs::read_to_string("./tests/obj/integration/resources/features.obj").unwrap();
+    let entities = read_file("./tests/obj/integration/resources/features.obj").unwrap();
+    let path = std::env::temp_dir().join("wavefront_rs_features.obj");
+    write_file(&path, &entities).unwrap();
+    let dest_content = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(source_content, dest_content);
+}
+
+#[test]
+fn test_read_file_missing() {
+    let error = read_file("./tests/obj/integration/resources/missing.obj").unwrap_err();
+    assert_eq!(ReaderErrorKind::Io, error.kind());
+}
+
+#[test]
+fn test_features_single_spaced() {
+    let file = File::open("./tests/obj/integration/resources/features.obj").unwrap();
+    for e in read_all(&mut BufReader::new(file)).unwrap() {
+        let written = e.to_string();
+        assert!(!written.contains("  "), "{:?}", written);
+        assert_eq!(written.trim(), written);
+    }
+}
+
+#[test]
+fn test_crlf_read_line() {
+    let file = File::open("./tests/obj/integration/resources/crlf.obj").unwrap();
+    let mut reader = BufReader::new(file);
+    let mut entities = Vec::new();
+    for _ in 0..7 {
+        entities.push(ReadLexer::read_line(&mut reader).unwrap());
+    }
+    assert_eq!(
+        Entity::Comment {
+            content: "written on windows".to_owned()
+        },
+        entities[0]
+    );
+    assert_eq!(
+        Entity::Mtllib {
+            name: "crlf.mtl".to_owned()
+        },
+        entities[1]
+    );
+    assert_eq!(
+        Entity::Object {
+            name: "crlf".to_owned()
+        },
+        entities[2]
+    );
+    assert_eq!(
+        Entity::Vertex {
+            x: 0.1,
+            y: 1.2,
+            z: 3.4,
+            w: None,
+            color: None,
+        },
+        entities[3]
+    );
+    assert_eq!(
+        Entity::Usemtl {
+            name: "shiny".to_owned()
+        },
+        entities[5]
+    );
+    assert_eq!(
+        entities,
+        read_file("./tests/obj/integration/resources/crlf.obj").unwrap()
+    );
+}
+
+#[test]
+fn test_bom_read_file() {
+    let entities = read_file("./tests/obj/integration/resources/bom.obj").unwrap();
+    assert_eq!(
+        Entity::Vertex {
+            x: 0.0,
+            y: 1.0,
+            z: 2.0,
+            w: None,
+            color: None,
+        },
+        entities[0]
+    );
+    assert_eq!(2, entities.len());
+}
+
+#[test]
+fn test_features_serialized_len() {
+    let file = File::open("./tests/obj/integration/resources/features.obj").unwrap();
+    for e in read_all(&mut BufReader::new(file)).unwrap() {
+        let mut written = Vec::new();
+        FormatWriter::write_line(&mut written, &e).unwrap();
+        assert_eq!(written.len(), e.serialized_len(), "{:?}", e);
     }
-    assert_eq!(source_content, dest);
 }
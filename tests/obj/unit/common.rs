@@ -0,0 +1,9 @@
+//! Contains helpers that are shared by the unit tests.
+//!
+
+use wavefront_rs::obj::entity::Entity;
+
+/// Parses each of the given lines into an entity.
+pub fn parse(lines: &[&str]) -> Vec<Entity> {
+    lines.iter().map(|x| x.parse::<Entity>().unwrap()).collect()
+}
@@ -0,0 +1,62 @@
+extern crate wavefront_rs;
+use wavefront_rs::error::ReaderErrorKind;
+use wavefront_rs::obj::async_reader::*;
+use wavefront_rs::obj::entity::*;
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+#[test]
+fn test_read_line() {
+    let mut reader = tokio::io::BufReader::new("v 0.1 1.2 2.3\nvn 1 0 0\n".as_bytes());
+    block_on(async {
+        assert_eq!(
+            Some(Entity::Vertex {
+                x: 0.1,
+                y: 1.2,
+                z: 2.3,
+                w: None,
+                color: None,
+            }),
+            read_line(&mut reader).await.unwrap()
+        );
+        assert_eq!(
+            Some(Entity::VertexNormal {
+                x: 1.0,
+                y: 0.0,
+                z: 0.0,
+            }),
+            read_line(&mut reader).await.unwrap()
+        );
+        assert_eq!(None, read_line(&mut reader).await.unwrap());
+    });
+}
+
+#[test]
+fn test_read_line_eof() {
+    let mut reader = tokio::io::BufReader::new("".as_bytes());
+    assert_eq!(None, block_on(read_line(&mut reader)).unwrap());
+}
+
+#[test]
+fn test_read_line_error() {
+    let mut reader = tokio::io::BufReader::new("v 0.1 x 2.3".as_bytes());
+    let error = block_on(read_line(&mut reader)).unwrap_err();
+    assert_eq!(ReaderErrorKind::InvalidNumber, error.kind());
+    assert_eq!("v 0.1 x 2.3", error.raw());
+}
+
+#[test]
+fn test_read_line_continuation() {
+    let mut reader = tokio::io::BufReader::new("p 1 \\\n2 \\\n3\n".as_bytes());
+    assert_eq!(
+        Some(Entity::Point {
+            vertices: vec![1, 2, 3],
+        }),
+        block_on(read_line(&mut reader)).unwrap()
+    );
+}
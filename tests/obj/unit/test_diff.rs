@@ -0,0 +1,135 @@
+extern crate wavefront_rs;
+use super::common::parse;
+use wavefront_rs::obj::diff::*;
+use wavefront_rs::obj::entity::*;
+
+#[test]
+fn test_diff_equal() {
+    let entities = parse(&["o cube", "v 0.1 0.2 0.3", "vt 0.5 1", "f 1 2 3"]);
+    assert!(diff(&entities, &entities).is_empty());
+    assert!(diff(&[], &[]).is_empty());
+}
+
+#[test]
+fn test_diff_changed() {
+    let a = parse(&["o cube", "v 0 0 0", "f 1 2 3"]);
+    let b = parse(&["o box", "v 0 0 0", "f 1 2 4"]);
+    assert_eq!(
+        vec![
+            Difference::Changed {
+                index: 0,
+                old: a[0].clone(),
+                new: b[0].clone(),
+            },
+            Difference::Changed {
+                index: 2,
+                old: a[2].clone(),
+                new: b[2].clone(),
+            },
+        ],
+        diff(&a, &b)
+    );
+}
+
+#[test]
+fn test_diff_added_removed() {
+    let a = parse(&["v 0 0 0", "v 1 0 0", "v 0 1 0"]);
+    let b = parse(&["v 0 0 0"]);
+    assert_eq!(
+        vec![
+            Difference::Removed {
+                index: 1,
+                entity: a[1].clone(),
+            },
+            Difference::Removed {
+                index: 2,
+                entity: a[2].clone(),
+            },
+        ],
+        diff(&a, &b)
+    );
+    assert_eq!(
+        vec![
+            Difference::Added {
+                index: 1,
+                entity: a[1].clone(),
+            },
+            Difference::Added {
+                index: 2,
+                entity: a[2].clone(),
+            },
+        ],
+        diff(&b, &a)
+    );
+    assert_eq!(
+        vec![1, 2],
+        diff(&b, &a).iter().map(|x| x.index()).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_diff_float_epsilon() {
+    let a = parse(&[
+        "v 0.1 0.2 0.3 1 0.5 0.5 0.5",
+        "vn 0 0 1",
+        "vt 0.333333",
+        "vp 0.5 0.5 1",
+        "curv 0 0.9999 1 2",
+        "surf 0 1 0 1 1 2 3",
+        "parm u 0 0.5 1",
+        "trim 0 1 1",
+    ]);
+    let b = parse(&[
+        "v 0.1001 0.2 0.3 1 0.5 0.5 0.5001",
+        "vn 0 0 0.9999",
+        "vt 0.3333",
+        "vp 0.5 0.5001 1",
+        "curv 0 1 1 2",
+        "surf 0 1 0.0001 1 1 2 3",
+        "parm u 0 0.5001 1",
+        "trim 0.0001 1 1",
+    ]);
+    assert_eq!(a.len(), diff(&a, &b).len());
+    assert!(diff_with_epsilon(&a, &b, 0.001).is_empty());
+    assert_eq!(a.len(), diff_with_epsilon(&a, &b, 0.00001).len());
+}
+
+#[test]
+fn test_diff_epsilon_does_not_hide_structural_changes() {
+    for (a, b) in [
+        ("v 0 0 0", "v 0 0 0 1"),
+        ("v 0 0 0", "v 0 0 0 1 0 0 0"),
+        ("vt 0.5", "vt 0.5 0"),
+        ("curv 0 1 1 2", "curv 0 1 1 3"),
+        ("parm u 0 1", "parm v 0 1"),
+        ("parm u 0 1", "parm u 0 1 1"),
+        ("trim 0 1 1", "trim 0 1 2"),
+        ("vn 0 0 1", "v 0 0 1"),
+    ] {
+        let a = parse(&[a]);
+        let b = parse(&[b]);
+        assert_eq!(1, diff_with_epsilon(&a, &b, 1.0).len());
+    }
+}
+
+#[test]
+fn test_diff_round_trip_with_precision() {
+    let a = parse(&[
+        "v 0.123456789 1.987654321 -2.5",
+        "vt 0.333333333 0.666666667",
+    ]);
+    let b: Vec<Entity> = a
+        .iter()
+        .map(|e| {
+            let mut result = Vec::new();
+            wavefront_rs::obj::format_writer::FormatWriter::write_with_precision(&mut result, e, 6)
+                .unwrap();
+            String::from_utf8(result)
+                .unwrap()
+                .parse::<Entity>()
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(2, diff(&a, &b).len());
+    assert!(diff_with_epsilon(&a, &b, 1e-6).is_empty());
+}
@@ -1,4 +1,5 @@
 extern crate wavefront_rs;
+use wavefront_rs::error::ReaderErrorKind;
 use wavefront_rs::obj::entity::*;
 
 #[test]
@@ -40,7 +41,7 @@ fn test_from_objformat_object() {
 #[test]
 fn test_into_format_group() {
     let entity = Entity::Group {
-        name: "token".to_owned(),
+        names: vec!["token".to_owned()],
     };
     assert_eq!("g token", Into::<Format>::into(entity));
 }
@@ -50,7 +51,7 @@ fn test_from_objformat_group() {
     assert_eq!(
         Entity::from(Format::from("g token")),
         Entity::Group {
-            name: "token".to_owned()
+            names: vec!["token".to_owned()]
         }
     );
 }
@@ -58,17 +59,17 @@ fn test_from_objformat_group() {
 #[test]
 fn test_into_format_smoothing_group() {
     let entity = Entity::SmoothingGroup {
-        name: "token".to_owned(),
+        group: SmoothingGroup::Group(1),
     };
-    assert_eq!("s token", Into::<Format>::into(entity));
+    assert_eq!("s 1", Into::<Format>::into(entity));
 }
 
 #[test]
 fn test_from_objformat_smoothing_group() {
     assert_eq!(
-        Entity::from(Format::from("s token")),
+        Entity::from(Format::from("s 1")),
         Entity::SmoothingGroup {
-            name: "token".to_owned()
+            group: SmoothingGroup::Group(1)
         }
     );
 }
@@ -134,6 +135,7 @@ fn test_into_format_vertex_xyzw() {
         y: 1f64,
         z: 2f64,
         w: Some(3f64),
+        color: None,
     };
     assert_eq!("v 0 1 2 3", Into::<Format>::into(entity));
 }
@@ -146,6 +148,7 @@ fn test_from_objformat_vertex_xyzw() {
             y: 1.2,
             z: 2.3,
             w: Some(3.4),
+            color: None,
         },
         Entity::from(Format::from("v 0.1 1.2 2.3 3.4"))
     );
@@ -158,6 +161,7 @@ fn test_into_format_vertex_xyz() {
         y: 1f64,
         z: 2f64,
         w: None,
+        color: None,
     };
     assert_eq!("v 0 1 2", Into::<Format>::into(entity));
 }
@@ -170,6 +174,7 @@ fn test_from_objformat_vertex_xyz() {
             y: 1.2f64,
             z: 2.3f64,
             w: None,
+            color: None,
         },
         Entity::from(Format::from("v 0.1 1.2 2.3"))
     );
@@ -470,7 +475,13 @@ fn test_from_objformat_face_v() {
 #[test]
 fn test_into_format_line() {
     let entity = Entity::Line {
-        vertices: vec![0, 1, 2, 3, 4],
+        vertices: vec![
+            LineVertex::new(0),
+            LineVertex::new(1),
+            LineVertex::new(2),
+            LineVertex::new(3),
+            LineVertex::new(4),
+        ],
     };
     assert_eq!("l 0 1 2 3 4", Into::<Format>::into(entity));
 }
@@ -479,7 +490,13 @@ fn test_into_format_line() {
 fn test_from_objformat_line() {
     assert_eq!(
         Entity::Line {
-            vertices: vec!(0, 1, 2, 3, 4)
+            vertices: vec![
+                LineVertex::new(0),
+                LineVertex::new(1),
+                LineVertex::new(2),
+                LineVertex::new(3),
+                LineVertex::new(4)
+            ]
         },
         Entity::from(Format::from("l 0 1 2 3 4"))
     );
@@ -527,13 +544,7 @@ fn test_token_object() {
 
 #[test]
 fn test_token_group() {
-    assert_eq!(
-        "g",
-        Entity::Group {
-            name: "".to_owned()
-        }
-        .token()
-    );
+    assert_eq!("g", Entity::Group { names: vec![] }.token());
 }
 
 #[test]
@@ -541,7 +552,7 @@ fn test_token_smoothing_group() {
     assert_eq!(
         "s",
         Entity::SmoothingGroup {
-            name: "".to_owned()
+            group: SmoothingGroup::Off
         }
         .token()
     );
@@ -588,7 +599,8 @@ fn test_token_vertex() {
             x: 0f64,
             y: 0f64,
             z: 0f64,
-            w: None
+            w: None,
+            color: None,
         }
         .token()
     );
@@ -640,7 +652,7 @@ fn test_token_face() {
 
 #[test]
 fn test_token_line() {
-    assert_eq!("l", Entity::Line { vertices: vec!() }.token());
+    assert_eq!("l", Entity::Line { vertices: vec![] }.token());
 }
 
 #[test]
@@ -667,7 +679,7 @@ fn test_to_string_object() {
 #[test]
 fn test_to_string_group() {
     let entity = Entity::Group {
-        name: "token".to_owned(),
+        names: vec!["token".to_owned()],
     };
     assert_eq!("g token", entity.to_string());
 }
@@ -675,9 +687,26 @@ fn test_to_string_group() {
 #[test]
 fn test_to_string_smoothing_group() {
     let entity = Entity::SmoothingGroup {
-        name: "token".to_owned(),
+        group: SmoothingGroup::Group(1),
+    };
+    assert_eq!("s 1", entity.to_string());
+}
+
+#[test]
+fn test_to_string_smoothing_group_off() {
+    let entity = Entity::SmoothingGroup {
+        group: SmoothingGroup::Off,
     };
-    assert_eq!("s token", entity.to_string());
+    assert_eq!("s off", entity.to_string());
+}
+
+#[test]
+fn test_smoothing_group_number() {
+    assert_eq!(0, SmoothingGroup::Off.number());
+    assert_eq!(3, SmoothingGroup::Group(3).number());
+    assert!(SmoothingGroup::Off.is_off());
+    assert!(SmoothingGroup::Group(0).is_off());
+    assert!(!SmoothingGroup::Group(1).is_off());
 }
 
 #[test]
@@ -711,6 +740,7 @@ fn test_to_string_vertex_xyzw() {
         y: 1f64,
         z: 2f64,
         w: Some(3f64),
+        color: None,
     };
     assert_eq!("v 0 1 2 3", entity.to_string());
 }
@@ -722,6 +752,7 @@ fn test_to_string_vertex_xyz() {
         y: 1f64,
         z: 2f64,
         w: None,
+        color: None,
     };
     assert_eq!("v 0 1 2", entity.to_string());
 }
@@ -865,7 +896,13 @@ fn test_to_string_face_v() {
 #[test]
 fn test_to_string_line() {
     let entity = Entity::Line {
-        vertices: vec![0, 1, 2, 3, 4],
+        vertices: vec![
+            LineVertex::new(0),
+            LineVertex::new(1),
+            LineVertex::new(2),
+            LineVertex::new(3),
+            LineVertex::new(4),
+        ],
     };
     assert_eq!("l 0 1 2 3 4", entity.to_string());
 }
@@ -877,3 +914,997 @@ fn test_to_string_point() {
     };
     assert_eq!("p 0 1 2 3 4", entity.to_string());
 }
+
+#[test]
+fn test_face_vertex_resolve_last() {
+    assert_eq!(
+        FaceVertex::new2(10, Some(20), Some(30)),
+        FaceVertex::new2(-1, Some(-1), Some(-1)).resolve(10, 20, 30)
+    );
+}
+
+#[test]
+fn test_face_vertex_resolve_independent() {
+    assert_eq!(
+        FaceVertex::new2(8, Some(19), Some(28)),
+        FaceVertex::new2(-3, Some(-2), Some(-3)).resolve(10, 20, 30)
+    );
+}
+
+#[test]
+fn test_face_vertex_resolve_mixed() {
+    assert_eq!(
+        FaceVertex::new2(4, Some(20), None),
+        FaceVertex::new2(4, Some(-1), None).resolve(10, 20, 30)
+    );
+}
+
+#[test]
+fn test_face_vertex_resolve_absolute() {
+    assert_eq!(
+        FaceVertex::new2(1, Some(2), Some(3)),
+        FaceVertex::new2(1, Some(2), Some(3)).resolve(10, 20, 30)
+    );
+}
+
+#[test]
+fn test_into_format_curve() {
+    let entity = Entity::Curve {
+        u_start: 0.5,
+        u_end: 1.5,
+        vertices: vec![1, -2, 3, 4],
+    };
+    assert_eq!("curv 0.5 1.5 1 -2 3 4", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_curve() {
+    assert_eq!(
+        Entity::from(Format::from("curv 0.5 1.5 1 -2 3 4")),
+        Entity::Curve {
+            u_start: 0.5,
+            u_end: 1.5,
+            vertices: vec![1, -2, 3, 4],
+        }
+    );
+}
+
+#[test]
+fn test_token_curve() {
+    assert_eq!(
+        "curv",
+        Entity::Curve {
+            u_start: 0.5,
+            u_end: 1.5,
+            vertices: vec![1, -2, 3, 4],
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_into_format_curve2d() {
+    let entity = Entity::Curve2D {
+        vertices: vec![1, 2, -3, 4],
+    };
+    assert_eq!("curv2 1 2 -3 4", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_curve2d() {
+    assert_eq!(
+        Entity::from(Format::from("curv2 1 2 -3 4")),
+        Entity::Curve2D {
+            vertices: vec![1, 2, -3, 4],
+        }
+    );
+}
+
+#[test]
+fn test_token_curve2d() {
+    assert_eq!(
+        "curv2",
+        Entity::Curve2D {
+            vertices: vec![1, 2, -3, 4],
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_into_format_surface() {
+    let entity = Entity::Surface {
+        s_start: 0.5,
+        s_end: 1.5,
+        t_start: 2.5,
+        t_end: 3.5,
+        vertices: vec![
+            FaceVertex::new2(1, Some(2), Some(3)),
+            FaceVertex::new2(4, None, Some(5)),
+            FaceVertex::new2(-6, None, None),
+        ],
+    };
+    assert_eq!(
+        "surf 0.5 1.5 2.5 3.5 1/2/3 4//5 -6",
+        Into::<Format>::into(entity)
+    );
+}
+
+#[test]
+fn test_from_objformat_surface() {
+    assert_eq!(
+        Entity::from(Format::from("surf 0.5 1.5 2.5 3.5 1/2/3 4//5 -6")),
+        Entity::Surface {
+            s_start: 0.5,
+            s_end: 1.5,
+            t_start: 2.5,
+            t_end: 3.5,
+            vertices: vec![
+                FaceVertex::new2(1, Some(2), Some(3)),
+                FaceVertex::new2(4, None, Some(5)),
+                FaceVertex::new2(-6, None, None),
+            ],
+        }
+    );
+}
+
+#[test]
+fn test_token_surface() {
+    assert_eq!(
+        "surf",
+        Entity::Surface {
+            s_start: 0.5,
+            s_end: 1.5,
+            t_start: 2.5,
+            t_end: 3.5,
+            vertices: vec![
+                FaceVertex::new2(1, Some(2), Some(3)),
+                FaceVertex::new2(4, None, Some(5)),
+                FaceVertex::new2(-6, None, None),
+            ],
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_into_format_curve_surface_type_rational() {
+    let entity = Entity::CurveSurfaceType {
+        rational: true,
+        kind: "bspline".to_owned(),
+    };
+    assert_eq!("cstype rat bspline", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_curve_surface_type_rational() {
+    assert_eq!(
+        Entity::from(Format::from("cstype rat bspline")),
+        Entity::CurveSurfaceType {
+            rational: true,
+            kind: "bspline".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn test_token_curve_surface_type_rational() {
+    assert_eq!(
+        "cstype",
+        Entity::CurveSurfaceType {
+            rational: true,
+            kind: "bspline".to_owned(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_into_format_curve_surface_type() {
+    let entity = Entity::CurveSurfaceType {
+        rational: false,
+        kind: "bezier".to_owned(),
+    };
+    assert_eq!("cstype bezier", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_curve_surface_type() {
+    assert_eq!(
+        Entity::from(Format::from("cstype bezier")),
+        Entity::CurveSurfaceType {
+            rational: false,
+            kind: "bezier".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn test_token_curve_surface_type() {
+    assert_eq!(
+        "cstype",
+        Entity::CurveSurfaceType {
+            rational: false,
+            kind: "bezier".to_owned(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_into_format_degree_u() {
+    let entity = Entity::Degree { u: 3, v: None };
+    assert_eq!("deg 3", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_degree_u() {
+    assert_eq!(
+        Entity::from(Format::from("deg 3")),
+        Entity::Degree { u: 3, v: None }
+    );
+}
+
+#[test]
+fn test_token_degree_u() {
+    assert_eq!("deg", Entity::Degree { u: 3, v: None }.token());
+}
+
+#[test]
+fn test_into_format_degree_uv() {
+    let entity = Entity::Degree { u: 3, v: Some(2) };
+    assert_eq!("deg 3 2", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_degree_uv() {
+    assert_eq!(
+        Entity::from(Format::from("deg 3 2")),
+        Entity::Degree { u: 3, v: Some(2) }
+    );
+}
+
+#[test]
+fn test_token_degree_uv() {
+    assert_eq!("deg", Entity::Degree { u: 3, v: Some(2) }.token());
+}
+
+#[test]
+fn test_into_format_parm() {
+    let entity = Entity::Parm {
+        direction: 'u',
+        values: vec![0.0, 0.5, 1.0],
+    };
+    assert_eq!("parm u 0 0.5 1", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_parm() {
+    assert_eq!(
+        Entity::from(Format::from("parm u 0 0.5 1")),
+        Entity::Parm {
+            direction: 'u',
+            values: vec![0.0, 0.5, 1.0],
+        }
+    );
+}
+
+#[test]
+fn test_token_parm() {
+    assert_eq!(
+        "parm",
+        Entity::Parm {
+            direction: 'u',
+            values: vec![0.0, 0.5, 1.0],
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_into_format_step_u() {
+    let entity = Entity::Step { u: 1, v: None };
+    assert_eq!("step 1", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_step_u() {
+    assert_eq!(
+        Entity::from(Format::from("step 1")),
+        Entity::Step { u: 1, v: None }
+    );
+}
+
+#[test]
+fn test_token_step_u() {
+    assert_eq!("step", Entity::Step { u: 1, v: None }.token());
+}
+
+#[test]
+fn test_into_format_step_uv() {
+    let entity = Entity::Step { u: 2, v: Some(3) };
+    assert_eq!("step 2 3", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_step_uv() {
+    assert_eq!(
+        Entity::from(Format::from("step 2 3")),
+        Entity::Step { u: 2, v: Some(3) }
+    );
+}
+
+#[test]
+fn test_token_step_uv() {
+    assert_eq!("step", Entity::Step { u: 2, v: Some(3) }.token());
+}
+
+#[test]
+fn test_into_format_trim() {
+    let entity = Entity::Trim {
+        curves: vec![
+            CurveReference::new(0.0, 1.0, 1),
+            CurveReference::new(0.5, 2.0, -1),
+        ],
+    };
+    assert_eq!("trim 0 1 1 0.5 2 -1", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_trim() {
+    assert_eq!(
+        Entity::from(Format::from("trim 0 1 1 0.5 2 -1")),
+        Entity::Trim {
+            curves: vec![
+                CurveReference::new(0.0, 1.0, 1),
+                CurveReference::new(0.5, 2.0, -1),
+            ],
+        }
+    );
+}
+
+#[test]
+fn test_into_format_hole() {
+    let entity = Entity::Hole {
+        curves: vec![CurveReference::new(0.25, 0.75, 2)],
+    };
+    assert_eq!("hole 0.25 0.75 2", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_hole() {
+    assert_eq!(
+        Entity::from(Format::from("hole 0.25 0.75 2")),
+        Entity::Hole {
+            curves: vec![CurveReference::new(0.25, 0.75, 2)],
+        }
+    );
+}
+
+#[test]
+fn test_into_format_special_curve() {
+    let entity = Entity::SpecialCurve {
+        curves: vec![CurveReference::new(0.0, 1.0, 3)],
+    };
+    assert_eq!("scrv 0 1 3", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_special_curve() {
+    assert_eq!(
+        Entity::from(Format::from("scrv 0 1 3")),
+        Entity::SpecialCurve {
+            curves: vec![CurveReference::new(0.0, 1.0, 3)],
+        }
+    );
+}
+
+#[test]
+fn test_into_format_special_point() {
+    let entity = Entity::SpecialPoint {
+        vertices: vec![1, -1],
+    };
+    assert_eq!("sp 1 -1", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_special_point() {
+    assert_eq!(
+        Entity::from(Format::from("sp 1 -1")),
+        Entity::SpecialPoint {
+            vertices: vec![1, -1],
+        }
+    );
+}
+
+#[test]
+fn test_into_format_end() {
+    let entity = Entity::End;
+    assert_eq!("end", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_end() {
+    assert_eq!(Entity::from(Format::from("end")), Entity::End);
+}
+
+#[test]
+fn test_token_trimming_statements() {
+    assert_eq!("trim", Entity::Trim { curves: vec![] }.token());
+    assert_eq!("hole", Entity::Hole { curves: vec![] }.token());
+    assert_eq!("scrv", Entity::SpecialCurve { curves: vec![] }.token());
+    assert_eq!("sp", Entity::SpecialPoint { vertices: vec![] }.token());
+}
+
+#[test]
+fn test_token_end() {
+    assert_eq!("end", Entity::End.token());
+}
+
+#[test]
+fn test_into_format_bevel() {
+    let entity = Entity::Bevel { enabled: true };
+    assert_eq!("bevel on", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_bevel() {
+    assert_eq!(
+        Entity::from(Format::from("bevel on")),
+        Entity::Bevel { enabled: true }
+    );
+}
+
+#[test]
+fn test_token_bevel() {
+    assert_eq!("bevel", Entity::Bevel { enabled: true }.token());
+}
+
+#[test]
+fn test_into_format_bevel_off() {
+    let entity = Entity::Bevel { enabled: false };
+    assert_eq!("bevel off", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_bevel_off() {
+    assert_eq!(
+        Entity::from(Format::from("bevel off")),
+        Entity::Bevel { enabled: false }
+    );
+}
+
+#[test]
+fn test_token_bevel_off() {
+    assert_eq!("bevel", Entity::Bevel { enabled: false }.token());
+}
+
+#[test]
+fn test_into_format_color_interpolation() {
+    let entity = Entity::ColorInterpolation { enabled: true };
+    assert_eq!("c_interp on", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_color_interpolation() {
+    assert_eq!(
+        Entity::from(Format::from("c_interp on")),
+        Entity::ColorInterpolation { enabled: true }
+    );
+}
+
+#[test]
+fn test_token_color_interpolation() {
+    assert_eq!(
+        "c_interp",
+        Entity::ColorInterpolation { enabled: true }.token()
+    );
+}
+
+#[test]
+fn test_into_format_dissolve_interpolation() {
+    let entity = Entity::DissolveInterpolation { enabled: false };
+    assert_eq!("d_interp off", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_dissolve_interpolation() {
+    assert_eq!(
+        Entity::from(Format::from("d_interp off")),
+        Entity::DissolveInterpolation { enabled: false }
+    );
+}
+
+#[test]
+fn test_token_dissolve_interpolation() {
+    assert_eq!(
+        "d_interp",
+        Entity::DissolveInterpolation { enabled: false }.token()
+    );
+}
+
+#[test]
+fn test_into_format_level_of_detail() {
+    let entity = Entity::LevelOfDetail { level: 5 };
+    assert_eq!("lod 5", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_level_of_detail() {
+    assert_eq!(
+        Entity::from(Format::from("lod 5")),
+        Entity::LevelOfDetail { level: 5 }
+    );
+}
+
+#[test]
+fn test_token_level_of_detail() {
+    assert_eq!("lod", Entity::LevelOfDetail { level: 5 }.token());
+}
+
+#[test]
+fn test_into_format_shadow_object() {
+    let entity = Entity::ShadowObject {
+        name: "shadow.obj".to_owned(),
+    };
+    assert_eq!("shadow_obj shadow.obj", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_shadow_object() {
+    assert_eq!(
+        Entity::from(Format::from("shadow_obj shadow.obj")),
+        Entity::ShadowObject {
+            name: "shadow.obj".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn test_token_shadow_object() {
+    assert_eq!(
+        "shadow_obj",
+        Entity::ShadowObject {
+            name: "shadow.obj".to_owned(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_into_format_trace_object() {
+    let entity = Entity::TraceObject {
+        name: "trace.obj".to_owned(),
+    };
+    assert_eq!("trace_obj trace.obj", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_trace_object() {
+    assert_eq!(
+        Entity::from(Format::from("trace_obj trace.obj")),
+        Entity::TraceObject {
+            name: "trace.obj".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn test_token_trace_object() {
+    assert_eq!(
+        "trace_obj",
+        Entity::TraceObject {
+            name: "trace.obj".to_owned(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_serialized_len() {
+    assert_eq!(8, Entity::from(Format::from("o token")).serialized_len());
+    assert_eq!(
+        "v 0.1 1.2 2.3\n".len(),
+        Entity::from(Format::from("v 0.1 1.2 2.3")).serialized_len()
+    );
+    assert_eq!(
+        "# a\n# b\n".len(),
+        Entity::Comment {
+            content: "a\nb".to_owned()
+        }
+        .serialized_len()
+    );
+    assert_eq!(
+        "vendor_ext 1 2\n".len(),
+        Entity::Unknown {
+            raw: "vendor_ext 1 2".to_owned()
+        }
+        .serialized_len()
+    );
+}
+
+#[test]
+fn test_token_unknown() {
+    assert_eq!(
+        "vendor_ext",
+        Entity::Unknown {
+            raw: "vendor_ext 1 2 3".to_owned()
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_from_str_vertex() {
+    assert_eq!(
+        Entity::Vertex {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+            w: None,
+            color: None,
+        },
+        "v 1 2 3".parse::<Entity>().unwrap()
+    );
+}
+
+#[test]
+fn test_from_str_invalid() {
+    assert_eq!(
+        wavefront_rs::error::ReaderErrorKind::InvalidNumber,
+        "v 1 x 3".parse::<Entity>().unwrap_err().kind()
+    );
+}
+
+#[test]
+fn test_display_alignment() {
+    let entity = Entity::Object {
+        name: "token".to_owned(),
+    };
+    assert_eq!("[o token   ]", format!("[{:<10}]", entity));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_json_round_trip() {
+    let entities = vec![
+        Entity::Vertex {
+            x: 0.1,
+            y: 1.2,
+            z: 2.3,
+            w: None,
+            color: None,
+        },
+        Entity::Face {
+            vertices: vec![
+                FaceVertex::new2(1, Some(2), Some(3)),
+                FaceVertex::new2(4, None, Some(5)),
+                FaceVertex::new(6),
+            ],
+        },
+        Entity::End,
+    ];
+    let json = serde_json::to_string(&entities).unwrap();
+    assert_eq!(
+        entities,
+        serde_json::from_str::<Vec<Entity>>(&json).unwrap()
+    );
+}
+
+#[test]
+fn test_face_builder() {
+    assert_eq!(
+        Entity::Face {
+            vertices: vec![
+                FaceVertex::new(1),
+                FaceVertex::new2(2, None, Some(5)),
+                FaceVertex::new2(3, Some(6), None),
+                FaceVertex::new2(4, Some(8), Some(7)),
+            ],
+        },
+        FaceBuilder::new()
+            .vertex(1)
+            .vertex_texture(2, 5)
+            .vertex_normal(3, 6)
+            .vertex_full(4, 7, 8)
+            .build()
+    );
+}
+
+#[test]
+fn test_face_builder_empty() {
+    assert_eq!(
+        Entity::Face {
+            vertices: Vec::new()
+        },
+        FaceBuilder::new().build()
+    );
+}
+
+#[test]
+fn test_into_format_maplib() {
+    let entity = Entity::Maplib {
+        name: "token".to_owned(),
+    };
+    assert_eq!("maplib token", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_maplib() {
+    assert_eq!(
+        Entity::from(Format::from("maplib token")),
+        Entity::Maplib {
+            name: "token".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn test_token_maplib() {
+    assert_eq!(
+        "maplib",
+        Entity::Maplib {
+            name: "token".to_owned(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_into_format_usemap() {
+    let entity = Entity::Usemap {
+        name: "token".to_owned(),
+    };
+    assert_eq!("usemap token", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_usemap() {
+    assert_eq!(
+        Entity::from(Format::from("usemap token")),
+        Entity::Usemap {
+            name: "token".to_owned(),
+        }
+    );
+}
+
+#[test]
+fn test_token_usemap() {
+    assert_eq!(
+        "usemap",
+        Entity::Usemap {
+            name: "token".to_owned(),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_into_format_vertex_color() {
+    let entity = Entity::Vertex {
+        x: 0.1,
+        y: 1.2,
+        z: 2.3,
+        w: None,
+        color: Some([1.0, 0.5, 0.0]),
+    };
+    assert_eq!("v 0.1 1.2 2.3 1 0.5 0", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_vertex_color() {
+    assert_eq!(
+        Entity::from(Format::from("v 0.1 1.2 2.3 1 0.5 0")),
+        Entity::Vertex {
+            x: 0.1,
+            y: 1.2,
+            z: 2.3,
+            w: None,
+            color: Some([1.0, 0.5, 0.0]),
+        }
+    );
+}
+
+#[test]
+fn test_token_vertex_color() {
+    assert_eq!(
+        "v",
+        Entity::Vertex {
+            x: 0.1,
+            y: 1.2,
+            z: 2.3,
+            w: None,
+            color: Some([1.0, 0.5, 0.0]),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_into_format_vertex_w_color() {
+    let entity = Entity::Vertex {
+        x: 0.1,
+        y: 1.2,
+        z: 2.3,
+        w: Some(3.4),
+        color: Some([1.0, 0.5, 0.0]),
+    };
+    assert_eq!("v 0.1 1.2 2.3 3.4 1 0.5 0", Into::<Format>::into(entity));
+}
+
+#[test]
+fn test_from_objformat_vertex_w_color() {
+    assert_eq!(
+        Entity::from(Format::from("v 0.1 1.2 2.3 3.4 1 0.5 0")),
+        Entity::Vertex {
+            x: 0.1,
+            y: 1.2,
+            z: 2.3,
+            w: Some(3.4),
+            color: Some([1.0, 0.5, 0.0]),
+        }
+    );
+}
+
+#[test]
+fn test_token_vertex_w_color() {
+    assert_eq!(
+        "v",
+        Entity::Vertex {
+            x: 0.1,
+            y: 1.2,
+            z: 2.3,
+            w: Some(3.4),
+            color: Some([1.0, 0.5, 0.0]),
+        }
+        .token()
+    );
+}
+
+#[test]
+fn test_approx_eq() {
+    let a = Entity::Vertex {
+        x: 0.1 + 0.2,
+        y: 0.0,
+        z: 1.0,
+        w: None,
+        color: Some([0.5, 0.5, 0.5]),
+    };
+    let b = Entity::Vertex {
+        x: 0.3,
+        y: 0.0,
+        z: 1.0,
+        w: None,
+        color: Some([0.5, 0.5, 0.5]),
+    };
+    assert_ne!(a, b);
+    assert!(a.approx_eq(&b, 1e-9));
+    assert!(b.approx_eq(&a, 1e-9));
+    assert!(!a.approx_eq(&b, 0.0));
+}
+
+#[test]
+fn test_approx_eq_entities() {
+    for (a, b, epsilon, expected) in [
+        ("v 0.3 0 0", "v 0.30001 0 0", 0.0001, true),
+        ("v 0.3 0 0", "v 0.31 0 0", 0.0001, false),
+        ("v 0 0 0 1", "v 0 0 0", 1.0, false),
+        ("vn 0 0 1", "vn 0 0 0.99", 0.1, true),
+        ("vt 0.5 0.5", "vt 0.5 0.49", 0.1, true),
+        ("vt 0.5", "vt 0.5 0", 1.0, false),
+        ("vp 0.5 0.5 1", "vp 0.5 0.5 0.9", 0.01, false),
+        ("curv 0 1 1 2", "curv 0.01 1 1 2", 0.1, true),
+        ("curv 0 1 1 2", "curv 0 1 1 3", 1.0, false),
+        ("surf 0 1 0 1 1 2 3", "surf 0 1 0 1.01 1 2 3", 0.1, true),
+        ("parm u 0 1", "parm u 0 1.01", 0.1, true),
+        ("parm u 0 1", "parm v 0 1", 1.0, false),
+        ("trim 0 1 1", "trim 0.01 1 1", 0.1, true),
+        ("trim 0 1 1", "trim 0 1 2", 1.0, false),
+        ("f 1 2 3", "f 1 2 3", 0.0, true),
+        ("f 1 2 3", "f 1 2 4", 1.0, false),
+        ("o cube", "o box", 1.0, false),
+        ("v 0 0 1", "vn 0 0 1", 1.0, false),
+    ] {
+        let a = a.parse::<Entity>().unwrap();
+        let b = b.parse::<Entity>().unwrap();
+        assert_eq!(expected, a.approx_eq(&b, epsilon), "{} {}", a, b);
+    }
+}
+
+#[test]
+fn test_kind() {
+    for (input, kind) in [
+        ("# comment", EntityKind::Comment),
+        ("o cube", EntityKind::Object),
+        ("v 1 2 3", EntityKind::Vertex),
+        ("vn 0 0 1", EntityKind::VertexNormal),
+        ("f 1 2 3", EntityKind::Face),
+        ("end", EntityKind::End),
+        ("scrv 0 1 1", EntityKind::SpecialCurve),
+    ] {
+        assert_eq!(kind, input.parse::<Entity>().unwrap().kind());
+    }
+}
+
+#[test]
+fn test_kind_groups_entities() {
+    let entities: Vec<Entity> = ["v 0 0 0", "v 1 0 0", "vn 0 0 1", "f 1 2 1"]
+        .iter()
+        .map(|x| x.parse::<Entity>().unwrap())
+        .collect();
+    let mut counts = std::collections::HashMap::new();
+    for e in &entities {
+        *counts.entry(e.kind()).or_insert(0) += 1;
+    }
+    assert_eq!(Some(&2), counts.get(&EntityKind::Vertex));
+    assert_eq!(Some(&1), counts.get(&EntityKind::VertexNormal));
+    assert_eq!(None, counts.get(&EntityKind::Line));
+}
+
+#[test]
+fn test_face_vertex_from_token() {
+    for (input, expected) in [
+        ("1", FaceVertex::new(1)),
+        ("1/2", FaceVertex::new2(1, Some(2), None)),
+        ("1//3", FaceVertex::new2(1, None, Some(3))),
+        ("1/2/3", FaceVertex::new2(1, Some(2), Some(3))),
+        ("-1/-2/-3", FaceVertex::new2(-1, Some(-2), Some(-3))),
+        (" 4/5 ", FaceVertex::new2(4, Some(5), None)),
+        ("1/", FaceVertex::new(1)),
+        ("1//", FaceVertex::new(1)),
+    ] {
+        assert_eq!(
+            expected,
+            FaceVertex::from_token(input).unwrap(),
+            "{}",
+            input
+        );
+    }
+}
+
+#[test]
+fn test_face_vertex_token_normal_before_texture() {
+    let vertex = FaceVertex::from_token("1/2/3").unwrap();
+    assert_eq!(Some(2), vertex.normal);
+    assert_eq!(Some(3), vertex.texture);
+    assert_eq!(Some(2), FaceVertex::from_token("1/2").unwrap().normal);
+    assert_eq!(Some(3), FaceVertex::from_token("1//3").unwrap().texture);
+    assert_eq!("1/2/3", vertex.to_token());
+}
+
+#[test]
+fn test_face_vertex_from_token_invalid() {
+    for input in ["", "/2/3", "a", "1/b", "1//c", "1.5/2", "1/2/3/4"] {
+        let error = FaceVertex::from_token(input).unwrap_err();
+        assert_eq!(ReaderErrorKind::InvalidNumber, error.kind(), "{}", input);
+    }
+}
+
+#[test]
+fn test_face_vertex_to_token() {
+    for (vertex, expected) in [
+        (FaceVertex::new(1), "1"),
+        (FaceVertex::new2(1, Some(2), None), "1/2"),
+        (FaceVertex::new2(1, None, Some(3)), "1//3"),
+        (FaceVertex::new2(1, Some(2), Some(3)), "1/2/3"),
+        (FaceVertex::new2(-1, None, Some(-3)), "-1//-3"),
+    ] {
+        assert_eq!(expected, vertex.to_token());
+        assert_eq!(vertex, FaceVertex::from_token(&vertex.to_token()).unwrap());
+    }
+}
+
+#[test]
+fn test_face_vertex_token_matches_face() {
+    let face = "f 1 2/3 4//5 6/7/8".parse::<Entity>().unwrap();
+    match &face {
+        Entity::Face { vertices } => {
+            let tokens: Vec<String> = vertices.iter().map(|x| x.to_token()).collect();
+            assert_eq!(format!("f {}", tokens.join(" ")), face.to_string());
+            for (vertex, token) in vertices.iter().zip(["1", "2/3", "4//5", "6/7/8"].iter()) {
+                assert_eq!(vertex, &FaceVertex::from_token(token).unwrap());
+            }
+        }
+        _ => panic!("expected a face"),
+    }
+}
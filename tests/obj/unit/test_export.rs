@@ -0,0 +1,86 @@
+extern crate wavefront_rs;
+use super::common::parse;
+use wavefront_rs::obj::export::*;
+
+#[test]
+fn test_to_stl_triangles_quad() {
+    let entities = parse(&["v 0 0 0", "v 1 0 0", "v 1 1 0", "v 0 1 0", "f 1 2 3 4"]);
+    assert_eq!(
+        vec![
+            [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [1.0, 1.0, 0.0]],
+            [[0.0, 0.0, 0.0], [1.0, 1.0, 0.0], [0.0, 1.0, 0.0]],
+        ],
+        to_stl_triangles(&entities)
+    );
+}
+
+#[test]
+fn test_to_stl_facets_normals() {
+    let entities = parse(&["v 0 0 0", "v 0 0 2", "v 0 2 0", "f -3 -2 -1", "f 1 3 2"]);
+    let facets = to_stl_facets(&entities);
+    assert_eq!(2, facets.len());
+    assert_eq!([-1.0, 0.0, 0.0], facets[0].normal);
+    assert_eq!([1.0, 0.0, 0.0], facets[1].normal);
+    assert_eq!(
+        [[0.0, 0.0, 0.0], [0.0, 0.0, 2.0], [0.0, 2.0, 0.0]],
+        facets[0].vertices
+    );
+}
+
+#[test]
+fn test_to_stl_facets_degenerate() {
+    let entities = parse(&["v 0 0 0", "v 1 0 0", "v 2 0 0", "f 1 2 3"]);
+    assert_eq!([0.0, 0.0, 0.0], to_stl_facets(&entities)[0].normal);
+}
+
+#[test]
+fn test_to_stl_triangles_out_of_range() {
+    let entities = parse(&[
+        "v 0 0 0", "v 1 0 0", "v 1 1 0", "f 1 2 4", "f 0 1 2", "f 1 2",
+    ]);
+    assert!(to_stl_triangles(&entities).is_empty());
+    assert!(to_stl_triangles(&[]).is_empty());
+}
+
+#[test]
+fn test_write_ply() {
+    let entities = parse(&[
+        "o square",
+        "v 0 0 0",
+        "v 1 0 0",
+        "v 1 1 0",
+        "v 0 1 0.5",
+        "f 1 2 3 -1",
+        "f 1 2 5",
+    ]);
+    let mut result = Vec::new();
+    write_ply(&mut result, &entities).unwrap();
+    assert_eq!(
+        "ply\n\
+         format ascii 1.0\n\
+         element vertex 4\n\
+         property float x\n\
+         property float y\n\
+         property float z\n\
+         element face 2\n\
+         property list uchar int vertex_indices\n\
+         end_header\n\
+         0 0 0\n\
+         1 0 0\n\
+         1 1 0\n\
+         0 1 0.5\n\
+         3 0 1 2\n\
+         3 0 2 3\n",
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn test_write_ply_empty() {
+    let mut result = Vec::new();
+    write_ply(&mut result, &parse(&["# empty", "f 1 2 3"])).unwrap();
+    let result = String::from_utf8(result).unwrap();
+    assert!(result.contains("element vertex 0\n"));
+    assert!(result.contains("element face 0\n"));
+    assert!(result.ends_with("end_header\n"));
+}
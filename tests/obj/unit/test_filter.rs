@@ -0,0 +1,73 @@
+extern crate wavefront_rs;
+use super::common::parse;
+use wavefront_rs::obj::entity::*;
+use wavefront_rs::obj::filter::*;
+
+#[test]
+fn test_geometry_only() {
+    let entities = parse(&[
+        "# cube",
+        "mtllib cube.mtl",
+        "o cube",
+        "g front",
+        "s 1",
+        "usemtl red",
+        "v 0 0 0",
+        "vn 0 0 1",
+        "vt 0 0",
+        "vp 0.5",
+        "f 1/1/1 1/1/1 1/1/1",
+        "l 1 1",
+        "p 1",
+        "curv2 1",
+        "end",
+    ]);
+    assert_eq!(
+        parse(&[
+            "v 0 0 0",
+            "vn 0 0 1",
+            "vt 0 0",
+            "f 1/1/1 1/1/1 1/1/1",
+            "l 1 1",
+            "p 1",
+        ]),
+        geometry_only(&entities)
+    );
+}
+
+#[test]
+fn test_is_geometry() {
+    let entities = parse(&["o cube", "v 0 0 0", "# comment", "p 1"]);
+    assert_eq!(2, entities.iter().filter(|x| is_geometry(x)).count());
+    assert!(geometry_only(&[]).is_empty());
+}
+
+#[test]
+fn test_merge_comments() {
+    let entities = parse(&[
+        "# exported by",
+        "#",
+        "# tool 1.0",
+        "o cube",
+        "# vertices",
+        "v 0 0 0",
+    ]);
+    let merged = merge_comments(&entities);
+    assert_eq!(
+        vec![
+            Entity::Comment {
+                content: "exported by\n\ntool 1.0".to_owned()
+            },
+            Entity::Object {
+                name: "cube".to_owned()
+            },
+            Entity::Comment {
+                content: "vertices".to_owned()
+            },
+            entities[5].clone(),
+        ],
+        merged
+    );
+    assert_eq!("# exported by\n#\n# tool 1.0", merged[0].to_string());
+    assert!(merge_comments(&[]).is_empty());
+}
@@ -0,0 +1,273 @@
+extern crate wavefront_rs;
+use super::common::parse;
+use wavefront_rs::obj::entity::*;
+use wavefront_rs::obj::geometry::*;
+
+#[test]
+fn test_triangulate_triangle() {
+    let face = Entity::Face {
+        vertices: vec![FaceVertex::new(1), FaceVertex::new(2), FaceVertex::new(3)],
+    };
+    assert_eq!(vec![face.clone()], triangulate(&face));
+}
+
+#[test]
+fn test_triangulate_pentagon() {
+    let face = Entity::Face {
+        vertices: vec![
+            FaceVertex::new2(1, Some(11), Some(21)),
+            FaceVertex::new2(2, Some(12), Some(22)),
+            FaceVertex::new2(3, Some(13), Some(23)),
+            FaceVertex::new2(4, Some(14), Some(24)),
+            FaceVertex::new2(5, Some(15), Some(25)),
+        ],
+    };
+    assert_eq!(
+        vec![
+            Entity::Face {
+                vertices: vec![
+                    FaceVertex::new2(1, Some(11), Some(21)),
+                    FaceVertex::new2(2, Some(12), Some(22)),
+                    FaceVertex::new2(3, Some(13), Some(23)),
+                ],
+            },
+            Entity::Face {
+                vertices: vec![
+                    FaceVertex::new2(1, Some(11), Some(21)),
+                    FaceVertex::new2(3, Some(13), Some(23)),
+                    FaceVertex::new2(4, Some(14), Some(24)),
+                ],
+            },
+            Entity::Face {
+                vertices: vec![
+                    FaceVertex::new2(1, Some(11), Some(21)),
+                    FaceVertex::new2(4, Some(14), Some(24)),
+                    FaceVertex::new2(5, Some(15), Some(25)),
+                ],
+            },
+        ],
+        triangulate(&face)
+    );
+}
+
+#[test]
+fn test_triangulate_degenerate() {
+    let face = Entity::Face {
+        vertices: vec![FaceVertex::new(1), FaceVertex::new(2)],
+    };
+    assert!(triangulate(&face).is_empty());
+}
+
+#[test]
+fn test_triangulate_no_face() {
+    let line = Entity::Line {
+        vertices: vec![LineVertex::new(1), LineVertex::new(2), LineVertex::new(3)],
+    };
+    assert!(triangulate(&line).is_empty());
+}
+
+#[test]
+fn test_split_quad() {
+    let face = Entity::Face {
+        vertices: vec![
+            FaceVertex::new2(1, Some(11), Some(21)),
+            FaceVertex::new2(2, Some(12), Some(22)),
+            FaceVertex::new2(3, Some(13), Some(23)),
+            FaceVertex::new2(4, Some(14), Some(24)),
+        ],
+    };
+    assert_eq!(
+        Some((
+            Entity::Face {
+                vertices: vec![
+                    FaceVertex::new2(1, Some(11), Some(21)),
+                    FaceVertex::new2(2, Some(12), Some(22)),
+                    FaceVertex::new2(3, Some(13), Some(23)),
+                ],
+            },
+            Entity::Face {
+                vertices: vec![
+                    FaceVertex::new2(1, Some(11), Some(21)),
+                    FaceVertex::new2(3, Some(13), Some(23)),
+                    FaceVertex::new2(4, Some(14), Some(24)),
+                ],
+            },
+        )),
+        split_quad(&face)
+    );
+}
+
+#[test]
+fn test_split_quad_no_quad() {
+    let triangle = Entity::Face {
+        vertices: vec![FaceVertex::new(1), FaceVertex::new(2), FaceVertex::new(3)],
+    };
+    assert_eq!(None, split_quad(&triangle));
+    assert_eq!(
+        None,
+        split_quad(&Entity::Line {
+            vertices: vec![
+                LineVertex::new(1),
+                LineVertex::new(2),
+                LineVertex::new(3),
+                LineVertex::new(4)
+            ]
+        })
+    );
+}
+
+#[test]
+fn test_bounding_box() {
+    let entities = vec![
+        Entity::Vertex {
+            x: 1.0,
+            y: -2.0,
+            z: 3.0,
+            w: Some(100.0),
+            color: None,
+        },
+        Entity::VertexNormal {
+            x: 50.0,
+            y: 50.0,
+            z: 50.0,
+        },
+        Entity::Vertex {
+            x: -1.0,
+            y: 4.0,
+            z: 2.0,
+            w: None,
+            color: None,
+        },
+    ];
+    assert_eq!(
+        Some(BoundingBox {
+            min: [-1.0, -2.0, 2.0],
+            max: [1.0, 4.0, 3.0],
+        }),
+        bounding_box(&entities)
+    );
+}
+
+#[test]
+fn test_bounding_box_no_vertices() {
+    let entities = vec![Entity::Object {
+        name: "token".to_owned(),
+    }];
+    assert_eq!(None, bounding_box(&entities));
+}
+
+#[test]
+fn test_bounding_box_center_size() {
+    let bb = BoundingBox {
+        min: [-1.0, -2.0, 2.0],
+        max: [1.0, 4.0, 3.0],
+    };
+    assert_eq!([0.0, 1.0, 2.5], bb.center());
+    assert_eq!([2.0, 6.0, 1.0], bb.size());
+}
+
+#[test]
+fn test_compute_normals() {
+    let entities = parse(&[
+        "v 0 0 0",
+        "v 1 0 0",
+        "v 0 1 0",
+        "v 0 0 1",
+        "vn 1 0 0",
+        "f 1 2 3",
+        "f -4 -3 -1",
+    ]);
+    let d = 1.0 / 2.0f64.sqrt();
+    let mut expected = parse(&[
+        "v 0 0 0",
+        "v 1 0 0",
+        "v 0 1 0",
+        "v 0 0 1",
+        "vn 1 0 0",
+        "f 1/2 2/3 3/4",
+        "f -4/2 -3/3 -1/5",
+    ]);
+    expected.extend(vec![
+        Entity::VertexNormal {
+            x: 0.0,
+            y: -d,
+            z: d,
+        },
+        Entity::VertexNormal {
+            x: 0.0,
+            y: -d,
+            z: d,
+        },
+        Entity::VertexNormal {
+            x: 0.0,
+            y: 0.0,
+            z: 1.0,
+        },
+        Entity::VertexNormal {
+            x: 0.0,
+            y: -1.0,
+            z: 0.0,
+        },
+    ]);
+    assert_eq!(expected, compute_normals(&entities));
+}
+
+#[test]
+fn test_compute_normals_keeps_faces_with_normals() {
+    let entities = parse(&["v 0 0 0", "v 1 0 0", "v 0 1 0", "vn 0 0 1", "f 1/1 2/1 3/1"]);
+    assert_eq!(entities, compute_normals(&entities));
+}
+
+#[test]
+fn test_compute_normals_degenerate() {
+    let entities = parse(&[
+        "v 0 0 0", "v 1 0 0", "v 2 0 0", "v 0 1 0", "f 1 2 3", "f 1 2 4", "f 1 2 9",
+    ]);
+    let result = compute_normals(&entities);
+    assert_eq!(
+        parse(&["f 1/1 2/2 3", "f 1/1 2/2 4/3", "f 1/1 2/2 9"])[..],
+        result[4..7]
+    );
+    assert_eq!(
+        vec![
+            Entity::VertexNormal {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0,
+            };
+            3
+        ],
+        result[7..]
+    );
+}
+
+#[test]
+fn test_flip_winding() {
+    let mut entities = parse(&["vn 0 0 1", "f 1/11/21 2/12/22 3/13", "l 1 2 3"]);
+    flip_winding(&mut entities);
+    assert_eq!(
+        parse(&["vn 0 0 1", "f 3/13 2/12/22 1/11/21", "l 1 2 3"]),
+        entities
+    );
+}
+
+#[test]
+fn test_flip_winding_with_normals() {
+    let mut entities = parse(&["vn 0 -1 1", "f 1//1 2//2 3//3"]);
+    flip_winding_with_normals(&mut entities);
+    assert_eq!(parse(&["vn 0 1 -1", "f 3//3 2//2 1//1"]), entities);
+}
+
+#[test]
+fn test_face_edges() {
+    let face = "f 1/1 2/2 3/3 -1".parse::<Entity>().unwrap();
+    assert_eq!(vec![(1, 2), (2, 3), (3, -1), (-1, 1)], face_edges(&face));
+    let face = "f 4 5 6".parse::<Entity>().unwrap();
+    assert_eq!(vec![(4, 5), (5, 6), (6, 4)], face_edges(&face));
+}
+
+#[test]
+fn test_face_edges_no_face() {
+    assert!(face_edges(&"l 1 2".parse::<Entity>().unwrap()).is_empty());
+    assert!(face_edges(&"f 1 2".parse::<Entity>().unwrap()).is_empty());
+}
@@ -0,0 +1,517 @@
+extern crate wavefront_rs;
+use super::common::parse;
+use wavefront_rs::error::ValidationErrorKind;
+use wavefront_rs::mtl::model::MtlModel;
+use wavefront_rs::obj::entity::*;
+use wavefront_rs::obj::model::*;
+
+#[test]
+fn test_from_entities() {
+    let model = ObjModel::from_entities(&parse(&[
+        "mtllib token.mtl",
+        "o cube",
+        "v 0 0 0",
+        "v 1 0 0",
+        "v 0 1 0",
+        "vn 0 0 1",
+        "g front",
+        "usemtl red",
+        "f 1 2 3",
+        "g back side",
+        "l 1 2",
+        "o plane",
+        "v 1 1 0",
+        "vt 0 0",
+        "f -1 -2 -3",
+        "usemtl blue",
+        "p 4",
+    ]));
+    assert_eq!(4, model.vertices().len());
+    assert_eq!(1, model.normals().len());
+    assert_eq!(1, model.textures().len());
+    assert_eq!(
+        vec!["cube", "plane"],
+        model.objects().iter().map(|x| x.name()).collect::<Vec<_>>()
+    );
+
+    let cube = &model.objects()[0];
+    assert_eq!(3, cube.vertices().len());
+    assert_eq!(2, cube.elements().len());
+    assert_eq!(Some("front"), cube.elements()[0].group());
+    assert_eq!(Some("red"), cube.elements()[0].material());
+    assert_eq!(Some("back"), cube.elements()[1].group());
+    assert_eq!(
+        &["back".to_owned(), "side".to_owned()],
+        cube.elements()[1].groups()
+    );
+    assert_eq!(Some("red"), cube.elements()[1].material());
+    assert_eq!(
+        &Entity::Line {
+            vertices: vec![LineVertex::new(1), LineVertex::new(2)]
+        },
+        cube.elements()[1].entity()
+    );
+    assert_eq!(1, cube.faces().count());
+
+    let plane = &model.objects()[1];
+    assert_eq!(1, plane.vertices().len());
+    assert_eq!(None, plane.elements()[0].group());
+    assert!(plane.elements()[0].groups().is_empty());
+    assert_eq!(Some("red"), plane.elements()[0].material());
+    assert_eq!(Some("blue"), plane.elements()[1].material());
+    assert_eq!(
+        vec![&Entity::Face {
+            vertices: vec![FaceVertex::new(4), FaceVertex::new(3), FaceVertex::new(2)],
+        }],
+        plane.faces().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_from_entities_without_object() {
+    let model = ObjModel::from_entities(&parse(&["v 0 0 0", "p 1"]));
+    assert_eq!(1, model.objects().len());
+    assert_eq!("", model.objects()[0].name());
+    assert_eq!(1, model.objects()[0].elements().len());
+    assert_eq!(0, model.objects()[0].faces().count());
+}
+
+#[test]
+fn test_from_entities_empty() {
+    let model = ObjModel::from_entities(&[]);
+    assert!(model.objects().is_empty());
+    assert!(model.vertices().is_empty());
+}
+
+#[test]
+fn test_faces_with_positions() {
+    let model = ObjModel::from_entities(&parse(&[
+        "o a",
+        "v 0 0 0",
+        "v 1 0 0",
+        "v 0 1 0",
+        "f 1 2 3",
+        "l 1 2",
+        "f 1 2 5",
+        "o b",
+        "f -1/1 -2/1 0/1",
+        "f -1 -2 -3",
+    ]));
+    assert_eq!(
+        vec![
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]],
+            vec![[0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 0.0]],
+        ],
+        model.faces_with_positions().collect::<Vec<_>>()
+    );
+
+    let results = model.faces_with_positions_strict().collect::<Vec<_>>();
+    assert_eq!(4, results.len());
+    assert!(results[0].is_ok());
+    let error = results[1].as_ref().unwrap_err();
+    assert_eq!(ValidationErrorKind::Vertex, error.kind());
+    assert_eq!(1, error.entity());
+    assert_eq!(5, error.index());
+    assert_eq!(0, results[2].as_ref().unwrap_err().index());
+    assert_eq!(
+        &results[0],
+        &Ok(model.faces_with_positions().next().unwrap())
+    );
+}
+
+#[test]
+fn test_to_entities_by_material() {
+    let model = ObjModel::from_entities(&parse(&[
+        "v 0 0 0",
+        "p 1",
+        "o cube",
+        "v 1 0 0",
+        "usemtl red",
+        "f 1 2 -1",
+        "vn 0 0 1",
+        "usemtl blue",
+        "g side",
+        "f 1//1 2//1 1//1",
+        "usemtl red",
+        "f 2 1 2",
+        "g",
+        "l 1 2",
+        "o plane",
+        "vt 0 0",
+        "f 1/1 2/1 1/1",
+    ]));
+    assert_eq!(
+        parse(&[
+            "v 0 0 0",
+            "v 1 0 0",
+            "vn 0 0 1",
+            "vt 0 0",
+            "p 1",
+            "o cube",
+            "usemtl red",
+            "f 1 2 2",
+            "g side",
+            "f 2 1 2",
+            "g",
+            "l 1 2",
+            "usemtl blue",
+            "g side",
+            "f 1//1 2//1 1//1",
+            "o plane",
+            "usemtl red",
+            "f 1/1 2/1 1/1",
+        ]),
+        model.to_entities_by_material()
+    );
+    assert!(ObjModel::from_entities(&[])
+        .to_entities_by_material()
+        .is_empty());
+}
+
+#[test]
+fn test_to_entities_by_material_keeps_state() {
+    let model = ObjModel::from_entities(&parse(&[
+        "mtllib a.mtl",
+        "maplib a.mpl",
+        "v 0 0 0",
+        "s 1",
+        "mg 1 0.5",
+        "usemap wood",
+        "usemtl red",
+        "p 1",
+        "o",
+        "s off",
+        "usemtl blue",
+        "p 1",
+        "mtllib b.mtl",
+        "usemtl red",
+        "p 1",
+    ]));
+    assert_eq!(
+        vec!["a.mtl".to_owned(), "b.mtl".to_owned()],
+        model.material_libraries()
+    );
+    assert_eq!(
+        Some(SmoothingGroup::Off),
+        model.objects()[1].elements()[0].smoothing_group()
+    );
+    assert_eq!(
+        Some("1 0.5"),
+        model.objects()[1].elements()[0].merging_group()
+    );
+    assert_eq!(Some("wood"), model.objects()[1].elements()[0].map());
+    assert_eq!(
+        parse(&[
+            "mtllib a.mtl",
+            "mtllib b.mtl",
+            "maplib a.mpl",
+            "v 0 0 0",
+            "usemtl red",
+            "s 1",
+            "mg 1 0.5",
+            "usemap wood",
+            "p 1",
+            "o",
+            "usemtl blue",
+            "s off",
+            "p 1",
+            "usemtl red",
+            "p 1",
+        ]),
+        model.to_entities_by_material()
+    );
+}
+
+#[test]
+fn test_to_entities_by_material_round_trip() {
+    let source = parse(&[
+        "mtllib token.mtl",
+        "v 0 0 0",
+        "s 2",
+        "f 1 1 1",
+        "o",
+        "usemtl a",
+        "f 1 1 1",
+    ]);
+    let entities = ObjModel::from_entities(&source).to_entities_by_material();
+    assert_eq!(source, entities);
+    assert_eq!(
+        ObjModel::from_entities(&source),
+        ObjModel::from_entities(&entities)
+    );
+}
+
+#[test]
+fn test_to_entities_by_material_keeps_other_entities() {
+    let model = ObjModel::from_entities(&parse(&[
+        "# header",
+        "o curve",
+        "v 0 0 0",
+        "v 1 0 0",
+        "usemtl red",
+        "f 1 2 1",
+        "vp 0 0",
+        "vp 1 0",
+        "cstype bspline",
+        "deg 1",
+        "curv2 -2 -1",
+        "surf 0 1 0 1 1 2 1",
+        "parm u 0 1",
+        "trim 0 1 1",
+        "end",
+        "usemtl blue",
+        "p 1",
+    ]));
+    assert_eq!(parse(&["# header"]), model.other());
+    assert_eq!(9, model.objects()[0].other().len());
+    assert_eq!(
+        parse(&[
+            "# header",
+            "v 0 0 0",
+            "v 1 0 0",
+            "o curve",
+            "vp 0 0",
+            "vp 1 0",
+            "cstype bspline",
+            "deg 1",
+            "curv2 1 2",
+            "surf 0 1 0 1 1 2 1",
+            "parm u 0 1",
+            "trim 0 1 1",
+            "end",
+            "usemtl red",
+            "f 1 2 1",
+            "usemtl blue",
+            "p 1",
+        ]),
+        model.to_entities_by_material()
+    );
+}
+
+#[test]
+fn test_merge() {
+    let first = parse(&["# first", "o a", "v 0 0 0", "v 1 0 0", "vn 0 0 1", "l 1 2"]);
+    let second = parse(&[
+        "o b",
+        "v 0 1 0",
+        "v 1 1 0",
+        "v 0 0 1",
+        "vt 0 0",
+        "vn 0 1 0",
+        "f 1/1/1 2/1/1 -1/-1/-1",
+        "p 3",
+        "vp 0.5",
+        "curv2 1",
+    ]);
+    let third = parse(&[
+        "vp 0.1",
+        "curv 0.0 1.0 1 2",
+        "curv2 1 -1",
+        "vt 1 1",
+        "l 1/2 2/-1",
+    ]);
+    assert_eq!(
+        parse(&[
+            "# first",
+            "o a",
+            "v 0 0 0",
+            "v 1 0 0",
+            "vn 0 0 1",
+            "l 1 2",
+            "o b",
+            "v 0 1 0",
+            "v 1 1 0",
+            "v 0 0 1",
+            "vt 0 0",
+            "vn 0 1 0",
+            "f 3/2/1 4/2/1 -1/-1/-1",
+            "p 5",
+            "vp 0.5",
+            "curv2 1",
+            "vp 0.1",
+            "curv 0.0 1.0 6 7",
+            "curv2 2 -1",
+            "vt 1 1",
+            "l 6/3 7/-1",
+        ]),
+        merge(&[first, second, third])
+    );
+}
+
+#[test]
+fn test_merge_free_form() {
+    let first = parse(&["vp 0 0", "vp 1 0", "curv2 1 2", "curv2 2 1"]);
+    let second = parse(&[
+        "vp 0 1",
+        "vp 1 1",
+        "curv2 1 2",
+        "surf 0 1 0 1 1 1 1",
+        "trim 0.0 1.0 1",
+        "hole 0.0 1.0 -1",
+        "scrv 0.0 0.5 1 0.5 1.0 1",
+        "sp 1 2",
+        "end",
+    ]);
+    assert_eq!(
+        parse(&[
+            "vp 0 0",
+            "vp 1 0",
+            "curv2 1 2",
+            "curv2 2 1",
+            "vp 0 1",
+            "vp 1 1",
+            "curv2 3 4",
+            "surf 0 1 0 1 1 1 1",
+            "trim 0.0 1.0 3",
+            "hole 0.0 1.0 -1",
+            "scrv 0.0 0.5 3 0.5 1.0 3",
+            "sp 3 4",
+            "end",
+        ]),
+        merge(&[first, second])
+    );
+}
+
+#[test]
+fn test_merge_empty() {
+    assert!(merge(&[]).is_empty());
+    assert!(merge(&[Vec::new(), Vec::new()]).is_empty());
+}
+
+#[test]
+fn test_resolve_materials() {
+    let mtl = MtlModel::from_entities(
+        &["newmtl bronze", "Kd 1 0 0", "newmtl glass"]
+            .iter()
+            .map(|x| x.parse::<wavefront_rs::mtl::entity::Entity>().unwrap())
+            .collect::<Vec<_>>(),
+    );
+    let obj = parse(&[
+        "mtllib token.mtl",
+        "usemtl bronze",
+        "f 1 2 3",
+        "usemtl glass",
+    ]);
+    assert_eq!(Ok(()), resolve_materials(&obj, &mtl));
+    let obj = parse(&[
+        "usemtl steel",
+        "usemtl bronze",
+        "usemtl wood",
+        "usemtl steel",
+    ]);
+    assert_eq!(
+        Err(vec!["steel".to_owned(), "wood".to_owned()]),
+        resolve_materials(&obj, &mtl)
+    );
+    assert_eq!(Ok(()), resolve_materials(&[], &MtlModel::default()));
+}
+
+#[test]
+fn test_index_buffer() {
+    let model = ObjModel::from_entities(&parse(&[
+        "v 0 0 0",
+        "v 1 0 0",
+        "v 1 1 0",
+        "v 0 1 0",
+        "v 5 5 5",
+        "o quad",
+        "f 1 2 3 4",
+        "f 3 -2 1",
+    ]));
+    let (positions, indices) = model.index_buffer();
+    assert_eq!(
+        vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [0.0, 1.0, 0.0]
+        ],
+        positions
+    );
+    assert_eq!(vec![0, 1, 2, 0, 2, 3, 2, 3, 0], indices);
+}
+
+#[test]
+fn test_index_buffer_skips_invalid_triangles() {
+    let model = ObjModel::from_entities(&parse(&[
+        "v 0 0 0",
+        "v 1 0 0",
+        "v 1 1 0",
+        "f 1 2 3 9",
+        "f 1 2",
+    ]));
+    let (positions, indices) = model.index_buffer();
+    assert_eq!(3, positions.len());
+    assert_eq!(vec![0, 1, 2], indices);
+    assert_eq!(
+        (Vec::new(), Vec::new()),
+        ObjModel::from_entities(&[]).index_buffer()
+    );
+}
+
+#[test]
+fn test_index_buffer_with_attributes() {
+    let model = ObjModel::from_entities(&parse(&[
+        "v 0 0 0",
+        "v 1 0 0",
+        "v 1 1 0",
+        "vn 0 0 1",
+        "vn 0 0 -1",
+        "vt 0 0",
+        "vt 1",
+        "f 1/1/1 2/1/2 3/1",
+        "f 1/2/1 3 2//2",
+    ]));
+    let buffer = model.index_buffer_with_attributes();
+    assert_eq!(
+        vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [1.0, 0.0, 0.0],
+        ],
+        buffer.positions
+    );
+    assert_eq!(
+        vec![
+            [0.0, 0.0, 1.0],
+            [0.0, 0.0, 1.0],
+            [0.0, 0.0, 1.0],
+            [0.0, 0.0, -1.0],
+            [0.0, 0.0, 0.0],
+            [0.0, 0.0, 0.0],
+        ],
+        buffer.normals
+    );
+    assert_eq!(
+        vec![
+            [0.0, 0.0],
+            [1.0, 0.0],
+            [0.0, 0.0],
+            [0.0, 0.0],
+            [0.0, 0.0],
+            [1.0, 0.0],
+        ],
+        buffer.textures
+    );
+    assert_eq!(vec![0, 1, 2, 3, 4, 5], buffer.indices);
+}
+
+#[test]
+fn test_index_buffer_with_attributes_shares_equal_vertices() {
+    let model = ObjModel::from_entities(&parse(&[
+        "v 0 0 0",
+        "v 1 0 0",
+        "v 1 1 0",
+        "v 0 1 0",
+        "vn 0 0 1",
+        "f 1/1 2/1 3/1 4/1",
+    ]));
+    let buffer = model.index_buffer_with_attributes();
+    assert_eq!(4, buffer.positions.len());
+    assert_eq!(4, buffer.normals.len());
+    assert_eq!(4, buffer.textures.len());
+    assert_eq!(vec![0, 1, 2, 0, 2, 3], buffer.indices);
+}
@@ -0,0 +1,149 @@
+extern crate wavefront_rs;
+use super::common::parse;
+use wavefront_rs::obj::optimize::*;
+
+#[test]
+fn test_dedup_vertices() {
+    let entities = parse(&[
+        "v 0 0 0",
+        "v 1 0 0",
+        "v 0 0 0.0001",
+        "v 0 1 0",
+        "vn 0 0 1",
+        "vn 0 0 1",
+        "vt 0.5 0.5",
+        "vt 0.5",
+        "vt 0.5 0.5",
+        "f 1/1/1 2/2/2 4/1/3",
+        "f 3/2/2 2/1/1 -1/-1/-1",
+        "l 3 4",
+    ]);
+    let (result, removed) = dedup_vertices_with_stats(&entities, 0.001);
+    assert_eq!(
+        Removed {
+            vertices: 1,
+            normals: 1,
+            textures: 1,
+        },
+        removed
+    );
+    assert_eq!(
+        parse(&[
+            "v 0 0 0",
+            "v 1 0 0",
+            "v 0 1 0",
+            "vn 0 0 1",
+            "vt 0.5 0.5",
+            "vt 0.5",
+            "f 1/1/1 2/1/2 3/1/1",
+            "f 1/1/2 2/1/1 3/1/1",
+            "l 1 3",
+        ]),
+        result
+    );
+}
+
+#[test]
+fn test_dedup_vertices_keeps_distinct() {
+    let entities = parse(&[
+        "v 0 0 0",
+        "v 0 0 0.01",
+        "v 0 0 0 1 0 0",
+        "v 0 0 0 0.5",
+        "f 1 2 3 4",
+    ]);
+    let (result, removed) = dedup_vertices_with_stats(&entities, 0.001);
+    assert_eq!(Removed::default(), removed);
+    assert_eq!(entities, result);
+}
+
+#[test]
+fn test_dedup_vertices_exact() {
+    let entities = parse(&["v 0 0 0", "v -0 0 0", "v 0 0 0.0001", "p 2 3"]);
+    assert_eq!(
+        parse(&["v 0 0 0", "v 0 0 0.0001", "p 1 2"]),
+        dedup_vertices(&entities, 0.0)
+    );
+}
+
+#[test]
+fn test_dedup_vertices_across_cells() {
+    let entities = parse(&["v 0.0999 0 0", "v 0.1001 0 0", "p 2"]);
+    assert_eq!(
+        parse(&["v 0.0999 0 0", "p 1"]),
+        dedup_vertices(&entities, 0.1)
+    );
+}
+
+#[test]
+fn test_remove_unused_vertices() {
+    let mut entities = parse(&[
+        "v 0 0 0",
+        "v 1 0 0",
+        "v 0 1 0",
+        "v 1 1 0",
+        "vn 0 0 1",
+        "vn 0 0 -1",
+        "vt 0 0",
+        "vt 1 1",
+        "f 1/1/1 2/1/1 3/1/1",
+        "f 2/2/2 4/2/2 3/2/2",
+        "l -4 -2",
+    ]);
+    entities.remove(9);
+    let (result, removed) = remove_unused_vertices_with_stats(&entities);
+    assert_eq!(
+        Removed {
+            vertices: 1,
+            normals: 1,
+            textures: 1,
+        },
+        removed
+    );
+    assert_eq!(
+        parse(&[
+            "v 0 0 0",
+            "v 1 0 0",
+            "v 0 1 0",
+            "vn 0 0 1",
+            "vt 0 0",
+            "f 1/1/1 2/1/1 3/1/1",
+            "l 1 3",
+        ]),
+        result
+    );
+    let entities = parse(&["v 0 0 0", "v 1 0 0", "v 0 1 0", "v 1 1 0", "p 4", "f 2 4 9"]);
+    assert_eq!(
+        parse(&["v 1 0 0", "v 1 1 0", "p 2", "f 1 2 9"]),
+        remove_unused_vertices(&entities)
+    );
+}
+
+#[test]
+fn test_remove_unused_vertices_orphan() {
+    let mut entities = parse(&[
+        "v 0 0 0", "v 1 0 0", "v 0 1 0", "v 1 1 0", "f 1 2 3", "f 2 4 3",
+    ]);
+    entities.pop();
+    assert_eq!(
+        parse(&["v 0 0 0", "v 1 0 0", "v 0 1 0", "f 1 2 3"]),
+        remove_unused_vertices(&entities)
+    );
+}
+
+#[test]
+fn test_remove_unused_vertices_line_texture() {
+    let entities = parse(&["v 0 0 0", "v 1 0 0", "vt 0 0", "vt 1 1", "l 1/2 2/2"]);
+    assert_eq!(
+        parse(&["v 0 0 0", "v 1 0 0", "vt 1 1", "l 1/1 2/1"]),
+        remove_unused_vertices(&entities)
+    );
+}
+
+#[test]
+fn test_remove_unused_vertices_forward_reference() {
+    let entities = parse(&["f 1 2 4", "v 0 0 0", "v 1 0 0", "v 0 1 0", "v 1 1 0"]);
+    let (result, removed) = remove_unused_vertices_with_stats(&entities);
+    assert_eq!(parse(&["f 1 2 3", "v 0 0 0", "v 1 0 0", "v 1 1 0"]), result);
+    assert_eq!(1, removed.vertices);
+}
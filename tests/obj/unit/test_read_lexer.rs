@@ -1,5 +1,6 @@
 extern crate wavefront_rs;
 use std::io::BufReader;
+use wavefront_rs::error::ReaderErrorKind;
 use wavefront_rs::obj::entity::*;
 use wavefront_rs::obj::read_lexer::*;
 
@@ -17,7 +18,7 @@ fn test_read_to_end_comment() {
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -45,7 +46,7 @@ fn test_read_to_end_object() {
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -59,6 +60,31 @@ fn test_read_line_object() {
     );
 }
 
+#[test]
+fn test_read_line_object_without_name() {
+    for input in ["o", "o  ", "  O", "o # comment"] {
+        assert_eq!(
+            Entity::Object {
+                name: String::new()
+            },
+            ReadLexer::read_line(&mut BufReader::new(input.as_bytes())).unwrap(),
+            "{}",
+            input
+        );
+    }
+}
+
+#[test]
+fn test_read_line_object_name_after_whitespace() {
+    let stream = std::io::Cursor::new("  O  my cube ");
+    assert_eq!(
+        Entity::Object {
+            name: "my cube".to_owned()
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
 #[test]
 fn test_read_to_end_group() {
     let stream = std::io::Cursor::new("g token");
@@ -66,14 +92,14 @@ fn test_read_to_end_group() {
     ReadLexer::read_to_end(&mut BufReader::new(stream), |x| {
         assert_eq!(
             Entity::Group {
-                name: "token".to_owned()
+                names: vec!["token".to_owned()]
             },
             x
         );
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -81,7 +107,7 @@ fn test_read_line_group() {
     let stream = std::io::Cursor::new("g token");
     assert_eq!(
         Entity::Group {
-            name: "token".to_owned()
+            names: vec!["token".to_owned()]
         },
         ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
     );
@@ -89,32 +115,58 @@ fn test_read_line_group() {
 
 #[test]
 fn test_read_to_end_smoothing_group() {
-    let stream = std::io::Cursor::new("s token");
+    let stream = std::io::Cursor::new("s 1");
     let exists = std::cell::Cell::new(false);
     ReadLexer::read_to_end(&mut BufReader::new(stream), |x| {
         assert_eq!(
             Entity::SmoothingGroup {
-                name: "token".to_owned()
+                group: SmoothingGroup::Group(1)
             },
             x
         );
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
 fn test_read_line_smoothing_group() {
-    let stream = std::io::Cursor::new("s token");
+    let stream = std::io::Cursor::new("s 1");
     assert_eq!(
         Entity::SmoothingGroup {
-            name: "token".to_owned()
+            group: SmoothingGroup::Group(1)
         },
         ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
     );
 }
 
+#[test]
+fn test_read_line_smoothing_group_off() {
+    for (input, group) in &[
+        ("s off", SmoothingGroup::Off),
+        ("s OFF", SmoothingGroup::Off),
+        ("s 0", SmoothingGroup::Group(0)),
+    ] {
+        assert_eq!(
+            Entity::SmoothingGroup { group: *group },
+            ReadLexer::read_line(&mut BufReader::new(input.as_bytes())).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_read_line_smoothing_group_invalid() {
+    for (input, kind) in &[
+        ("s token", ReaderErrorKind::InvalidNumber),
+        ("s -1", ReaderErrorKind::InvalidNumber),
+        ("s", ReaderErrorKind::MissingField),
+    ] {
+        let error = ReadLexer::read_line(&mut BufReader::new(input.as_bytes())).unwrap_err();
+        assert_eq!(*kind, error.kind());
+    }
+}
+
 #[test]
 fn test_read_to_end_merging_group() {
     let stream = std::io::Cursor::new("mg token");
@@ -129,7 +181,7 @@ fn test_read_to_end_merging_group() {
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -143,6 +195,20 @@ fn test_read_line_merging_group() {
     );
 }
 
+#[test]
+fn test_read_line_merging_group_after_whitespace() {
+    for input in ["  mg 1 2", "MG 1 2", "\tMg  1 2 "] {
+        assert_eq!(
+            Entity::MergingGroup {
+                name: "1 2".to_owned()
+            },
+            ReadLexer::read_line(&mut BufReader::new(input.as_bytes())).unwrap(),
+            "{}",
+            input
+        );
+    }
+}
+
 #[test]
 fn test_read_to_end_mtllib() {
     let stream = std::io::Cursor::new("mtllib token");
@@ -157,7 +223,7 @@ fn test_read_to_end_mtllib() {
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -171,6 +237,41 @@ fn test_read_line_mtllib() {
     );
 }
 
+#[test]
+fn test_read_line_file_names_with_spaces() {
+    for (input, expected) in [
+        (
+            "mtllib my materials.mtl",
+            Entity::Mtllib {
+                name: "my materials.mtl".to_owned(),
+            },
+        ),
+        (
+            "maplib  my  maps.mpc ",
+            Entity::Maplib {
+                name: "my  maps.mpc".to_owned(),
+            },
+        ),
+        (
+            "shadow_obj my shadow.obj # comment",
+            Entity::ShadowObject {
+                name: "my shadow.obj".to_owned(),
+            },
+        ),
+        (
+            "trace_obj my trace.obj",
+            Entity::TraceObject {
+                name: "my trace.obj".to_owned(),
+            },
+        ),
+    ] {
+        assert_eq!(
+            expected,
+            ReadLexer::read_line(&mut BufReader::new(input.as_bytes())).unwrap()
+        );
+    }
+}
+
 #[test]
 fn test_read_to_end_usemtl() {
     let stream = std::io::Cursor::new("usemtl token");
@@ -185,7 +286,7 @@ fn test_read_to_end_usemtl() {
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -210,13 +311,14 @@ fn test_read_to_end_vertex_xyzw() {
                 y: 1.2f64,
                 z: 2.3f64,
                 w: Some(3.4f64),
+                color: None,
             },
             x
         );
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -228,6 +330,7 @@ fn test_read_line_vertex_xyzw() {
             y: 1.2f64,
             z: 2.3f64,
             w: Some(3.4f64),
+            color: None,
         },
         ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
     );
@@ -244,13 +347,14 @@ fn test_read_to_end_vertex_xyz() {
                 y: 1.2f64,
                 z: 2.3f64,
                 w: None,
+                color: None,
             },
             x
         );
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -262,6 +366,7 @@ fn test_read_line_vertex_xyz() {
             y: 1.2f64,
             z: 2.3f64,
             w: None,
+            color: None,
         },
         ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
     );
@@ -283,7 +388,7 @@ fn test_read_to_end_vertex_normal() {
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -315,7 +420,7 @@ fn test_read_to_end_vertex_texture_uvw() {
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -347,7 +452,7 @@ fn test_read_to_end_vertex_texture_uv() {
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -379,7 +484,7 @@ fn test_read_to_end_vertex_texture_u() {
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -411,7 +516,7 @@ fn test_read_to_end_vertex_parameter_uvw() {
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -443,7 +548,7 @@ fn test_read_to_end_vertex_parameter_uv() {
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -475,7 +580,7 @@ fn test_read_to_end_vertex_parameter_u() {
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -509,7 +614,7 @@ fn test_read_to_end_face_vnt_3() {
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -547,7 +652,7 @@ fn test_read_to_end_face_vnt_5() {
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -585,7 +690,7 @@ fn test_read_to_end_face_vt() {
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -621,7 +726,7 @@ fn test_read_to_end_face_vn() {
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -657,7 +762,7 @@ fn test_read_to_end_face_v() {
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -682,14 +787,20 @@ fn test_read_to_end_line() {
     ReadLexer::read_to_end(&mut BufReader::new(stream), |x| {
         assert_eq!(
             Entity::Line {
-                vertices: vec!(0, 1, 2, 3, 4)
+                vertices: vec![
+                    LineVertex::new(0),
+                    LineVertex::new(1),
+                    LineVertex::new(2),
+                    LineVertex::new(3),
+                    LineVertex::new(4)
+                ]
             },
             x
         );
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -697,7 +808,13 @@ fn test_read_line_line() {
     let stream = std::io::Cursor::new("l 0 1 2 3 4");
     assert_eq!(
         Entity::Line {
-            vertices: vec!(0, 1, 2, 3, 4)
+            vertices: vec![
+                LineVertex::new(0),
+                LineVertex::new(1),
+                LineVertex::new(2),
+                LineVertex::new(3),
+                LineVertex::new(4)
+            ]
         },
         ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
     );
@@ -717,7 +834,7 @@ fn test_read_to_end_point() {
         exists.set(true);
     })
     .unwrap();
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -730,3 +847,791 @@ fn test_read_line_point() {
         ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
     );
 }
+
+#[test]
+fn test_entities() {
+    let mut stream = BufReader::new(std::io::Cursor::new("g token\n\nmg token\n"));
+    let mut entities = Vec::new();
+    for e in ReadLexer::entities(&mut stream) {
+        entities.push(e.unwrap());
+    }
+    assert_eq!(
+        vec![
+            Entity::Group {
+                names: vec!["token".to_owned()]
+            },
+            Entity::MergingGroup {
+                name: "token".to_owned()
+            },
+        ],
+        entities
+    );
+}
+
+#[test]
+fn test_read_line_error_unknown_token() {
+    let stream = std::io::Cursor::new("xyz 1 2 3\n");
+    let error = ReadLexer::read_line(&mut BufReader::new(stream)).unwrap_err();
+    assert_eq!(ReaderErrorKind::UnknownToken, error.kind());
+    assert_eq!("xyz 1 2 3", error.raw());
+    assert_eq!(1, error.line());
+}
+
+#[test]
+fn test_read_line_error_missing_field() {
+    let stream = std::io::Cursor::new("v 0.1 1.2");
+    let error = ReadLexer::read_line(&mut BufReader::new(stream)).unwrap_err();
+    assert_eq!(ReaderErrorKind::MissingField, error.kind());
+}
+
+#[test]
+fn test_read_line_error_invalid_number() {
+    let stream = std::io::Cursor::new("v 0.1 x 2.3");
+    let error = ReadLexer::read_line(&mut BufReader::new(stream)).unwrap_err();
+    assert_eq!(ReaderErrorKind::InvalidNumber, error.kind());
+}
+
+#[test]
+fn test_read_line_error_invalid_face_index() {
+    let stream = std::io::Cursor::new("f 1/2/3 x 3");
+    let error = ReadLexer::read_line(&mut BufReader::new(stream)).unwrap_err();
+    assert_eq!(ReaderErrorKind::InvalidNumber, error.kind());
+}
+
+#[test]
+fn test_read_line_error_eof() {
+    let stream = std::io::Cursor::new("");
+    let error = ReadLexer::read_line(&mut BufReader::new(stream)).unwrap_err();
+    assert_eq!(ReaderErrorKind::EndOfFile, error.kind());
+}
+
+#[test]
+fn test_read_to_end_error_line() {
+    let stream = std::io::Cursor::new("o token\n\nl 1 x\n");
+    let error = ReadLexer::read_to_end(&mut BufReader::new(stream), |_| {}).unwrap_err();
+    assert_eq!(ReaderErrorKind::InvalidNumber, error.kind());
+    assert_eq!(3, error.line());
+    assert_eq!("l 1 x", error.raw());
+}
+
+#[test]
+fn test_read_line_curve() {
+    let stream = std::io::Cursor::new("curv 0.5 1.5 1 -2 3 4");
+    assert_eq!(
+        Entity::Curve {
+            u_start: 0.5,
+            u_end: 1.5,
+            vertices: vec![1, -2, 3, 4],
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_curve2d() {
+    let stream = std::io::Cursor::new("curv2 1 2 -3 4");
+    assert_eq!(
+        Entity::Curve2D {
+            vertices: vec![1, 2, -3, 4],
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_curve_missing_parameter() {
+    let stream = std::io::Cursor::new("curv 0.5");
+    let error = ReadLexer::read_line(&mut BufReader::new(stream)).unwrap_err();
+    assert_eq!(ReaderErrorKind::MissingField, error.kind());
+}
+
+#[test]
+fn test_read_line_surface() {
+    let stream = std::io::Cursor::new("surf 0.5 1.5 2.5 3.5 1/2/3 4//5 -6");
+    assert_eq!(
+        Entity::Surface {
+            s_start: 0.5,
+            s_end: 1.5,
+            t_start: 2.5,
+            t_end: 3.5,
+            vertices: vec![
+                FaceVertex::new2(1, Some(2), Some(3)),
+                FaceVertex::new2(4, None, Some(5)),
+                FaceVertex::new2(-6, None, None),
+            ],
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_curve_surface_type_rational() {
+    for input in [
+        "cstype rat bspline",
+        "cstype RAT bspline",
+        "cstype Rat bspline",
+    ] {
+        assert_eq!(
+            Entity::CurveSurfaceType {
+                rational: true,
+                kind: "bspline".to_owned(),
+            },
+            ReadLexer::read_line(&mut BufReader::new(input.as_bytes())).unwrap(),
+            "{}",
+            input
+        );
+    }
+}
+
+#[test]
+fn test_read_line_curve_surface_type() {
+    let stream = std::io::Cursor::new("cstype bezier");
+    assert_eq!(
+        Entity::CurveSurfaceType {
+            rational: false,
+            kind: "bezier".to_owned(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_degree_u() {
+    let stream = std::io::Cursor::new("deg 3");
+    assert_eq!(
+        Entity::Degree { u: 3, v: None },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_degree_uv() {
+    let stream = std::io::Cursor::new("deg 3 2");
+    assert_eq!(
+        Entity::Degree { u: 3, v: Some(2) },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_end() {
+    let stream = std::io::Cursor::new("end");
+    assert_eq!(
+        Entity::End,
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_with_preserve_unknown() {
+    let stream = std::io::Cursor::new("vendor_ext  1 2 3\n");
+    let options = ReadOptions {
+        preserve_unknown: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        Entity::Unknown {
+            raw: "vendor_ext  1 2 3".to_owned()
+        },
+        ReadLexer::read_line_with(&mut BufReader::new(stream), &options).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_with_preserve_unknown_inline_comment() {
+    let options = ReadOptions {
+        preserve_unknown: true,
+        comma_separators: true,
+        ..Default::default()
+    };
+    for input in ["vendor_ext a b # note", "vendor_ext a,b"] {
+        assert_eq!(
+            Entity::Unknown {
+                raw: input.to_owned()
+            },
+            ReadLexer::read_line_with(&mut BufReader::new(input.as_bytes()), &options).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_read_line_unknown_not_preserved_by_default() {
+    let stream = std::io::Cursor::new("vendor_ext 1 2 3\n");
+    assert!(ReadLexer::read_line(&mut BufReader::new(stream)).is_err());
+}
+
+#[test]
+fn test_read_line_maplib() {
+    let stream = std::io::Cursor::new("maplib token");
+    assert_eq!(
+        Entity::Maplib {
+            name: "token".to_owned(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_usemap() {
+    let stream = std::io::Cursor::new("usemap token");
+    assert_eq!(
+        Entity::Usemap {
+            name: "token".to_owned(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_continuation() {
+    let stream = std::io::Cursor::new("f 1 2 \\\n3 4\\\n 5 6\nv 0 0 0\n");
+    let mut reader = BufReader::new(stream);
+    assert_eq!(
+        Entity::Face {
+            vertices: vec![
+                FaceVertex::new(1),
+                FaceVertex::new(2),
+                FaceVertex::new(3),
+                FaceVertex::new(4),
+                FaceVertex::new(5),
+                FaceVertex::new(6),
+            ],
+        },
+        ReadLexer::read_line(&mut reader).unwrap()
+    );
+    assert_eq!(
+        Entity::Vertex {
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+            w: None,
+            color: None,
+        },
+        ReadLexer::read_line(&mut reader).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_continuation_at_eof() {
+    let stream = std::io::Cursor::new("l 1 2 \\\r\n");
+    assert_eq!(
+        Entity::Line {
+            vertices: vec![LineVertex::new(1), LineVertex::new(2)],
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_with_strict_w_zero() {
+    let stream = std::io::Cursor::new("v 1 2 3 0");
+    let options = ReadOptions {
+        strict_w: true,
+        ..Default::default()
+    };
+    let error = ReadLexer::read_line_with(&mut BufReader::new(stream), &options).unwrap_err();
+    assert_eq!(ReaderErrorKind::InvalidNumber, error.kind());
+    assert_eq!(
+        "line 1: w of v must be positive and finite but is 0 (\"v 1 2 3 0\")",
+        error.to_string()
+    );
+}
+
+#[test]
+fn test_read_line_with_strict_w_nan() {
+    let stream = std::io::Cursor::new("v 1 2 3 nan");
+    let options = ReadOptions {
+        strict_w: true,
+        ..Default::default()
+    };
+    let error = ReadLexer::read_line_with(&mut BufReader::new(stream), &options).unwrap_err();
+    assert_eq!(ReaderErrorKind::InvalidNumber, error.kind());
+}
+
+#[test]
+fn test_read_line_with_strict_w_valid() {
+    let options = ReadOptions {
+        strict_w: true,
+        ..Default::default()
+    };
+    for line in &["v 1 2 3 0.5", "v 1 2 3"] {
+        let stream = std::io::Cursor::new(line);
+        assert!(ReadLexer::read_line_with(&mut BufReader::new(stream), &options).is_ok());
+    }
+}
+
+#[test]
+fn test_read_line_lenient_w_by_default() {
+    for (line, w) in &[("v 1 2 3 0", 0.0), ("v 1 2 3 -1", -1.0)] {
+        let stream = std::io::Cursor::new(line);
+        assert_eq!(
+            Entity::Vertex {
+                x: 1.0,
+                y: 2.0,
+                z: 3.0,
+                w: Some(*w),
+                color: None,
+            },
+            ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+        );
+    }
+    let stream = std::io::Cursor::new("v 1 2 3 nan");
+    assert!(ReadLexer::read_line(&mut BufReader::new(stream)).is_ok());
+}
+
+#[test]
+fn test_read_line_inline_comment() {
+    let stream = std::io::Cursor::new("v 1 2 3 # origin\n");
+    assert_eq!(
+        Entity::Vertex {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+            w: None,
+            color: None,
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_group_names() {
+    for (input, names) in &[("g a b c", vec!["a", "b", "c"]), ("g", vec![])] {
+        assert_eq!(
+            Entity::Group {
+                names: names.iter().map(|x| x.to_string()).collect(),
+            },
+            ReadLexer::read_line(&mut BufReader::new(input.as_bytes())).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_read_line_inline_comment_after_name() {
+    let stream = std::io::Cursor::new("g front side#note\n");
+    assert_eq!(
+        Entity::Group {
+            names: vec!["front".to_owned(), "side".to_owned()],
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_comment_keeps_hash() {
+    let stream = std::io::Cursor::new("# see # here");
+    assert_eq!(
+        Entity::Comment {
+            content: "see # here".to_owned(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_only_inline_comment_fields() {
+    let stream = std::io::Cursor::new("v 1 2 # 3");
+    let error = ReadLexer::read_line(&mut BufReader::new(stream)).unwrap_err();
+    assert_eq!(ReaderErrorKind::MissingField, error.kind());
+}
+
+#[test]
+fn test_read_line_comment_empty() {
+    let stream = std::io::Cursor::new("#");
+    assert_eq!(
+        Entity::Comment {
+            content: "".to_owned(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_comment_without_space() {
+    let stream = std::io::Cursor::new("#token");
+    assert_eq!(
+        Entity::Comment {
+            content: "token".to_owned(),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_vertex_color() {
+    let stream = std::io::Cursor::new("v 0.1 1.2 2.3 1 0.5 0");
+    assert_eq!(
+        Entity::Vertex {
+            x: 0.1,
+            y: 1.2,
+            z: 2.3,
+            w: None,
+            color: Some([1.0, 0.5, 0.0]),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_vertex_w_color() {
+    let stream = std::io::Cursor::new("v 0.1 1.2 2.3 3.4 1 0.5 0");
+    assert_eq!(
+        Entity::Vertex {
+            x: 0.1,
+            y: 1.2,
+            z: 2.3,
+            w: Some(3.4),
+            color: Some([1.0, 0.5, 0.0]),
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_vertex_extra_fields() {
+    let stream = std::io::Cursor::new("v 0.1 1.2 2.3 3.4 0.5");
+    assert_eq!(
+        Entity::Vertex {
+            x: 0.1,
+            y: 1.2,
+            z: 2.3,
+            w: Some(3.4),
+            color: None,
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+    for input in ["v 0.1 1.2 2.3 3.4 1 0.5 0 1", "v 0.1 1.2 2.3 3.4 1 0.5 0 x"] {
+        assert_eq!(
+            Entity::Vertex {
+                x: 0.1,
+                y: 1.2,
+                z: 2.3,
+                w: Some(3.4),
+                color: Some([1.0, 0.5, 0.0]),
+            },
+            ReadLexer::read_line(&mut BufReader::new(input.as_bytes())).unwrap(),
+            "{}",
+            input
+        );
+    }
+    let stream = std::io::Cursor::new("v 0.1 1.2 2.3 3.4 1 x 0");
+    assert_eq!(
+        ReaderErrorKind::InvalidNumber,
+        ReadLexer::read_line(&mut BufReader::new(stream))
+            .unwrap_err()
+            .kind()
+    );
+}
+
+#[test]
+fn test_read_line_vertex_reject_extra_values() {
+    let options = ReadOptions {
+        reject_extra_values: true,
+        ..Default::default()
+    };
+    for input in ["v 1 2 3 4 5", "v 1 2 3 4 5 6 7 8"] {
+        let error =
+            ReadLexer::read_line_with(&mut BufReader::new(input.as_bytes()), &options).unwrap_err();
+        assert_eq!(ReaderErrorKind::InvalidNumber, error.kind(), "{}", input);
+    }
+    for input in ["v 1 2 3", "v 1 2 3 4", "v 1 2 3 4 5 6", "v 1 2 3 4 5 6 7"] {
+        assert!(
+            ReadLexer::read_line_with(&mut BufReader::new(input.as_bytes()), &options).is_ok(),
+            "{}",
+            input
+        );
+    }
+}
+
+#[test]
+fn test_read_line_token_case_insensitive() {
+    let stream = std::io::Cursor::new("USEMTL token");
+    assert_eq!(
+        Entity::Usemtl {
+            name: "token".to_owned()
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_long_unknown_token() {
+    let stream = std::io::Cursor::new("unknown_token 1 2");
+    let error = ReadLexer::read_line(&mut BufReader::new(stream)).unwrap_err();
+    assert_eq!(ReaderErrorKind::UnknownToken, error.kind());
+    assert_eq!(
+        "line 1: unknown token \"unknown_token\" (\"unknown_token 1 2\")",
+        error.to_string()
+    );
+}
+
+#[test]
+fn test_read_line_line_texture() {
+    let stream = std::io::Cursor::new("l 1/1 2/-1 3");
+    assert_eq!(
+        Entity::Line {
+            vertices: vec![
+                LineVertex::new2(1, Some(1)),
+                LineVertex::new2(2, Some(-1)),
+                LineVertex::new(3),
+            ],
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+    let stream = std::io::Cursor::new("l 1/x 2");
+    assert_eq!(
+        ReaderErrorKind::InvalidNumber,
+        ReadLexer::read_line(&mut BufReader::new(stream))
+            .unwrap_err()
+            .kind()
+    );
+}
+
+#[test]
+fn test_read_line_render_attributes() {
+    for (line, entity) in [
+        ("BEVEL ON", Entity::Bevel { enabled: true }),
+        (
+            "c_interp Off",
+            Entity::ColorInterpolation { enabled: false },
+        ),
+        (
+            "d_interp on",
+            Entity::DissolveInterpolation { enabled: true },
+        ),
+        ("lod 0", Entity::LevelOfDetail { level: 0 }),
+        (
+            "shadow_obj shadow.obj",
+            Entity::ShadowObject {
+                name: "shadow.obj".to_owned(),
+            },
+        ),
+        (
+            "trace_obj trace.obj",
+            Entity::TraceObject {
+                name: "trace.obj".to_owned(),
+            },
+        ),
+    ] {
+        let stream = std::io::Cursor::new(line);
+        assert_eq!(
+            entity,
+            ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+        );
+    }
+}
+
+#[test]
+fn test_read_line_render_attributes_invalid() {
+    for (line, kind) in [
+        ("bevel", ReaderErrorKind::MissingField),
+        ("bevel yes", ReaderErrorKind::InvalidNumber),
+        ("lod", ReaderErrorKind::MissingField),
+        ("lod high", ReaderErrorKind::InvalidNumber),
+        ("shadow_obj", ReaderErrorKind::MissingField),
+    ] {
+        let stream = std::io::Cursor::new(line);
+        assert_eq!(
+            kind,
+            ReadLexer::read_line(&mut BufReader::new(stream))
+                .unwrap_err()
+                .kind()
+        );
+    }
+}
+
+#[test]
+fn test_read_line_trim_invalid() {
+    for (line, kind) in [
+        ("trim 0 1", ReaderErrorKind::MissingField),
+        ("hole 0", ReaderErrorKind::MissingField),
+        ("scrv 0 1 x", ReaderErrorKind::InvalidNumber),
+        ("trim a 1 1", ReaderErrorKind::InvalidNumber),
+        ("sp 1 x", ReaderErrorKind::InvalidNumber),
+    ] {
+        let stream = std::io::Cursor::new(line);
+        assert_eq!(
+            kind,
+            ReadLexer::read_line(&mut BufReader::new(stream))
+                .unwrap_err()
+                .kind()
+        );
+    }
+}
+
+#[test]
+fn test_read_line_parm_v() {
+    let stream = std::io::Cursor::new("parm V 0 1");
+    assert_eq!(
+        Entity::Parm {
+            direction: 'v',
+            values: vec![0.0, 1.0],
+        },
+        ReadLexer::read_line(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_parm_step_invalid() {
+    for (line, kind) in [
+        ("parm", ReaderErrorKind::MissingField),
+        ("parm w 0 1", ReaderErrorKind::InvalidNumber),
+        ("parm u 0 x", ReaderErrorKind::InvalidNumber),
+        ("step", ReaderErrorKind::MissingField),
+        ("step 1 x", ReaderErrorKind::InvalidNumber),
+    ] {
+        let stream = std::io::Cursor::new(line);
+        assert_eq!(
+            kind,
+            ReadLexer::read_line(&mut BufReader::new(stream))
+                .unwrap_err()
+                .kind()
+        );
+    }
+}
+
+#[test]
+fn test_read_line_with_comma_separators() {
+    let options = ReadOptions {
+        comma_separators: true,
+        ..Default::default()
+    };
+    let stream = std::io::Cursor::new("v 1.0, 2.0,3.0\n");
+    assert_eq!(
+        Entity::Vertex {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+            w: None,
+            color: None,
+        },
+        ReadLexer::read_line_with(&mut BufReader::new(stream), &options).unwrap()
+    );
+    let stream = std::io::Cursor::new("f 1/1,2/2,3/3");
+    assert_eq!(
+        Entity::Face {
+            vertices: vec![
+                FaceVertex::new2(1, Some(1), None),
+                FaceVertex::new2(2, Some(2), None),
+                FaceVertex::new2(3, Some(3), None),
+            ],
+        },
+        ReadLexer::read_line_with(&mut BufReader::new(stream), &options).unwrap()
+    );
+    let stream = std::io::Cursor::new("# a, b");
+    assert_eq!(
+        Entity::Comment {
+            content: "a, b".to_owned()
+        },
+        ReadLexer::read_line_with(&mut BufReader::new(stream), &options).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_comma_separators_keep_names() {
+    let options = ReadOptions {
+        comma_separators: true,
+        ..Default::default()
+    };
+    let expected = [
+        (
+            "mtllib my,file.mtl",
+            Entity::Mtllib {
+                name: "my,file.mtl".to_owned(),
+            },
+        ),
+        (
+            "usemtl a,b",
+            Entity::Usemtl {
+                name: "a,b".to_owned(),
+            },
+        ),
+        (
+            "o my,cube",
+            Entity::Object {
+                name: "my,cube".to_owned(),
+            },
+        ),
+        (
+            "p,1,2",
+            Entity::Point {
+                vertices: vec![1, 2],
+            },
+        ),
+    ];
+    for (input, entity) in expected {
+        assert_eq!(
+            entity,
+            ReadLexer::read_line_with(&mut BufReader::new(input.as_bytes()), &options).unwrap(),
+            "{}",
+            input
+        );
+    }
+}
+
+#[test]
+fn test_read_line_comma_separators_strict_by_default() {
+    let stream = std::io::Cursor::new("v 1.0, 2.0, 3.0");
+    assert_eq!(
+        ReaderErrorKind::InvalidNumber,
+        ReadLexer::read_line(&mut BufReader::new(stream))
+            .unwrap_err()
+            .kind()
+    );
+}
+
+#[test]
+fn test_read_line_with_decimal_comma() {
+    let options = ReadOptions {
+        decimal_comma: true,
+        ..Default::default()
+    };
+    let stream = std::io::Cursor::new("v 1,5 -2,25 3\n");
+    assert_eq!(
+        Entity::Vertex {
+            x: 1.5,
+            y: -2.25,
+            z: 3.0,
+            w: None,
+            color: None,
+        },
+        ReadLexer::read_line_with(&mut BufReader::new(stream), &options).unwrap()
+    );
+    let stream = std::io::Cursor::new("vt 0.5 0,25");
+    assert_eq!(
+        Entity::VertexTexture {
+            u: 0.5,
+            v: Some(0.25),
+            w: None,
+        },
+        ReadLexer::read_line_with(&mut BufReader::new(stream), &options).unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_decimal_comma_strict_by_default() {
+    let stream = std::io::Cursor::new("vn 0,5 0 1");
+    assert_eq!(
+        ReaderErrorKind::InvalidNumber,
+        ReadLexer::read_line(&mut BufReader::new(stream))
+            .unwrap_err()
+            .kind()
+    );
+}
+
+#[test]
+fn test_read_line_mixed_decimal_separators() {
+    let options = ReadOptions {
+        decimal_comma: true,
+        ..Default::default()
+    };
+    for options in [ReadOptions::default(), options] {
+        let stream = std::io::Cursor::new("v 1.000,5 0 0");
+        let error = ReadLexer::read_line_with(&mut BufReader::new(stream), &options).unwrap_err();
+        assert_eq!(ReaderErrorKind::InvalidNumber, error.kind());
+        assert_eq!(
+            "line 1: mixed decimal separators in v (\"v 1.000,5 0 0\")",
+            error.to_string()
+        );
+    }
+}
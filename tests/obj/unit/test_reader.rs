@@ -0,0 +1,545 @@
+extern crate wavefront_rs;
+use std::io::BufReader;
+use wavefront_rs::error::ReaderErrorKind;
+use wavefront_rs::obj::entity::*;
+use wavefront_rs::obj::read_lexer::ReadOptions;
+use wavefront_rs::obj::reader::*;
+
+#[test]
+fn test_read_all() {
+    let stream = std::io::Cursor::new("o token\nv 0.1 1.2 2.3\n\nf 1 1 1\n");
+    assert_eq!(
+        vec![
+            Entity::Object {
+                name: "token".to_owned()
+            },
+            Entity::Vertex {
+                x: 0.1,
+                y: 1.2,
+                z: 2.3,
+                w: None,
+                color: None,
+            },
+            Entity::Face {
+                vertices: vec![FaceVertex::new(1), FaceVertex::new(1), FaceVertex::new(1)],
+            },
+        ],
+        read_all(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_all_empty() {
+    let stream = std::io::Cursor::new("\n   \n");
+    assert!(read_all(&mut BufReader::new(stream)).unwrap().is_empty());
+}
+
+#[test]
+fn test_read_all_error_line() {
+    let stream = std::io::Cursor::new("o token\n\nv 0.1 x 2.3\n");
+    assert_eq!(3, read_all(&mut BufReader::new(stream)).unwrap_err().line());
+}
+
+#[test]
+fn test_entity_iterator() {
+    let stream = std::io::Cursor::new("o token\n\n   \nvn 0.1 1.2 2.3\n");
+    let mut iter = EntityIterator::new(BufReader::new(stream));
+    assert_eq!(
+        Entity::Object {
+            name: "token".to_owned()
+        },
+        iter.next().unwrap().unwrap()
+    );
+    assert_eq!(
+        Entity::VertexNormal {
+            x: 0.1,
+            y: 1.2,
+            z: 2.3,
+        },
+        iter.next().unwrap().unwrap()
+    );
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn test_entity_iterator_error() {
+    let stream = std::io::Cursor::new("o token\nvn 0.1 x 2.3\n");
+    let mut iter = EntityIterator::new(BufReader::new(stream));
+    assert!(iter.next().unwrap().is_ok());
+    assert_eq!(2, iter.next().unwrap().unwrap_err().line());
+}
+
+#[test]
+fn test_read_all_with_preserve_unknown() {
+    let stream = std::io::Cursor::new("o token\nvendor_ext 1\n");
+    let options = ReadOptions {
+        preserve_unknown: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        vec![
+            Entity::Object {
+                name: "token".to_owned()
+            },
+            Entity::Unknown {
+                raw: "vendor_ext 1".to_owned()
+            },
+        ],
+        read_all_with(&mut BufReader::new(stream), &options).unwrap()
+    );
+}
+
+#[test]
+fn test_read_all_with_skip_comments() {
+    let stream = std::io::Cursor::new(
+        "# header
+o token
+# note
+#
+",
+    );
+    let options = ReadOptions {
+        skip_comments: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        vec![Entity::Object {
+            name: "token".to_owned()
+        }],
+        read_all_with(&mut BufReader::new(stream), &options).unwrap()
+    );
+}
+
+fn vertex(x: f64, y: f64, z: f64) -> Entity {
+    Entity::Vertex {
+        x,
+        y,
+        z,
+        w: None,
+        color: None,
+    }
+}
+
+#[test]
+fn test_read_all_with_warnings_lenient_vertices() {
+    let stream = std::io::Cursor::new("v 1 2\nv 1 2 3\n\nv 4\n");
+    let options = ReadOptions {
+        lenient_vertices: true,
+        ..Default::default()
+    };
+    let (entities, warnings) =
+        read_all_with_warnings(&mut BufReader::new(stream), &options).unwrap();
+    assert_eq!(
+        vec![
+            vertex(1.0, 2.0, 0.0),
+            vertex(1.0, 2.0, 3.0),
+            vertex(4.0, 0.0, 0.0)
+        ],
+        entities
+    );
+    assert_eq!(
+        vec![1, 4],
+        warnings.iter().map(|x| x.line()).collect::<Vec<_>>()
+    );
+    assert_eq!(ReaderErrorKind::MissingField, warnings[0].kind());
+    assert_eq!("v 1 2", warnings[0].raw());
+}
+
+#[test]
+fn test_read_all_strict_vertices_by_default() {
+    let stream = std::io::Cursor::new("v 1 2 3\nv 1 2\n");
+    let error = read_all(&mut BufReader::new(stream)).unwrap_err();
+    assert_eq!(ReaderErrorKind::MissingField, error.kind());
+    assert_eq!(2, error.line());
+}
+
+#[test]
+fn test_read_all_lenient_vertices_still_require_x() {
+    let stream = std::io::Cursor::new("v\n");
+    let options = ReadOptions {
+        lenient_vertices: true,
+        ..Default::default()
+    };
+    let error = read_all_with(&mut BufReader::new(stream), &options).unwrap_err();
+    assert_eq!(ReaderErrorKind::MissingField, error.kind());
+}
+
+#[test]
+fn test_read_all_discards_extra_values_by_default() {
+    let stream = std::io::Cursor::new("vn 0 0 1 0\nvt 0.5 0.25 0 1 up\n");
+    assert_eq!(
+        vec![
+            Entity::VertexNormal {
+                x: 0.0,
+                y: 0.0,
+                z: 1.0
+            },
+            Entity::VertexTexture {
+                u: 0.5,
+                v: Some(0.25),
+                w: Some(0.0)
+            },
+        ],
+        read_all(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_all_with_warnings_extra_values() {
+    let stream = std::io::Cursor::new("vn 0 0 1 0\nvn 0 1 0\nvt 0.5 0.25 0 1 1\n");
+    let (entities, warnings) =
+        read_all_with_warnings(&mut BufReader::new(stream), &ReadOptions::default()).unwrap();
+    assert_eq!(3, entities.len());
+    assert_eq!(
+        vec![1, 3],
+        warnings.iter().map(|x| x.line()).collect::<Vec<_>>()
+    );
+    assert_eq!(ReaderErrorKind::InvalidNumber, warnings[0].kind());
+    assert_eq!("vt 0.5 0.25 0 1 1", warnings[1].raw());
+}
+
+#[test]
+fn test_read_all_with_warnings_extra_vertex_values() {
+    let stream = std::io::Cursor::new("v 1 2 3 4 5\nv 1 2 3 4 5 6 7 8\nv 1 2 3 4 5 6\n");
+    let (entities, warnings) =
+        read_all_with_warnings(&mut BufReader::new(stream), &ReadOptions::default()).unwrap();
+    assert_eq!(
+        ["v 1 2 3 4", "v 1 2 3 4 5 6 7", "v 1 2 3 4 5 6"]
+            .iter()
+            .map(|x| x.parse::<Entity>().unwrap())
+            .collect::<Vec<_>>(),
+        entities
+    );
+    assert_eq!(
+        vec![1, 2],
+        warnings.iter().map(|x| x.line()).collect::<Vec<_>>()
+    );
+    assert_eq!("v 1 2 3 4 5", warnings[0].raw());
+}
+
+#[test]
+fn test_read_all_reject_extra_values() {
+    let options = ReadOptions {
+        reject_extra_values: true,
+        ..Default::default()
+    };
+    for input in [
+        "vn 0 0 1 0",
+        "vt 0.5 0.5 0 1",
+        "vp 0.5 0.5 1 1",
+        "vn 0 0 1 up",
+    ] {
+        let error = read_all_with(&mut BufReader::new(input.as_bytes()), &options).unwrap_err();
+        assert_eq!(ReaderErrorKind::InvalidNumber, error.kind(), "{}", input);
+    }
+}
+
+#[test]
+fn test_reader_warnings() {
+    let stream = std::io::Cursor::new("v 1 2 3\nv 1 2\n");
+    let options = ReadOptions {
+        lenient_vertices: true,
+        ..Default::default()
+    };
+    let mut reader = Reader::with_options(BufReader::new(stream), options);
+    reader.next().unwrap().unwrap();
+    assert!(reader.warnings().is_empty());
+    reader.next().unwrap().unwrap();
+    assert_eq!(2, reader.warnings()[0].line());
+}
+
+#[test]
+fn test_read_all_keeps_comments_by_default() {
+    let stream = std::io::Cursor::new(
+        "# header
+o token
+",
+    );
+    assert_eq!(2, read_all(&mut BufReader::new(stream)).unwrap().len());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_read_all() {
+    let input = "o token\n\nv 0.1 1.2 2.3\r\nvn 0.1 1.2 2.3\nf 1 2 3\n";
+    assert_eq!(
+        read_all(&mut BufReader::new(std::io::Cursor::new(input))).unwrap(),
+        par_read_all(input).unwrap()
+    );
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_read_all_empty() {
+    assert!(par_read_all("\n   \n").unwrap().is_empty());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_read_all_first_error_line() {
+    assert_eq!(
+        3,
+        par_read_all("o token\n\nv 0.1 x 2.3\nunknown\n")
+            .unwrap_err()
+            .line()
+    );
+}
+
+#[test]
+fn test_read_all_continuation() {
+    let stream = std::io::Cursor::new("l 1 \\\n2 \\\n3\n\nv 0.1 x 2.3\n");
+    let mut iter = EntityIterator::new(BufReader::new(stream));
+    assert_eq!(
+        Entity::Line {
+            vertices: vec![LineVertex::new(1), LineVertex::new(2), LineVertex::new(3)],
+        },
+        iter.next().unwrap().unwrap()
+    );
+    assert_eq!(5, iter.next().unwrap().unwrap_err().line());
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn test_read_all_crlf_continuation() {
+    let stream = std::io::Cursor::new("l 1 \\\r\n2\r\no token\r\n");
+    assert_eq!(
+        vec![
+            Entity::Line {
+                vertices: vec![LineVertex::new(1), LineVertex::new(2)],
+            },
+            Entity::Object {
+                name: "token".to_owned()
+            },
+        ],
+        read_all(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[test]
+fn test_read_all_continuation_error_line() {
+    let stream = std::io::Cursor::new("o token\nf 1 \\\n2 \\\nx\n");
+    assert_eq!(2, read_all(&mut BufReader::new(stream)).unwrap_err().line());
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_read_all_continuation() {
+    let input = "l 1 \\\n2 \\\n3\no token\nf 1 \\\n2 \\\nx\n";
+    let error = par_read_all(input).unwrap_err();
+    assert_eq!(5, error.line());
+    assert_eq!(
+        vec![Entity::Line {
+            vertices: vec![LineVertex::new(1), LineVertex::new(2), LineVertex::new(3)],
+        }],
+        par_read_all("l 1 \\\n2 \\\n3\n").unwrap()
+    );
+}
+
+#[test]
+fn test_read_all_resolved() {
+    let stream = std::io::Cursor::new(
+        "v 0 0 0\nv 1 0 0\nv 0 1 0\nvn 0 0 1\nvt 0 0\nvt 1 0\n\
+         f -3/-1/-2 -2/-1/-1 -1/1/2\nl -1 -2\np -3 2\nv 1 1 0\nf 1 -1 3\n\
+         vp 0.1\nvp 0.2\ncurv 0.0 1.0 -4 -1\ncurv2 -2 -1\nsurf 0.0 1.0 0.0 1.0 -1 -2\n",
+    );
+    let entities = read_all_resolved(&mut BufReader::new(stream)).unwrap();
+    assert_eq!(
+        Entity::Face {
+            vertices: vec![
+                FaceVertex::new2(1, Some(1), Some(1)),
+                FaceVertex::new2(2, Some(1), Some(2)),
+                FaceVertex::new2(3, Some(1), Some(2)),
+            ],
+        },
+        entities[6]
+    );
+    assert_eq!(
+        Entity::Line {
+            vertices: vec![LineVertex::new(3), LineVertex::new(2)],
+        },
+        entities[7]
+    );
+    assert_eq!(
+        Entity::Point {
+            vertices: vec![1, 2],
+        },
+        entities[8]
+    );
+    assert_eq!(
+        Entity::Face {
+            vertices: vec![FaceVertex::new(1), FaceVertex::new(4), FaceVertex::new(3)],
+        },
+        entities[10]
+    );
+    assert_eq!(
+        Entity::Curve {
+            u_start: 0.0,
+            u_end: 1.0,
+            vertices: vec![1, 4],
+        },
+        entities[13]
+    );
+    assert_eq!(
+        Entity::Curve2D {
+            vertices: vec![1, 2],
+        },
+        entities[14]
+    );
+    assert_eq!(
+        Entity::Surface {
+            s_start: 0.0,
+            s_end: 1.0,
+            t_start: 0.0,
+            t_end: 1.0,
+            vertices: vec![FaceVertex::new(4), FaceVertex::new(3)],
+        },
+        entities[15]
+    );
+}
+
+#[test]
+fn test_read_all_resolved_special_point() {
+    let stream = std::io::Cursor::new("vp 0.1\nvp 0.2\nsp -1 1\n");
+    assert_eq!(
+        Entity::SpecialPoint {
+            vertices: vec![2, 1],
+        },
+        read_all_resolved(&mut BufReader::new(stream)).unwrap()[2]
+    );
+}
+
+#[test]
+fn test_read_all_resolved_error_line() {
+    let stream = std::io::Cursor::new("v 0 0 0\nf -1 x\n");
+    assert_eq!(
+        2,
+        read_all_resolved(&mut BufReader::new(stream))
+            .unwrap_err()
+            .line()
+    );
+}
+
+#[test]
+fn test_read_all_validate_face_indices() {
+    let options = ReadOptions {
+        validate_face_indices: true,
+        ..Default::default()
+    };
+    let stream = std::io::Cursor::new("v 0 0 0\nv 1 0 0\nvn 0 0 1\nf 1/1 2/1 -1/-1\n");
+    assert_eq!(
+        4,
+        read_all_with(&mut BufReader::new(stream), &options)
+            .unwrap()
+            .len()
+    );
+    for (input, line, message) in &[
+        (
+            "v 0 0 0\nf 1 2 1\nv 1 0 0\n",
+            2,
+            "vertex index 2 of f is out of range",
+        ),
+        ("v 0 0 0\n\nf 0\n", 3, "vertex index 0 of f is out of range"),
+        ("v 0 0 0\nf -2\n", 2, "vertex index -2 of f is out of range"),
+        ("v 0 0 0\nf 1/1\n", 2, "normal index 1 of f is out of range"),
+        (
+            "v 0 0 0\nvn 0 0 1\nf 1/1/1\n",
+            3,
+            "texture index 1 of f is out of range",
+        ),
+    ] {
+        let stream = std::io::Cursor::new(input);
+        let error = read_all_with(&mut BufReader::new(stream), &options).unwrap_err();
+        assert_eq!(ReaderErrorKind::IndexOutOfRange, error.kind());
+        assert_eq!(*line, error.line());
+        assert!(error.to_string().contains(message));
+    }
+}
+
+#[test]
+fn test_read_all_face_indices_not_validated_by_default() {
+    let stream = std::io::Cursor::new("f 1 2 3\n");
+    assert_eq!(1, read_all(&mut BufReader::new(stream)).unwrap().len());
+}
+
+#[test]
+fn test_reader() {
+    let stream = std::io::Cursor::new("v 0 0 0\nv 1 0 0\nvt 0 0\nf -2/-1 -1/-1\nvn 0 0 1\n");
+    let mut reader = Reader::new(BufReader::new(stream));
+    assert_eq!(0, reader.vertices());
+    reader.next().unwrap().unwrap();
+    reader.next().unwrap().unwrap();
+    assert_eq!(2, reader.vertices());
+    assert_eq!(0, reader.textures());
+    reader.next().unwrap().unwrap();
+    assert_eq!(1, reader.textures());
+    assert_eq!(
+        Entity::Face {
+            vertices: vec![
+                FaceVertex::new2(-2, Some(-1), None),
+                FaceVertex::new2(-1, Some(-1), None),
+            ],
+        },
+        reader.next().unwrap().unwrap()
+    );
+    reader.next().unwrap().unwrap();
+    assert_eq!(1, reader.normals());
+    assert_eq!(0, reader.parameters());
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn test_reader_resolve_relative() {
+    let stream = std::io::Cursor::new("v 0 0 0\nv 1 0 0\nvn 0 0 1\nl -2 -1\nf -1/-1 1/1\n");
+    let entities = Reader::new(BufReader::new(stream))
+        .resolve_relative(true)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        vec![
+            Entity::Line {
+                vertices: vec![LineVertex::new(1), LineVertex::new(2)],
+            },
+            Entity::Face {
+                vertices: vec![
+                    FaceVertex::new2(2, Some(1), None),
+                    FaceVertex::new2(1, Some(1), None),
+                ],
+            },
+        ],
+        entities[3..]
+    );
+}
+
+#[test]
+fn test_reader_error_line() {
+    let stream = std::io::Cursor::new("v 0 0 0\n\nv x 0 0\n");
+    let mut reader = Reader::with_options(BufReader::new(stream), ReadOptions::default());
+    reader.next().unwrap().unwrap();
+    assert_eq!(3, reader.next().unwrap().unwrap_err().line());
+    assert_eq!(1, reader.vertices());
+}
+
+#[test]
+fn test_read_all_bom() {
+    let stream = std::io::Cursor::new("\u{FEFF}# comment\no \u{FEFF}token\n");
+    assert_eq!(
+        vec![
+            Entity::Comment {
+                content: "comment".to_owned()
+            },
+            Entity::Object {
+                name: "\u{FEFF}token".to_owned()
+            },
+        ],
+        read_all(&mut BufReader::new(stream)).unwrap()
+    );
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn test_par_read_all_bom() {
+    assert_eq!(
+        vec![Entity::Point { vertices: vec![1] }],
+        par_read_all("\u{FEFF}p 1\n").unwrap()
+    );
+}
@@ -0,0 +1,55 @@
+extern crate wavefront_rs;
+use std::io::BufReader;
+use wavefront_rs::obj::entity::*;
+use wavefront_rs::obj::read_lexer::ReadOptions;
+use wavefront_rs::obj::reader::read_all_with;
+use wavefront_rs::obj::stats::*;
+
+fn entities() -> Vec<Entity> {
+    let stream = std::io::Cursor::new(
+        "# cube\no cube\ng side\nv 0 0 0\nv 1 0 0\nv 0 1 0\nvn 0 0 1\nvt 0 0\n\
+         f 1 2 3\nf 3 2 1\nl 1 2\np 1\nvendor_ext 1\n",
+    );
+    let options = ReadOptions {
+        preserve_unknown: true,
+        ..Default::default()
+    };
+    read_all_with(&mut BufReader::new(stream), &options).unwrap()
+}
+
+#[test]
+fn test_count_by_token() {
+    let entities = entities();
+    let counts = count_by_token(&entities);
+    assert_eq!(Some(&3), counts.get("v"));
+    assert_eq!(Some(&2), counts.get("f"));
+    assert_eq!(Some(&1), counts.get("vn"));
+    assert_eq!(Some(&1), counts.get("#"));
+    assert_eq!(Some(&1), counts.get("vendor_ext"));
+    assert_eq!(None, counts.get("vp"));
+    assert_eq!(entities.len(), counts.values().sum::<usize>());
+}
+
+#[test]
+fn test_count_by_token_empty() {
+    assert!(count_by_token(&[]).is_empty());
+}
+
+#[test]
+fn test_entity_stats() {
+    assert_eq!(
+        EntityStats {
+            vertices: 3,
+            normals: 1,
+            textures: 1,
+            parameters: 0,
+            faces: 2,
+            lines: 1,
+            points: 1,
+            objects: 1,
+            groups: 1,
+            comments: 1,
+        },
+        EntityStats::from_entities(&entities())
+    );
+}
@@ -10,7 +10,7 @@ fn test_read_line_comment() {
             exists.set(true);
         }
     }
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -21,29 +21,29 @@ fn test_read_line_object() {
             exists.set(true);
         }
     }
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
 fn test_read_line_group() {
     let exists = std::cell::Cell::new(false);
-    if let Ok(Entity::Group { name }) = StringLexer::read_line("g token") {
-        if name == "token" {
+    if let Ok(Entity::Group { names }) = StringLexer::read_line("g token") {
+        if names == vec!["token"] {
             exists.set(true);
         }
     }
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
 fn test_read_line_smoothing_group() {
     let exists = std::cell::Cell::new(false);
-    if let Ok(Entity::SmoothingGroup { name }) = StringLexer::read_line("s token") {
-        if name == "token" {
+    if let Ok(Entity::SmoothingGroup { group }) = StringLexer::read_line("s 1") {
+        if group == SmoothingGroup::Group(1) {
             exists.set(true);
         }
     }
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -54,7 +54,7 @@ fn test_read_line_merging_group() {
             exists.set(true);
         }
     }
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -65,7 +65,7 @@ fn test_read_line_mtllib() {
             exists.set(true);
         }
     }
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -76,7 +76,7 @@ fn test_read_line_usemtl() {
             exists.set(true);
         }
     }
-    assert_eq!(true, exists.take());
+    assert!(exists.take());
 }
 
 #[test]
@@ -87,6 +87,7 @@ fn test_read_line_vertex_xyzw() {
             y: 1.2f64,
             z: 2.3f64,
             w: Some(3.4f64),
+            color: None,
         },
         StringLexer::read_line("v 0.1 1.2 2.3 3.4").unwrap()
     );
@@ -100,6 +101,7 @@ fn test_read_line_vertex_xyz() {
             y: 1.2f64,
             z: 2.3f64,
             w: None,
+            color: None,
         },
         StringLexer::read_line("v 0.1 1.2 2.3").unwrap()
     );
@@ -265,7 +267,13 @@ fn test_read_line_face_v() {
 fn test_read_line_line() {
     assert_eq!(
         Entity::Line {
-            vertices: vec!(0, 1, 2, 3, 4)
+            vertices: vec![
+                LineVertex::new(0),
+                LineVertex::new(1),
+                LineVertex::new(2),
+                LineVertex::new(3),
+                LineVertex::new(4)
+            ]
         },
         StringLexer::read_line("l 0 1 2 3 4").unwrap()
     );
@@ -280,3 +288,144 @@ fn test_read_line_point() {
         StringLexer::read_line("p 0 1 2 3 4").unwrap()
     );
 }
+
+#[test]
+fn test_read_line_curve() {
+    assert_eq!(
+        Entity::Curve {
+            u_start: 0.5,
+            u_end: 1.5,
+            vertices: vec![1, -2, 3, 4],
+        },
+        StringLexer::read_line("curv 0.5 1.5 1 -2 3 4").unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_curve2d() {
+    assert_eq!(
+        Entity::Curve2D {
+            vertices: vec![1, 2, -3, 4],
+        },
+        StringLexer::read_line("curv2 1 2 -3 4").unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_surface() {
+    assert_eq!(
+        Entity::Surface {
+            s_start: 0.5,
+            s_end: 1.5,
+            t_start: 2.5,
+            t_end: 3.5,
+            vertices: vec![
+                FaceVertex::new2(1, Some(2), Some(3)),
+                FaceVertex::new2(4, None, Some(5)),
+                FaceVertex::new2(-6, None, None),
+            ],
+        },
+        StringLexer::read_line("surf 0.5 1.5 2.5 3.5 1/2/3 4//5 -6").unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_curve_surface_type_rational() {
+    assert_eq!(
+        Entity::CurveSurfaceType {
+            rational: true,
+            kind: "bspline".to_owned(),
+        },
+        StringLexer::read_line("cstype rat bspline").unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_curve_surface_type() {
+    assert_eq!(
+        Entity::CurveSurfaceType {
+            rational: false,
+            kind: "bezier".to_owned(),
+        },
+        StringLexer::read_line("cstype bezier").unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_degree_u() {
+    assert_eq!(
+        Entity::Degree { u: 3, v: None },
+        StringLexer::read_line("deg 3").unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_degree_uv() {
+    assert_eq!(
+        Entity::Degree { u: 3, v: Some(2) },
+        StringLexer::read_line("deg 3 2").unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_end() {
+    assert_eq!(Entity::End, StringLexer::read_line("end").unwrap());
+}
+
+#[test]
+fn test_read_line_maplib() {
+    assert_eq!(
+        Entity::Maplib {
+            name: "token".to_owned(),
+        },
+        StringLexer::read_line("maplib token").unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_usemap() {
+    assert_eq!(
+        Entity::Usemap {
+            name: "token".to_owned(),
+        },
+        StringLexer::read_line("usemap token").unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_inline_comment() {
+    assert_eq!(
+        Entity::Face {
+            vertices: vec![FaceVertex::new(1), FaceVertex::new(2), FaceVertex::new(3)],
+        },
+        StringLexer::read_line("f 1 2 3 # triangle").unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_vertex_color() {
+    assert_eq!(
+        Entity::Vertex {
+            x: 0.1,
+            y: 1.2,
+            z: 2.3,
+            w: None,
+            color: Some([1.0, 0.5, 0.0]),
+        },
+        StringLexer::read_line("v 0.1 1.2 2.3 1 0.5 0").unwrap()
+    );
+}
+
+#[test]
+fn test_read_line_vertex_w_color() {
+    assert_eq!(
+        Entity::Vertex {
+            x: 0.1,
+            y: 1.2,
+            z: 2.3,
+            w: Some(3.4),
+            color: Some([1.0, 0.5, 0.0]),
+        },
+        StringLexer::read_line("v 0.1 1.2 2.3 3.4 1 0.5 0").unwrap()
+    );
+}
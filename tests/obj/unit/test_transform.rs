@@ -0,0 +1,97 @@
+extern crate wavefront_rs;
+use super::common::parse;
+use wavefront_rs::obj::entity::*;
+use wavefront_rs::obj::geometry::*;
+use wavefront_rs::obj::transform::*;
+
+#[test]
+fn test_scale() {
+    let mut entities = parse(&["v 1 2 3", "v -1 0 1 0.5", "vn 0 0 1", "f 1 2 1"]);
+    scale(&mut entities, [2.0, 3.0, -1.0]);
+    assert_eq!(
+        parse(&["v 2 6 -3", "v -2 0 -1 0.5", "vn 0 0 1", "f 1 2 1"]),
+        entities
+    );
+}
+
+#[test]
+fn test_scale_with_normals() {
+    let mut entities = parse(&["v 1 1 1", "vn 1 1 0", "vn 0 0 0"]);
+    scale_with_normals(&mut entities, [2.0, 1.0, 1.0]);
+    assert_eq!(
+        vec![
+            Entity::Vertex {
+                x: 2.0,
+                y: 1.0,
+                z: 1.0,
+                w: None,
+                color: None,
+            },
+            Entity::VertexNormal {
+                x: 0.5 / (1.25f64).sqrt(),
+                y: 1.0 / (1.25f64).sqrt(),
+                z: 0.0,
+            },
+            Entity::VertexNormal {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+        ],
+        entities
+    );
+}
+
+#[test]
+fn test_translate() {
+    let mut entities = parse(&["v 1 2 3", "vn 0 0 1", "v 0 0 0 1 0.5 0.5 0.5"]);
+    translate(&mut entities, [1.0, -2.0, 0.5]);
+    assert_eq!(
+        parse(&["v 2 0 3.5", "vn 0 0 1", "v 1 -2 0.5 1 0.5 0.5 0.5"]),
+        entities
+    );
+}
+
+#[test]
+fn test_normalize_to_unit_cube() {
+    let mut entities = parse(&["v 2 0 1", "v 6 2 3", "vn 0 1 0"]);
+    normalize_to_unit_cube(&mut entities);
+    assert_eq!(
+        parse(&["v -0.5 -0.25 -0.25", "v 0.5 0.25 0.25", "vn 0 1 0"]),
+        entities
+    );
+    assert_eq!(
+        Some(BoundingBox {
+            min: [-0.5, -0.25, -0.25],
+            max: [0.5, 0.25, 0.25],
+        }),
+        bounding_box(&entities)
+    );
+}
+
+#[test]
+fn test_normalize_to_unit_cube_degenerate() {
+    let mut entities = parse(&["v 1 2 3", "v 1 2 3"]);
+    normalize_to_unit_cube(&mut entities);
+    assert_eq!(parse(&["v 0 0 0", "v 0 0 0"]), entities);
+    let mut entities = parse(&["vn 0 1 0"]);
+    normalize_to_unit_cube(&mut entities);
+    assert_eq!(parse(&["vn 0 1 0"]), entities);
+}
+
+#[test]
+fn test_center() {
+    let mut entities = parse(&["v 2 0 1", "v 6 2 3", "vn 0 1 0"]);
+    let offset = center(&mut entities);
+    assert_eq!([-4.0, -1.0, -2.0], offset);
+    assert_eq!(parse(&["v -2 -1 -1", "v 2 1 1", "vn 0 1 0"]), entities);
+    translate(&mut entities, [-offset[0], -offset[1], -offset[2]]);
+    assert_eq!(parse(&["v 2 0 1", "v 6 2 3", "vn 0 1 0"]), entities);
+}
+
+#[test]
+fn test_center_empty() {
+    let mut entities = parse(&["vn 0 1 0"]);
+    assert_eq!([0.0; 3], center(&mut entities));
+    assert_eq!(parse(&["vn 0 1 0"]), entities);
+}
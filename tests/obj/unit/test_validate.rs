@@ -0,0 +1,97 @@
+extern crate wavefront_rs;
+use super::common::parse;
+use wavefront_rs::error::{ValidationError, ValidationErrorKind};
+use wavefront_rs::obj::validate::*;
+
+#[test]
+fn test_validate_valid() {
+    let entities = parse(&[
+        "v 0 0 0",
+        "v 1 0 0",
+        "v 0 1 0",
+        "vn 0 0 1",
+        "vt 0 0",
+        "f 1/1/1 2/1/1 3/1/1",
+        "f -3 -2 -1",
+        "l 1 2",
+        "p 3",
+    ]);
+    assert_eq!(Vec::<ValidationError>::new(), validate(&entities));
+}
+
+#[test]
+fn test_validate_empty() {
+    assert!(validate(&[]).is_empty());
+}
+
+#[test]
+fn test_validate_face_vertex_out_of_range() {
+    let entities = parse(&["v 0 0 0", "v 1 0 0", "v 0 1 0", "f 1 2 4"]);
+    assert_eq!(
+        vec![ValidationError::new(ValidationErrorKind::Vertex, 3, 4)],
+        validate(&entities)
+    );
+}
+
+#[test]
+fn test_validate_face_zero_index() {
+    let entities = parse(&["v 0 0 0", "v 1 0 0", "v 0 1 0", "f 0 1 2"]);
+    assert_eq!(
+        vec![ValidationError::new(ValidationErrorKind::Vertex, 3, 0)],
+        validate(&entities)
+    );
+}
+
+#[test]
+fn test_validate_face_normal_and_texture_out_of_range() {
+    let entities = parse(&["v 0 0 0", "vn 0 0 1", "vt 0 0", "f 1/2/3"]);
+    assert_eq!(
+        vec![
+            ValidationError::new(ValidationErrorKind::Normal, 3, 2),
+            ValidationError::new(ValidationErrorKind::Texture, 3, 3),
+        ],
+        validate(&entities)
+    );
+}
+
+#[test]
+fn test_validate_negative_index_resolved_against_seen() {
+    let entities = parse(&["v 0 0 0", "v 1 0 0", "f -1 -2 -3", "v 0 1 0"]);
+    assert_eq!(
+        vec![ValidationError::new(ValidationErrorKind::Vertex, 2, -3)],
+        validate(&entities)
+    );
+}
+
+#[test]
+fn test_validate_line_and_point_out_of_range() {
+    let entities = parse(&["v 0 0 0", "l 1 2", "p 5"]);
+    assert_eq!(
+        vec![
+            ValidationError::new(ValidationErrorKind::Vertex, 1, 2),
+            ValidationError::new(ValidationErrorKind::Vertex, 2, 5),
+        ],
+        validate(&entities)
+    );
+}
+
+#[test]
+fn test_validate_line_texture_out_of_range() {
+    let entities = parse(&["v 0 0 0", "vt 0 0", "l 1/1 1/2"]);
+    assert_eq!(
+        vec![ValidationError::new(ValidationErrorKind::Texture, 2, 2)],
+        validate(&entities)
+    );
+}
+
+#[test]
+fn test_validation_error_display() {
+    let error = ValidationError::new(ValidationErrorKind::Normal, 7, -2);
+    assert_eq!(7, error.entity());
+    assert_eq!(-2, error.index());
+    assert_eq!(ValidationErrorKind::Normal, error.kind());
+    assert_eq!(
+        "entity 7: normal index -2 is out of range",
+        error.to_string()
+    );
+}
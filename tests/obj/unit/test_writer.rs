@@ -1,16 +1,15 @@
 extern crate wavefront_rs;
-use std::io::BufWriter;
 use wavefront_rs::obj::entity::*;
-use wavefront_rs::obj::format_writer::FormatWriter;
+use wavefront_rs::obj::format_writer::*;
 
 #[test]
 fn test_write_comment() {
     let entity = Entity::Comment {
         content: "token".to_owned(),
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("# token", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("# token", String::from_utf8(result).unwrap());
 }
 
 #[test]
@@ -18,29 +17,57 @@ fn test_write_object() {
     let entity = Entity::Object {
         name: "token".to_owned(),
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("o token", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("o token", String::from_utf8(result).unwrap());
 }
 
 #[test]
 fn test_write_group() {
     let entity = Entity::Group {
-        name: "token".to_owned(),
+        names: vec!["token".to_owned()],
+    };
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("g token", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_group_names() {
+    let entity = Entity::Group {
+        names: vec!["a".to_owned(), "b".to_owned(), "c".to_owned()],
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("g token", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("g a b c", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_group_without_names() {
+    let entity = Entity::Group { names: vec![] };
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("g", String::from_utf8(result).unwrap());
 }
 
 #[test]
 fn test_write_smoothing_group() {
     let entity = Entity::SmoothingGroup {
-        name: "token".to_owned(),
+        group: SmoothingGroup::Group(1),
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("s token", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("s 1", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_smoothing_group_off() {
+    let entity = Entity::SmoothingGroup {
+        group: SmoothingGroup::Off,
+    };
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("s off", String::from_utf8(result).unwrap());
 }
 
 #[test]
@@ -48,9 +75,9 @@ fn test_write_merging_group() {
     let entity = Entity::MergingGroup {
         name: "token".to_owned(),
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("mg token", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("mg token", String::from_utf8(result).unwrap());
 }
 
 #[test]
@@ -58,9 +85,38 @@ fn test_write_mtllib() {
     let entity = Entity::Mtllib {
         name: "token".to_owned(),
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("mtllib token", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("mtllib token", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_mtllib_with_spaces() {
+    let entity = Entity::Mtllib {
+        name: " my  materials.mtl ".to_owned(),
+    };
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!(
+        "mtllib my  materials.mtl",
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn test_write_object_without_name_round_trip() {
+    let entity = "o".parse::<Entity>().unwrap();
+    assert_eq!(
+        Entity::Object {
+            name: String::new()
+        },
+        entity
+    );
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    let result = String::from_utf8(result).unwrap();
+    assert_eq!("o", result);
+    assert_eq!(entity, result.parse::<Entity>().unwrap());
 }
 
 #[test]
@@ -68,9 +124,9 @@ fn test_write_usemtl() {
     let entity = Entity::Usemtl {
         name: "token".to_owned(),
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("usemtl token", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("usemtl token", String::from_utf8(result).unwrap());
 }
 
 #[test]
@@ -80,10 +136,11 @@ fn test_write_vertex_xyzw() {
         y: 1f64,
         z: 2f64,
         w: Some(3f64),
+        color: None,
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("v 0 1 2 3", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("v 0 1 2 3", String::from_utf8(result).unwrap());
 }
 
 #[test]
@@ -93,10 +150,11 @@ fn test_write_vertex_xyz() {
         y: 1f64,
         z: 2f64,
         w: None,
+        color: None,
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("v 0 1 2", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("v 0 1 2", String::from_utf8(result).unwrap());
 }
 
 #[test]
@@ -106,9 +164,9 @@ fn test_write_vertex_normal() {
         y: 1f64,
         z: 2f64,
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("vn 0 1 2", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("vn 0 1 2", String::from_utf8(result).unwrap());
 }
 
 #[test]
@@ -118,9 +176,9 @@ fn test_write_vertex_texture_u() {
         v: None,
         w: None,
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("vt 0.1", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("vt 0.1", String::from_utf8(result).unwrap());
 }
 
 #[test]
@@ -130,9 +188,9 @@ fn test_write_vertex_texture_uv() {
         v: Some(1.2f64),
         w: None,
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("vt 0.1 1.2", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("vt 0.1 1.2", String::from_utf8(result).unwrap());
 }
 
 #[test]
@@ -142,9 +200,9 @@ fn test_write_vertex_texture_uvw() {
         v: Some(1.2f64),
         w: Some(2.3f64),
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("vt 0.1 1.2 2.3", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("vt 0.1 1.2 2.3", String::from_utf8(result).unwrap());
 }
 
 #[test]
@@ -154,9 +212,9 @@ fn test_write_vertex_parameter_uvw() {
         v: Some(1.2f64),
         w: Some(2.3f64),
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("vp 0.1 1.2 2.3", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("vp 0.1 1.2 2.3", String::from_utf8(result).unwrap());
 }
 
 #[test]
@@ -166,9 +224,9 @@ fn test_write_vertex_parameter_uv() {
         v: Some(1.2f64),
         w: None,
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("vp 0.1 1.2", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("vp 0.1 1.2", String::from_utf8(result).unwrap());
 }
 
 #[test]
@@ -178,9 +236,9 @@ fn test_write_vertex_parameter_u() {
         v: None,
         w: None,
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("vp 0.1", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("vp 0.1", String::from_utf8(result).unwrap());
 }
 
 #[test]
@@ -192,9 +250,9 @@ fn test_write_face_vnt_3() {
             FaceVertex::new2(6, Some(7), Some(8)),
         ],
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("f 0/1/2 3/4/5 6/7/8", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("f 0/1/2 3/4/5 6/7/8", String::from_utf8(result).unwrap());
 }
 
 #[test]
@@ -209,9 +267,12 @@ fn test_write_face_vnt_6() {
             FaceVertex::new2(15, Some(16), Some(17)),
         ],
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("f 0/1/2 3/4/5 6/7/8 9/10/11 12/13/14 15/16/17", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!(
+        "f 0/1/2 3/4/5 6/7/8 9/10/11 12/13/14 15/16/17",
+        String::from_utf8(result).unwrap()
+    );
 }
 
 #[test]
@@ -223,9 +284,9 @@ fn test_write_face_vt() {
             FaceVertex::new2(6, None, Some(8)),
         ],
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("f 0//2 3//5 6//8", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("f 0//2 3//5 6//8", String::from_utf8(result).unwrap());
 }
 
 #[test]
@@ -237,9 +298,9 @@ fn test_write_face_vn() {
             FaceVertex::new2(6, Some(7), None),
         ],
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("f 0/1 3/4 6/7", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("f 0/1 3/4 6/7", String::from_utf8(result).unwrap());
 }
 
 #[test]
@@ -251,19 +312,35 @@ fn test_write_face_v() {
             FaceVertex::new2(6, None, None),
         ],
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("f 0 3 6", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("f 0 3 6", String::from_utf8(result).unwrap());
 }
 
 #[test]
 fn test_write_line() {
     let entity = Entity::Line {
-        vertices: vec![0, 1, 2, 3, 4],
+        vertices: vec![
+            LineVertex::new(0),
+            LineVertex::new(1),
+            LineVertex::new(2),
+            LineVertex::new(3),
+            LineVertex::new(4),
+        ],
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("l 0 1 2 3 4", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("l 0 1 2 3 4", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_line_texture() {
+    let entity = Entity::Line {
+        vertices: vec![LineVertex::new2(1, Some(2)), LineVertex::new(3)],
+    };
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("l 1/2 3", String::from_utf8(result).unwrap());
 }
 
 #[test]
@@ -271,7 +348,456 @@ fn test_write_point() {
     let entity = Entity::Point {
         vertices: vec![0, 1, 2, 3, 4],
     };
-    let mut result = String::new();
-    FormatWriter::write(&mut BufWriter::new(unsafe { result.as_mut_vec() }), &entity);
-    assert_eq!("p 0 1 2 3 4", result);
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("p 0 1 2 3 4", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_curve() {
+    let entity = Entity::Curve {
+        u_start: 0.5,
+        u_end: 1.5,
+        vertices: vec![1, -2, 3, 4],
+    };
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("curv 0.5 1.5 1 -2 3 4", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_curve2d() {
+    let entity = Entity::Curve2D {
+        vertices: vec![1, 2, -3, 4],
+    };
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("curv2 1 2 -3 4", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_surface() {
+    let entity = Entity::Surface {
+        s_start: 0.5,
+        s_end: 1.5,
+        t_start: 2.5,
+        t_end: 3.5,
+        vertices: vec![
+            FaceVertex::new2(1, Some(2), Some(3)),
+            FaceVertex::new2(4, None, Some(5)),
+            FaceVertex::new2(-6, None, None),
+        ],
+    };
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!(
+        "surf 0.5 1.5 2.5 3.5 1/2/3 4//5 -6",
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn test_write_curve_surface_type_rational() {
+    let entity = Entity::CurveSurfaceType {
+        rational: true,
+        kind: "bspline".to_owned(),
+    };
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("cstype rat bspline", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_curve_surface_type() {
+    let entity = Entity::CurveSurfaceType {
+        rational: false,
+        kind: "bezier".to_owned(),
+    };
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("cstype bezier", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_degree_u() {
+    let entity = Entity::Degree { u: 3, v: None };
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("deg 3", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_degree_uv() {
+    let entity = Entity::Degree { u: 3, v: Some(2) };
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("deg 3 2", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_end() {
+    let entity = Entity::End;
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("end", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_unknown() {
+    let entity = Entity::Unknown {
+        raw: "vendor_ext  1 2 3".to_owned(),
+    };
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("vendor_ext  1 2 3", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_with_precision_vertex() {
+    let entity = Entity::Vertex {
+        x: 0.1,
+        y: 1.2,
+        z: 2.3,
+        w: None,
+        color: None,
+    };
+    let mut result = Vec::new();
+    FormatWriter::write_with_precision(&mut result, &entity, 6).unwrap();
+    assert_eq!(
+        "v 0.100000 1.200000 2.300000",
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn test_write_with_precision_vertex_texture() {
+    let entity = Entity::VertexTexture {
+        u: 0.12345,
+        v: Some(1.0),
+        w: None,
+    };
+    let mut result = Vec::new();
+    FormatWriter::write_with_precision(&mut result, &entity, 2).unwrap();
+    assert_eq!("vt 0.12 1.00", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_with_precision_face() {
+    let entity = Entity::Face {
+        vertices: vec![
+            FaceVertex::new2(1, Some(2), Some(3)),
+            FaceVertex::new2(4, Some(5), Some(6)),
+            FaceVertex::new2(7, Some(8), Some(9)),
+        ],
+    };
+    let mut result = Vec::new();
+    FormatWriter::write_with_precision(&mut result, &entity, 2).unwrap();
+    assert_eq!("f 1/2/3 4/5/6 7/8/9", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_with_default_options() {
+    let entity = Entity::Vertex {
+        x: 0.1,
+        y: 1.2,
+        z: 2.3,
+        w: None,
+        color: None,
+    };
+    let mut result = Vec::new();
+    FormatWriter::write_with(&mut result, &entity, &FormatOptions::default()).unwrap();
+    assert_eq!("v 0.1 1.2 2.3", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_with_line_ending_crlf() {
+    let entity = Entity::Object {
+        name: "token".to_owned(),
+    };
+    let options = FormatOptions {
+        line_ending: LineEnding::CrLf,
+        ..Default::default()
+    };
+    let mut result = Vec::new();
+    FormatWriter::write_with(&mut result, &entity, &options).unwrap();
+    assert_eq!("o token\r\n", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_with_always_emit_optional_vertex() {
+    let entity = Entity::Vertex {
+        x: 0.1,
+        y: 1.2,
+        z: 2.3,
+        w: None,
+        color: None,
+    };
+    let options = FormatOptions {
+        always_emit_optional: true,
+        ..Default::default()
+    };
+    let mut result = Vec::new();
+    FormatWriter::write_with(&mut result, &entity, &options).unwrap();
+    assert_eq!("v 0.1 1.2 2.3 1", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_with_always_emit_optional_vertex_texture() {
+    let entity = Entity::VertexTexture {
+        u: 0.1,
+        v: None,
+        w: None,
+    };
+    let options = FormatOptions {
+        float_precision: Some(1),
+        line_ending: LineEnding::Lf,
+        always_emit_optional: true,
+        column_width: None,
+    };
+    let mut result = Vec::new();
+    FormatWriter::write_with(&mut result, &entity, &options).unwrap();
+    assert_eq!("vt 0.1 0.0 0.0\n", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_with_always_emit_optional_vertex_parameter() {
+    let entity = Entity::VertexParameter {
+        u: 0.1,
+        v: Some(0.2),
+        w: None,
+    };
+    let options = FormatOptions {
+        always_emit_optional: true,
+        ..Default::default()
+    };
+    let mut result = Vec::new();
+    FormatWriter::write_with(&mut result, &entity, &options).unwrap();
+    assert_eq!("vp 0.1 0.2 1", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_with_column_width() {
+    let entities = vec![
+        Entity::Vertex {
+            x: 0.1,
+            y: -1.2,
+            z: 2.3,
+            w: None,
+            color: None,
+        },
+        Entity::Vertex {
+            x: -10.25,
+            y: 0.0,
+            z: 100.5,
+            w: None,
+            color: None,
+        },
+        Entity::Vertex {
+            x: 1.0,
+            y: 22.0,
+            z: -3.0,
+            w: None,
+            color: None,
+        },
+    ];
+    let options = FormatOptions {
+        float_precision: Some(2),
+        line_ending: LineEnding::Lf,
+        column_width: Some(7),
+        ..Default::default()
+    };
+    let mut result = Vec::new();
+    for e in &entities {
+        FormatWriter::write_with(&mut result, e, &options).unwrap();
+    }
+    assert_eq!(
+        "v    0.10   -1.20    2.30\n\
+         v  -10.25    0.00  100.50\n\
+         v    1.00   22.00   -3.00\n",
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn test_write_with_column_width_does_not_truncate() {
+    let entity = Entity::VertexNormal {
+        x: 123.456,
+        y: 0.5,
+        z: 1.0,
+    };
+    let options = FormatOptions {
+        column_width: Some(4),
+        ..Default::default()
+    };
+    let mut result = Vec::new();
+    FormatWriter::write_with(&mut result, &entity, &options).unwrap();
+    let result = String::from_utf8(result).unwrap();
+    assert_eq!("vn 123.456  0.5    1", result);
+    assert_eq!(entity, result.parse::<Entity>().unwrap());
+}
+
+#[test]
+fn test_write_line_round_trip() {
+    let entities = vec![
+        Entity::Vertex {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+            w: None,
+            color: None,
+        },
+        Entity::Vertex {
+            x: 4.0,
+            y: 5.0,
+            z: 6.0,
+            w: Some(0.5),
+            color: None,
+        },
+        Entity::Face {
+            vertices: vec![FaceVertex::new(1), FaceVertex::new(2), FaceVertex::new(1)],
+        },
+    ];
+    let mut result = Vec::new();
+    for e in &entities {
+        FormatWriter::write_line(&mut result, e).unwrap();
+    }
+    let result = String::from_utf8(result).unwrap();
+    assert_eq!("v 1 2 3\nv 4 5 6 0.5\nf 1 2 1\n", result);
+    let reparsed: Vec<Entity> = result.lines().map(|x| x.parse().unwrap()).collect();
+    assert_eq!(entities, reparsed);
+}
+
+#[test]
+fn test_write_maplib() {
+    let entity = Entity::Maplib {
+        name: "token".to_owned(),
+    };
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("maplib token", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_usemap() {
+    let entity = Entity::Usemap {
+        name: "token".to_owned(),
+    };
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("usemap token", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_comment_empty() {
+    let entity = Entity::Comment {
+        content: "".to_owned(),
+    };
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("#", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_vertex_color() {
+    let entity = Entity::Vertex {
+        x: 0.1,
+        y: 1.2,
+        z: 2.3,
+        w: None,
+        color: Some([1.0, 0.5, 0.0]),
+    };
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!("v 0.1 1.2 2.3 1 0.5 0", String::from_utf8(result).unwrap());
+}
+
+#[test]
+fn test_write_vertex_w_color() {
+    let entity = Entity::Vertex {
+        x: 0.1,
+        y: 1.2,
+        z: 2.3,
+        w: Some(3.4),
+        color: Some([1.0, 0.5, 0.0]),
+    };
+    let mut result = Vec::new();
+    FormatWriter::write(&mut result, &entity).unwrap();
+    assert_eq!(
+        "v 0.1 1.2 2.3 3.4 1 0.5 0",
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn test_write_whitespace() {
+    let name = |x: &str| x.to_owned();
+    for (entity, expected) in vec![
+        (Entity::Object { name: name("") }, "o"),
+        (
+            Entity::Object {
+                name: name(" a  b "),
+            },
+            "o a b",
+        ),
+        (Entity::Mtllib { name: name("") }, "mtllib"),
+        (
+            Entity::Usemtl {
+                name: name("bronze\t"),
+            },
+            "usemtl bronze",
+        ),
+        (
+            Entity::Maplib {
+                name: name(" wood"),
+            },
+            "maplib wood",
+        ),
+        (Entity::Usemap { name: name("") }, "usemap"),
+        (
+            Entity::MergingGroup {
+                name: name("1  0.5"),
+            },
+            "mg 1 0.5",
+        ),
+        (
+            Entity::Group {
+                names: vec![name("a"), name(""), name(" b ")],
+            },
+            "g a b",
+        ),
+        (
+            Entity::Comment {
+                content: name("  indented  "),
+            },
+            "#   indented",
+        ),
+        (Entity::Comment { content: name(" ") }, "#"),
+        (
+            Entity::Unknown {
+                raw: name("bevel on \r"),
+            },
+            "bevel on",
+        ),
+    ] {
+        let mut result = Vec::new();
+        FormatWriter::write(&mut result, &entity).unwrap();
+        assert_eq!(expected, String::from_utf8(result).unwrap());
+    }
+}
+
+#[test]
+fn test_write_multi_line_comment() {
+    let entity = Entity::Comment {
+        content: "first \n\nthird\n".to_owned(),
+    };
+    let options = FormatOptions {
+        line_ending: LineEnding::CrLf,
+        ..Default::default()
+    };
+    let mut result = Vec::new();
+    FormatWriter::write_with(&mut result, &entity, &options).unwrap();
+    assert_eq!(
+        "# first\r\n#\r\n# third\r\n",
+        String::from_utf8(result).unwrap()
+    );
 }
@@ -0,0 +1,97 @@
+extern crate wavefront_rs;
+use std::io::{BufReader, Write};
+use wavefront_rs::obj::entity::*;
+use wavefront_rs::obj::format_writer::FormatWriter;
+use wavefront_rs::obj::model::ObjModel;
+use wavefront_rs::obj::reader::read_all;
+use wavefront_rs::obj::writer::*;
+
+struct FailingWriter {}
+
+impl Write for FailingWriter {
+    fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+        Err(std::io::Error::other("failed"))
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_write_all() {
+    let entities = vec![
+        Entity::Object {
+            name: "token".to_owned(),
+        },
+        Entity::Vertex {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+            w: None,
+            color: None,
+        },
+        Entity::Point { vertices: vec![1] },
+    ];
+    let mut result = Vec::new();
+    write_all(&mut result, &entities).unwrap();
+    assert_eq!(
+        "o token\nv 1 2 3\np 1\n",
+        String::from_utf8(result.clone()).unwrap()
+    );
+    assert_eq!(
+        entities,
+        read_all(&mut BufReader::new(result.as_slice())).unwrap()
+    );
+}
+
+#[test]
+fn test_write_all_empty() {
+    let mut result = Vec::new();
+    write_all(&mut result, &[]).unwrap();
+    assert!(result.is_empty());
+}
+
+#[test]
+fn test_write_all_error() {
+    let entities = vec![Entity::End];
+    assert!(write_all(&mut FailingWriter {}, &entities).is_err());
+}
+
+#[test]
+fn test_write_model_by_material() {
+    let source = "v 0 0 0\nusemtl a\nf 1 1 1\nusemtl b\nf 1 1 1\nusemtl a\nf 1 1 1\n";
+    let model = ObjModel::from_entities(&read_all(&mut BufReader::new(source.as_bytes())).unwrap());
+    let mut result = Vec::new();
+    write_model_by_material(&mut result, &model).unwrap();
+    assert_eq!(
+        "v 0 0 0\nusemtl a\nf 1 1 1\nf 1 1 1\nusemtl b\nf 1 1 1\n",
+        String::from_utf8(result).unwrap()
+    );
+}
+
+#[test]
+fn test_format_writer_error() {
+    let entity = Entity::Object {
+        name: "token".to_owned(),
+    };
+    let error = FormatWriter::write(&mut FailingWriter {}, &entity).unwrap_err();
+    assert_eq!("failed", error.to_string());
+    assert!(FormatWriter::write_line(&mut FailingWriter {}, &entity).is_err());
+}
+
+#[test]
+fn test_write_iter() {
+    let source = "v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n";
+    let entities = read_all(&mut BufReader::new(source.as_bytes())).unwrap();
+    let mut result = Vec::new();
+    write_iter(&mut result, entities.clone()).unwrap();
+    assert_eq!(source, String::from_utf8(result).unwrap());
+
+    let mut result = Vec::new();
+    let reader = wavefront_rs::obj::reader::EntityIterator::new(BufReader::new(source.as_bytes()));
+    write_iter(&mut result, reader.map(|x| x.unwrap())).unwrap();
+    assert_eq!(source, String::from_utf8(result).unwrap());
+
+    assert!(write_iter(&mut FailingWriter {}, entities).is_err());
+}
@@ -4,9 +4,40 @@ mod obj {
     }
 
     mod unit {
+        mod common;
+        #[cfg(feature = "tokio")]
+        mod test_async_reader;
+        mod test_diff;
         mod test_entity;
+        mod test_export;
+        mod test_filter;
+        mod test_geometry;
+        mod test_model;
+        mod test_optimize;
         mod test_read_lexer;
+        mod test_reader;
+        mod test_stats;
         mod test_string_lexer;
+        mod test_transform;
+        mod test_validate;
+        mod test_writer;
+        mod test_writer_document;
+    }
+}
+
+mod mtl {
+    mod integration {
+        mod integration_tests;
+    }
+
+    mod unit {
+        mod common;
+        mod test_entity;
+        mod test_model;
+        mod test_read_lexer;
+        mod test_reader;
+        mod test_util;
+        mod test_validate;
         mod test_writer;
     }
 }